@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+
+use firefly_ast::{value::Closure, BindingKind};
+use firefly_hir::{
+    resolve::{Symbol, SymbolTable},
+    stmt::Local,
+    ty::{Ty, TyKind},
+    value::{ClosureValue, Parameter, Value, ValueKind},
+    Entity, Id,
+};
+use firefly_span::Span;
+
+use crate::{labels::LabelStack, throws::ErrorHandler, AstLowerer};
+
+/// The locals a closure declares, and the
+/// locals from outside of it that it uses
+#[derive(Default)]
+pub struct ClosureCaptures {
+    declared: HashSet<Id<Local>>,
+    captures: Vec<Id<Local>>,
+}
+
+/// Tracks the closures that are being lowered,
+/// from the outermost to the innermost
+pub struct CaptureStack {
+    stack: Vec<ClosureCaptures>,
+}
+
+impl CaptureStack {
+    pub fn new() -> Self {
+        Self { stack: Vec::new() }
+    }
+
+    pub fn push(&mut self) {
+        self.stack.push(ClosureCaptures::default());
+    }
+
+    pub fn pop(&mut self) -> Option<ClosureCaptures> {
+        self.stack.pop()
+    }
+
+    /// Records a local declared by the innermost closure
+    pub fn declare(&mut self, local: Id<Local>) {
+        if let Some(closure) = self.stack.last_mut() {
+            closure.declared.insert(local);
+        }
+    }
+
+    /// Records a use of a local, which is captured by every
+    /// closure between the use and where it was declared
+    pub fn use_local(&mut self, local: Id<Local>) {
+        for closure in self.stack.iter_mut().rev() {
+            if closure.declared.contains(&local) {
+                return;
+            }
+
+            if !closure.captures.contains(&local) {
+                closure.captures.push(local);
+            }
+        }
+    }
+
+    /// Whether a local is captured by the innermost closure
+    pub fn is_captured(&self, local: Id<Local>) -> bool {
+        self.stack.last().is_some_and(|closure| !closure.declared.contains(&local))
+    }
+}
+
+impl AstLowerer {
+    pub(crate) fn lower_closure(
+        &mut self,
+        closure: &Closure,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+        span: Span,
+    ) -> Value {
+        let return_ty = closure
+            .signature
+            .return_ty
+            .as_ref()
+            .map(|return_ty| self.lower_ty(return_ty, parent, symbol_table))
+            .unwrap_or_else(|| Ty::new_unspanned(TyKind::Unit));
+
+        self.capture_stack.push();
+        symbol_table.push_scope();
+
+        let mut params = Vec::new();
+        let mut param_tys = Vec::new();
+
+        for param in &closure.signature.params {
+            let ty = self.lower_ty(&param.item.ty, parent, symbol_table);
+            let name = self.lower_name(&param.item.name);
+
+            let local = self.create_local(parent, &name, &ty, BindingKind::Let);
+            self.context.add_component(local, Parameter);
+
+            let local_symbol = self
+                .context()
+                .cast_id::<Symbol>(local)
+                .expect("internal compiler error: local doesn't have a symbol");
+
+            symbol_table.insert(name.name.clone(), local_symbol);
+
+            params.push(local);
+            param_tys.push(ty);
+        }
+
+        // Loops outside of the closure can't be broken out of
+        // from inside it, returns return from the closure, and
+        // closures can't throw
+        let old_label_stack = std::mem::replace(&mut self.label_stack, LabelStack::new());
+        let old_return_ty = self.return_ty.replace(return_ty.clone());
+        let old_error_handler = std::mem::replace(&mut self.error_handler, ErrorHandler::Func(None));
+        let old_try_kind = self.try_kind.take();
+
+        let body = self.lower_code_block(&closure.body, parent, symbol_table);
+        self.check_func_body(body, &return_ty);
+
+        self.label_stack = old_label_stack;
+        self.return_ty = old_return_ty;
+        self.error_handler = old_error_handler;
+        self.try_kind = old_try_kind;
+
+        symbol_table.pop_scope();
+        let captures = self
+            .capture_stack
+            .pop()
+            .expect("internal compiler error: closure isn't being lowered")
+            .captures;
+
+        let ty = Ty::new(TyKind::Func(param_tys, Box::new(return_ty)), span);
+        let closure = ClosureValue { params, captures, body };
+
+        Value::new(ValueKind::Closure(Box::new(closure)), ty, span)
+    }
+
+    /// Records the local a value is read from, if any,
+    /// so closures can capture it
+    pub(crate) fn use_value(&mut self, value: &Value) {
+        if let Some(local) = root_local(value) {
+            self.capture_stack.use_local(local);
+        }
+    }
+}
+
+/// Finds the local a value is a part of
+pub(crate) fn root_local(value: &Value) -> Option<Id<Local>> {
+    match &value.kind {
+        ValueKind::Local(local) => Some(*local),
+        ValueKind::FieldOf(parent, _)
+        | ValueKind::TupleMember(parent, _)
+        | ValueKind::InstanceFunc(parent, _) => root_local(parent),
+
+        _ => None,
+    }
+}
+
@@ -1,14 +1,41 @@
 use firefly_ast::Name;
 use firefly_error_messages::DiagnosticMessage;
 use firefly_errors::diagnostic::{Diagnostic, DiagnosticId, Level};
-use firefly_hir::{HirContext, IntoDiagnostic};
+use firefly_span::Span;
+use firefly_hir::{func::Func, generics::GenericParameter, items::{Global, TypeAlias}, resolve::Symbol, ty::Ty, HirContext, Id, IntoDiagnostic};
+use itertools::Itertools;
 
 pub enum DeclarationError {
     GlobalVarNoDefault(Name),
+    CyclicGlobals(Vec<Id<Global>>),
+    EnumField(Name),
+    MissingRequirement {
+        requirement: Id<Func>,
+        span: Span,
+    },
+    RequirementSignature {
+        requirement: Id<Func>,
+        method: Id<Func>,
+    },
+    MissingAssociatedType {
+        associated: Id<GenericParameter>,
+        span: Span,
+    },
+    NotAProtocol(Ty),
+    RedeclaredType(Name, Span),
+    CyclicTypeAliases(Vec<Id<TypeAlias>>),
+    MutatingOutsideMethod(Span),
+    MutatingInClass(Span),
+
+    MissingMain,
+    MultipleMains(Vec<Id<Func>>),
+    MainParamType(Ty),
+    MainReturnType(Ty),
+    MainThrows(Ty),
 }
 
 impl IntoDiagnostic for DeclarationError {
-    fn into_diagnostic(&self, _: &HirContext) -> Diagnostic {
+    fn into_diagnostic(&self, context: &HirContext) -> Diagnostic {
         match self {
             DeclarationError::GlobalVarNoDefault(name) => {
                 Diagnostic::new(Level::Error,
@@ -16,6 +43,146 @@ impl IntoDiagnostic for DeclarationError {
                 ).with_error_code(DiagnosticId::new("E0601"))
                  .with_source(name.span)
             }
+            DeclarationError::CyclicGlobals(globals) => {
+                let names = globals
+                    .iter()
+                    .filter_map(|global| context.try_get::<Symbol>(*global))
+                    .map(|symbol| &symbol.name)
+                    .collect_vec();
+
+                // The cycle leads back to the first global
+                let cycle = names
+                    .iter()
+                    .chain(names.first())
+                    .map(|name| format!("`{}`", name.name))
+                    .join(" -> ");
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Cycle in the default values of globals: {cycle}"))
+                ).with_error_code(DiagnosticId::new("E0602"))
+                 .with_sources(&names.iter().map(|name| name.span).collect())
+            }
+            DeclarationError::EnumField(name) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Enums can't have stored field `{}`", name.item))
+                ).with_error_code(DiagnosticId::new("E0607"))
+                 .with_source(name.span)
+            }
+            DeclarationError::MissingRequirement { requirement, span } => {
+                let name = &context.try_get::<Symbol>(*requirement).expect("internal compiler error: method doesn't have a symbol").name;
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Missing method `{}` required by the protocol", name.name))
+                ).with_error_code(DiagnosticId::new("E0608"))
+                 .with_sources(&vec![*span, name.span])
+            }
+            DeclarationError::RequirementSignature { requirement, method } => {
+                let requirement = &context.try_get::<Symbol>(*requirement).expect("internal compiler error: method doesn't have a symbol").name;
+                let method = &context.try_get::<Symbol>(*method).expect("internal compiler error: method doesn't have a symbol").name;
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Method `{}` doesn't match the signature required by the protocol",
+                        method.name
+                    ))
+                ).with_error_code(DiagnosticId::new("E0609"))
+                 .with_sources(&vec![method.span, requirement.span])
+            }
+            DeclarationError::MissingAssociatedType { associated, span } => {
+                let name = &context.try_get::<Symbol>(*associated).expect("internal compiler error: associated type doesn't have a symbol").name;
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Missing type `{}` required by the protocol", name.name))
+                ).with_error_code(DiagnosticId::new("E0615"))
+                 .with_sources(&vec![*span, name.span])
+            }
+            DeclarationError::RedeclaredType(name, original) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Type `{}` is already declared", name.item))
+                ).with_error_code(DiagnosticId::new("E0616"))
+                 .with_sources(&vec![name.span, *original])
+            }
+            DeclarationError::NotAProtocol(ty) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("`{}` is not a protocol", ty.display(context)))
+                ).with_error_code(DiagnosticId::new("E0610"))
+                 .with_source(ty.span)
+            }
+            DeclarationError::CyclicTypeAliases(aliases) => {
+                let names = aliases
+                    .iter()
+                    .filter_map(|alias| context.try_get::<Symbol>(*alias))
+                    .map(|symbol| &symbol.name)
+                    .collect_vec();
+
+                // The cycle leads back to the first alias
+                let cycle = names
+                    .iter()
+                    .chain(names.first())
+                    .map(|name| format!("`{}`", name.name))
+                    .join(" -> ");
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Cycle in type aliases: {cycle}"))
+                ).with_error_code(DiagnosticId::new("E0611"))
+                 .with_sources(&names.iter().map(|name| name.span).collect())
+            }
+            DeclarationError::MissingMain => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("No `main` function found".to_string())
+                ).with_error_code(DiagnosticId::new("E0603"))
+            }
+            DeclarationError::MultipleMains(funcs) => {
+                let spans = funcs
+                    .iter()
+                    .filter_map(|func| context.try_get::<Symbol>(*func))
+                    .map(|symbol| symbol.name.span)
+                    .collect_vec();
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Found {} `main` functions", funcs.len()))
+                ).with_error_code(DiagnosticId::new("E0604"))
+                 .with_sources(&spans)
+            }
+            DeclarationError::MainParamType(ty) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Parameters of `main` must be of type `string`, found `{}`",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0605"))
+                 .with_source(ty.span)
+            }
+            DeclarationError::MainReturnType(ty) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "`main` must return `()` or `int`, found `{}`",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0606"))
+                 .with_source(ty.span)
+            }
+            DeclarationError::MainThrows(ty) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "`main` can't throw an error, found `throws {}`",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0612"))
+                 .with_source(ty.span)
+            }
+            DeclarationError::MutatingOutsideMethod(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Only instance methods can be `mutating`".to_string())
+                ).with_error_code(DiagnosticId::new("E0613"))
+                 .with_source(*span)
+            }
+            DeclarationError::MutatingInClass(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Methods of a class can't be `mutating`, since they can always change its fields".to_string())
+                ).with_error_code(DiagnosticId::new("E0614"))
+                 .with_source(*span)
+            }
         }
     }
 }
\ No newline at end of file
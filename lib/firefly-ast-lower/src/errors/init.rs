@@ -0,0 +1,68 @@
+use firefly_error_messages::DiagnosticMessage;
+use firefly_errors::diagnostic::{Diagnostic, DiagnosticId, Level};
+use firefly_hir::{items::Field, resolve::Symbol, HirContext, Id, IntoDiagnostic};
+use firefly_span::Span;
+use itertools::Itertools;
+
+pub enum InitError {
+    OutsideStruct(Span),
+    FieldNotInitialized {
+        field: Id<Field>,
+        span: Span,
+    },
+    SelfBeforeInitialized {
+        fields: Vec<Id<Field>>,
+        span: Span,
+    },
+    FieldBeforeInitialized {
+        field: Id<Field>,
+        span: Span,
+    },
+}
+
+impl IntoDiagnostic for InitError {
+    fn into_diagnostic(&self, context: &HirContext) -> Diagnostic {
+        let field_name = |field: &Id<Field>| {
+            &context
+                .try_get::<Symbol>(*field)
+                .expect("internal compiler error: field doesn't have a symbol")
+                .name
+        };
+
+        match self {
+            Self::OutsideStruct(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("`init` can only be declared in a struct".to_string())
+                ).with_error_code(DiagnosticId::new("E1001"))
+                 .with_source(*span)
+            }
+            Self::FieldNotInitialized { field, span } => {
+                let name = field_name(field);
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Field `{}` isn't initialized when the init returns", name.name))
+                ).with_error_code(DiagnosticId::new("E1002"))
+                 .with_sources(&vec![*span, name.span])
+            }
+            Self::SelfBeforeInitialized { fields, span } => {
+                let names = fields
+                    .iter()
+                    .map(|field| format!("`{}`", field_name(field).name))
+                    .join(", ");
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("`self` is used before all of its fields are initialized, missing {names}"))
+                ).with_error_code(DiagnosticId::new("E1003"))
+                 .with_source(*span)
+            }
+            Self::FieldBeforeInitialized { field, span } => {
+                let name = field_name(field);
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Field `{}` is read before it's initialized", name.name))
+                ).with_error_code(DiagnosticId::new("E1004"))
+                 .with_sources(&vec![*span, name.span])
+            }
+        }
+    }
+}
@@ -4,10 +4,16 @@ mod symbol;
 mod value;
 mod decl;
 mod ty;
+mod pattern;
+mod throws;
+mod init;
 
 pub use module::*;
 pub use string::*;
 pub use symbol::*;
 pub use value::*;
 pub use decl::*;
-pub use ty::*;
\ No newline at end of file
+pub use ty::*;
+pub use pattern::*;
+pub use throws::*;
+pub use init::*;
\ No newline at end of file
@@ -0,0 +1,117 @@
+use firefly_ast::Name;
+use firefly_error_messages::DiagnosticMessage;
+use firefly_errors::diagnostic::{Diagnostic, DiagnosticId, Level};
+use firefly_hir::{ty::Ty, HirContext, IntoDiagnostic};
+use firefly_span::Span;
+
+pub enum PatternError {
+    NonExhaustive {
+        missing: String,
+        span: Span,
+    },
+    UnreachableArm(Span),
+
+    TypeMismatch {
+        expected: Ty,
+        found: Ty,
+        span: Span,
+    },
+    TupleMismatch {
+        expected: Ty,
+        found: usize,
+        span: Span,
+    },
+    NotAnEnum {
+        expected: Ty,
+        span: Span,
+    },
+    NoSuchCase {
+        expected: Ty,
+        name: Name,
+    },
+    CaseValueCount {
+        case: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    NotAPattern(Span),
+    DuplicateBinding(Name),
+}
+
+impl IntoDiagnostic for PatternError {
+    fn into_diagnostic(&self, context: &HirContext) -> Diagnostic {
+        match self {
+            Self::NonExhaustive { missing, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Match is not exhaustive: `{missing}` is not covered"))
+                ).with_error_code(DiagnosticId::new("E0701"))
+                 .with_source(*span)
+            }
+            Self::UnreachableArm(span) => {
+                Diagnostic::new(Level::Warning,
+                    DiagnosticMessage::Str("Unreachable match arm: the arms before it match every value it does".to_string())
+                ).with_error_code(DiagnosticId::new("E0702"))
+                 .with_source(*span)
+            }
+            Self::TypeMismatch { expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched types in pattern: expected `{}`, found `{}`",
+                        expected.display(context),
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0703"))
+                 .with_source(*span)
+            }
+            Self::TupleMismatch { expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched types in pattern: expected `{}`, found a tuple of {found} values",
+                        expected.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0703"))
+                 .with_source(*span)
+            }
+            Self::NotAnEnum { expected, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Case patterns can only match enums, found `{}`",
+                        expected.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0704"))
+                 .with_source(*span)
+            }
+            Self::NoSuchCase { expected, name } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Enum `{}` has no case `{}`",
+                        expected.display(context),
+                        name.item
+                    ))
+                ).with_error_code(DiagnosticId::new("E0705"))
+                 .with_source(name.span)
+            }
+            Self::CaseValueCount { case, expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Case `{case}` carries {expected} values, but the pattern has {found}"
+                    ))
+                ).with_error_code(DiagnosticId::new("E0706"))
+                 .with_source(*span)
+            }
+            Self::NotAPattern(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Value can't be used as a pattern: expected an enum case or a literal".to_string())
+                ).with_error_code(DiagnosticId::new("E0707"))
+                 .with_source(*span)
+            }
+            Self::DuplicateBinding(name) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("`{}` is bound more than once in the same pattern", name.item))
+                ).with_error_code(DiagnosticId::new("E0708"))
+                 .with_source(name.span)
+            }
+        }
+    }
+}
@@ -1,8 +1,15 @@
 use firefly_ast::Name;
 use firefly_error_messages::DiagnosticMessage;
 use firefly_errors::diagnostic::{Diagnostic, DiagnosticId, Level};
-use firefly_hir::{value::Value, HirContext, IntoDiagnostic};
+use firefly_hir::{
+    func::Callable,
+    resolve::Symbol,
+    ty::TyKind,
+    value::{HasValue, Value},
+    HirContext, Id, IntoDiagnostic,
+};
 use firefly_span::Span;
+use itertools::Itertools;
 
 pub enum SymbolError {
     NotFound(Name),
@@ -15,13 +22,15 @@ pub enum SymbolError {
     NoMembersOf(Value),
     NoMemberOn(Name, Value),
     MemberNotAValue(Name, Span),
+    InstanceMemberWithoutSelf(Name, Span),
+    InitNotVisible(Name, Span),
 
-    NoMatchingSymbol(String, Vec<Span>),
-    AmbiguousSymbol(String, Vec<Span>),
+    NoMatchingSymbol(String, Span, Vec<Id<Symbol>>),
+    AmbiguousSymbol(String, Span, Vec<Id<Symbol>>),
 }
 
 impl IntoDiagnostic for SymbolError {
-    fn into_diagnostic(&self, _context: &HirContext) -> firefly_errors::diagnostic::Diagnostic {
+    fn into_diagnostic(&self, context: &HirContext) -> firefly_errors::diagnostic::Diagnostic {
         match self {
             SymbolError::NotFound(name) => Diagnostic::new(
                 Level::Error,
@@ -59,15 +68,16 @@ impl IntoDiagnostic for SymbolError {
             .with_source(*decl),
             SymbolError::NoMembersOf(value) => Diagnostic::new(
                 Level::Error,
-                DiagnosticMessage::Str(format!("value of type `{:?}` has no members", value.ty)),
+                DiagnosticMessage::Str(format!("value of type `{}` has no members", value.ty.display(context))),
             )
             .with_error_code(DiagnosticId::new("E0120"))
             .with_source(value.span),
-            SymbolError::NoMemberOn(name, _) => Diagnostic::new(
+            SymbolError::NoMemberOn(name, value) => Diagnostic::new(
                 Level::Error,
                 DiagnosticMessage::Str(format!(
-                    "member `{}` not found for value of type ``",
-                    name.item
+                    "member `{}` not found for value of type `{}`",
+                    name.item,
+                    value.ty.display(context)
                 )),
             )
             .with_error_code(DiagnosticId::new("E0121"))
@@ -79,18 +89,100 @@ impl IntoDiagnostic for SymbolError {
             .with_error_code(DiagnosticId::new("E0122"))
             .with_source(name.span)
             .with_source(*decl),
-            SymbolError::NoMatchingSymbol(predicate, spans) => Diagnostic::new(
+            SymbolError::InstanceMemberWithoutSelf(access, decl) => Diagnostic::new(
                 Level::Error,
-                DiagnosticMessage::Str(format!("no option found matching {}", predicate)),
+                DiagnosticMessage::Str(format!(
+                    "instance member `{}` can't be used here, since there is no `self`",
+                    access.item
+                )),
+            )
+            .with_error_code(DiagnosticId::new("E0125"))
+            .with_source(access.span)
+            .with_source(*decl),
+            SymbolError::InitNotVisible(access, decl) => Diagnostic::new(
+                Level::Error,
+                DiagnosticMessage::Str(format!(
+                    "the initializer of `{}` is private, so it can't be called here",
+                    access.item
+                )),
+            )
+            .with_error_code(DiagnosticId::new("E0126"))
+            .with_source(access.span)
+            .with_source(*decl),
+            SymbolError::NoMatchingSymbol(predicate, span, candidates) => Diagnostic::new(
+                Level::Error,
+                DiagnosticMessage::Str(format!(
+                    "no option found matching {}{}",
+                    predicate,
+                    format_candidates(candidates, context)
+                )),
             )
             .with_error_code(DiagnosticId::new("E0123"))
-            .with_sources(spans),
-            SymbolError::AmbiguousSymbol(predicate, spans) => Diagnostic::new(
+            .with_source(*span)
+            .with_sources(&candidate_spans(candidates, context)),
+            SymbolError::AmbiguousSymbol(predicate, span, candidates) => Diagnostic::new(
                 Level::Error,
-                DiagnosticMessage::Str(format!("ambiguous option found matching {}", predicate)),
+                DiagnosticMessage::Str(format!(
+                    "ambiguous option found matching {}{}",
+                    predicate,
+                    format_candidates(candidates, context)
+                )),
             )
             .with_error_code(DiagnosticId::new("E0124"))
-            .with_sources(spans),
+            .with_source(*span)
+            .with_sources(&candidate_spans(candidates, context)),
         }
     }
 }
+
+/// Lists the signatures of every candidate, one per line
+fn format_candidates(candidates: &[Id<Symbol>], context: &HirContext) -> String {
+    candidates
+        .iter()
+        .map(|candidate| format!("\n  candidate: {}", format_signature(*candidate, context)))
+        .join("")
+}
+
+fn candidate_spans(candidates: &[Id<Symbol>], context: &HirContext) -> Vec<Span> {
+    candidates
+        .iter()
+        .map(|candidate| context.get(*candidate).name.span)
+        .filter(|span| *span != Span::default())
+        .collect_vec()
+}
+
+/// Formats the signature of a symbol the way it was declared
+fn format_signature(symbol: Id<Symbol>, context: &HirContext) -> String {
+    let name = &context.get(symbol).name.name;
+
+    if let Some(callable) = context.try_get::<Callable>(symbol) {
+        let params = callable
+            .labels
+            .iter()
+            .zip(&callable.params)
+            .map(|(label, param)| match label {
+                Some(label) => format!(
+                    "{} {}: {}",
+                    label.name,
+                    param.bind_name.name,
+                    param.ty.display(context)
+                ),
+                None => format!("{}: {}", param.bind_name.name, param.ty.display(context)),
+            })
+            .join(", ");
+
+        return match callable.return_ty.kind {
+            TyKind::Unit => format!("func {name}({params})"),
+            _ => format!(
+                "func {name}({params}) -> {}",
+                callable.return_ty.display(context)
+            ),
+        };
+    }
+
+    if let Some(HasValue { value }) = context.try_get::<HasValue>(symbol) {
+        return format!("{name}: {}", value.ty.display(context));
+    }
+
+    name.clone()
+}
@@ -0,0 +1,63 @@
+use firefly_error_messages::DiagnosticMessage;
+use firefly_errors::diagnostic::{Diagnostic, DiagnosticId, Level};
+use firefly_hir::{ty::Ty, HirContext, IntoDiagnostic};
+use firefly_span::Span;
+
+pub enum ThrowError {
+    Unhandled {
+        ty: Ty,
+        span: Span,
+    },
+    ErrorType {
+        expected: Ty,
+        found: Ty,
+        span: Span,
+    },
+    MissingTry(Span),
+    ThrowingFuncValue(Span),
+    UnreachableCatch(Span),
+}
+
+impl IntoDiagnostic for ThrowError {
+    fn into_diagnostic(&self, context: &HirContext) -> Diagnostic {
+        match self {
+            Self::Unhandled { ty, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "An error of type `{}` can be thrown here, but the function doesn't throw and it isn't caught",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0801"))
+                 .with_source(*span)
+            }
+            Self::ErrorType { expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched error type: expected `{}`, found `{}`",
+                        expected.display(context),
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0802"))
+                 .with_source(*span)
+            }
+            Self::MissingTry(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Call to a function that can throw must be marked with `try` or `try!`".to_string())
+                ).with_error_code(DiagnosticId::new("E0803"))
+                 .with_source(*span)
+            }
+            Self::ThrowingFuncValue(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("A function that can throw can only be called, not used as a value".to_string())
+                ).with_error_code(DiagnosticId::new("E0804"))
+                 .with_source(*span)
+            }
+            Self::UnreachableCatch(span) => {
+                Diagnostic::new(Level::Warning,
+                    DiagnosticMessage::Str("Unreachable catch block: nothing in the `do` block throws an error".to_string())
+                ).with_error_code(DiagnosticId::new("E0805"))
+                 .with_source(*span)
+            }
+        }
+    }
+}
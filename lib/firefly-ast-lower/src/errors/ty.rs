@@ -1,14 +1,133 @@
 use firefly_error_messages::DiagnosticMessage;
 use firefly_errors::diagnostic::{Diagnostic, DiagnosticId, Level};
-use firefly_hir::IntoDiagnostic;
-use firefly_span::Span;
+use firefly_hir::{items::{Field, ProtocolDef, StructDef}, resolve::Symbol, ty::Ty, Id, IntoDiagnostic, Name};
+use firefly_span::{Span, Spanned};
+use itertools::Itertools;
 
 pub enum TypeError {
-    CantCall(Span)
+    CantCall(Span),
+
+    ArgumentCount {
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    ArgumentType {
+        expected: Ty,
+        found: Ty,
+        span: Span,
+        param: Option<Span>,
+    },
+
+    ReturnType {
+        expected: Ty,
+        found: Ty,
+        span: Span,
+    },
+    MissingReturn {
+        expected: Ty,
+        span: Span,
+    },
+
+    ConditionType {
+        found: Ty,
+        span: Span,
+    },
+    AssignType {
+        expected: Ty,
+        found: Ty,
+        span: Span,
+    },
+
+    LiteralOutOfRange {
+        ty: Ty,
+        span: Span,
+    },
+    OperatorNotDefined {
+        operator: &'static str,
+        methods: Vec<&'static str>,
+        ty: Ty,
+        span: Span,
+    },
+    MatchArmType {
+        expected: Ty,
+        found: Ty,
+        span: Span,
+    },
+
+    BreakType {
+        expected: Ty,
+        found: Ty,
+        span: Span,
+    },
+
+    NotIterable {
+        ty: Ty,
+        span: Span,
+    },
+    RangeBounds {
+        start: Ty,
+        end: Ty,
+        span: Span,
+    },
+
+    IfBranchType {
+        expected: Ty,
+        found: Ty,
+        span: Span,
+    },
+    IfWithoutElse {
+        ty: Ty,
+        span: Span,
+    },
+
+    GenericArgumentCount {
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    CantInferGenerics(Span),
+    DoesntConform {
+        ty: Ty,
+        protocol: Id<ProtocolDef>,
+        span: Span,
+    },
+    ProtocolWithAssociatedTypes {
+        protocol: Id<ProtocolDef>,
+        span: Span,
+    },
+
+    NotOptional {
+        ty: Ty,
+        span: Span,
+    },
+    OptionalMember {
+        ty: Ty,
+        span: Span,
+    },
+    CantInferNil(Span),
+    CoalesceType {
+        expected: Ty,
+        found: Ty,
+        span: Span,
+    },
+    MemberwiseArguments {
+        struct_def: Id<StructDef>,
+        span: Span,
+    },
+    NotIdentifiable {
+        operator: &'static str,
+        ty: Ty,
+        span: Span,
+    },
+    ArgumentLabel {
+        expected: Name,
+        found: Spanned<String>,
+    },
 }
 
 impl IntoDiagnostic for TypeError {
-    fn into_diagnostic(&self, _: &firefly_hir::HirContext) -> Diagnostic {
+    fn into_diagnostic(&self, context: &firefly_hir::HirContext) -> Diagnostic {
         match self {
             Self::CantCall(span) => {
                 Diagnostic::new(Level::Error,
@@ -16,6 +135,280 @@ impl IntoDiagnostic for TypeError {
                 ).with_error_code(DiagnosticId::new("E0501"))
                  .with_source(*span)
             }
+            Self::ArgumentCount { expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Expected {expected} arguments, found {found}"))
+                ).with_error_code(DiagnosticId::new("E0502"))
+                 .with_source(*span)
+            }
+            Self::ArgumentType { expected, found, span, param } => {
+                let diag = Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched argument type: expected `{}`, found `{}`",
+                        expected.display(context),
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0503"))
+                 .with_source(*span);
+
+                match param {
+                    Some(param) => diag.with_source(*param),
+                    None => diag,
+                }
+            }
+            Self::ReturnType { expected, found, span } => {
+                let diag = Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched return type: expected `{}`, found `{}`",
+                        expected.display(context),
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0504"))
+                 .with_source(*span);
+
+                with_declared_type(diag, expected)
+            }
+            Self::MissingReturn { expected, span } => {
+                let diag = Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Not all paths return a value of type `{}`",
+                        expected.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0505"))
+                 .with_source(*span);
+
+                with_declared_type(diag, expected)
+            }
+            Self::ConditionType { found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Condition must be of type `bool`, found `{}`",
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0506"))
+                 .with_source(*span)
+            }
+            Self::AssignType { expected, found, span } => {
+                let diag = Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched types in assignment: expected `{}`, found `{}`",
+                        expected.display(context),
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0507"))
+                 .with_source(*span);
+
+                with_declared_type(diag, expected)
+            }
+            Self::LiteralOutOfRange { ty, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Integer literal out of range for `{}`",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0508"))
+                 .with_source(*span)
+            }
+            Self::OperatorNotDefined { operator, methods, ty, span } => {
+                let methods = methods.iter().map(|method| format!("`{method}`")).join(" or ");
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Operator `{operator}` is not defined for `{}`: no method named {methods}",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0509"))
+                 .with_source(*span)
+            }
+            Self::MatchArmType { expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched types in match arms: expected `{}`, found `{}`",
+                        expected.display(context),
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0510"))
+                 .with_source(*span)
+            }
+            Self::BreakType { expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched types in break: expected `{}`, found `{}`",
+                        expected.display(context),
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0513"))
+                 .with_source(*span)
+            }
+            Self::NotIterable { ty, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Can't loop over a value of type `{}`",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0511"))
+                 .with_source(*span)
+            }
+            Self::RangeBounds { start, end, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Range bounds must be integers of the same type, found `{}` and `{}`",
+                        start.display(context),
+                        end.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0512"))
+                 .with_source(*span)
+            }
+            Self::IfBranchType { expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched types in if branches: expected `{}`, found `{}`",
+                        expected.display(context),
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0514"))
+                 .with_source(*span)
+            }
+            Self::IfWithoutElse { ty, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "An if without an else can't have a value of type `{}`",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0515"))
+                 .with_source(*span)
+            }
+            Self::GenericArgumentCount { expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Expected {expected} generic arguments, found {found}"))
+                ).with_error_code(DiagnosticId::new("E0516"))
+                 .with_source(*span)
+            }
+            Self::CantInferGenerics(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Can't infer the generic arguments, they have to be written out".to_string())
+                ).with_error_code(DiagnosticId::new("E0517"))
+                 .with_source(*span)
+            }
+            Self::DoesntConform { ty, protocol, span } => {
+                let protocol_name = context
+                    .try_get::<Symbol>(*protocol)
+                    .map(|symbol| symbol.name.name.clone())
+                    .unwrap_or_default();
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Type `{}` doesn't conform to protocol `{protocol_name}`",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0518"))
+                 .with_source(*span)
+            }
+            Self::NotOptional { ty, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Expected an optional, found a value of type `{}`",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0519"))
+                 .with_source(*span)
+            }
+            Self::OptionalMember { ty, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "A value of optional type `{}` must be unwrapped to use its members, with `?.` or `!`",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0520"))
+                 .with_source(*span)
+            }
+            Self::CoalesceType { expected, found, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Mismatched types in `??`: expected `{}`, found `{}`",
+                        expected.display(context),
+                        found.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0521"))
+                 .with_source(*span)
+            }
+            Self::MemberwiseArguments { struct_def, span } => {
+                let name_of = |id| {
+                    context
+                        .try_get::<Symbol>(id)
+                        .map(|symbol| symbol.name.name.clone())
+                        .unwrap_or_default()
+                };
+
+                // Fields with defaults are optional, and labeled
+                let params = StructDef::fields(*struct_def, context)
+                    .into_iter()
+                    .map(|field| {
+                        let Field { ty, has_default, .. } = context.get(field);
+
+                        match has_default {
+                            true => format!("[{}: {}]", name_of(field.as_base()), ty.display(context)),
+                            false => format!("{}", ty.display(context)),
+                        }
+                    })
+                    .join(", ");
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Arguments don't match the memberwise initializer of `{}`, which takes `({params})`",
+                        name_of(struct_def.as_base())
+                    ))
+                ).with_error_code(DiagnosticId::new("E0522"))
+                 .with_source(*span)
+            }
+            Self::NotIdentifiable { operator, ty, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Operator `{operator}` compares instances of classes, but `{}` isn't a class",
+                        ty.display(context)
+                    ))
+                ).with_error_code(DiagnosticId::new("E0523"))
+                 .with_source(*span)
+            }
+            Self::ArgumentLabel { expected, found } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Expected argument label `{}`, found `{}`",
+                        expected.name,
+                        found.item
+                    ))
+                ).with_error_code(DiagnosticId::new("E0525"))
+                 .with_source(found.span)
+                 .with_source(expected.span)
+            }
+            Self::ProtocolWithAssociatedTypes { protocol, span } => {
+                let protocol_name = context
+                    .try_get::<Symbol>(*protocol)
+                    .map(|symbol| symbol.name.name.clone())
+                    .unwrap_or_default();
+
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Protocol `{protocol_name}` has associated types, so it can only be used as a generic bound"
+                    ))
+                ).with_error_code(DiagnosticId::new("E0526"))
+                 .with_source(*span)
+            }
+            Self::CantInferNil(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Can't infer the type of `nil`, the variable needs a type annotation like `int?`".to_string())
+                ).with_error_code(DiagnosticId::new("E0527"))
+                 .with_source(*span)
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Points to where a type was declared, if it was written in the source
+fn with_declared_type(diag: Diagnostic, ty: &Ty) -> Diagnostic {
+    if ty.span == Span::default() {
+        return diag;
+    }
+
+    diag.with_source(ty.span)
+}
@@ -1,7 +1,7 @@
 use firefly_ast::Name;
 use firefly_error_messages::DiagnosticMessage;
 use firefly_errors::diagnostic::{Diagnostic, DiagnosticId, Level};
-use firefly_hir::IntoDiagnostic;
+use firefly_hir::{IntoDiagnostic, Name as HirName};
 use firefly_span::Span;
 
 pub enum ValueError {
@@ -9,8 +9,22 @@ pub enum ValueError {
     UndefinedBreakLabel(Name),
     ContinueOutsideLoop(Span),
     UndefinedContinueLabel(Name),
+    BreakWithValue(Span),
 
     NotMutable(Span),
+    ImmutableBinding {
+        name: HirName,
+        span: Span,
+    },
+    ImmutableParameter {
+        name: HirName,
+        span: Span,
+    },
+    ImmutableSelf(Span),
+    MutatingMethodValue(Span),
+    AssignToCapture(Span),
+
+    RangeOutsideFor(Span),
 }
 
 impl IntoDiagnostic for ValueError {
@@ -40,12 +54,56 @@ impl IntoDiagnostic for ValueError {
                 ).with_error_code(DiagnosticId::new("E0304"))
                  .with_source(name.span)
             }
+            ValueError::BreakWithValue(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Only `loop` can be broken out of with a value".to_string())
+                ).with_error_code(DiagnosticId::new("E0305"))
+                 .with_source(*span)
+            }
             ValueError::NotMutable(value) => {
                 Diagnostic::new(Level::Error,
                     DiagnosticMessage::Str(format!("Value is not mutable"))
                 ).with_error_code(DiagnosticId::new("E0310"))
                  .with_source(*value)
             }
+            ValueError::ImmutableBinding { name, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Can't change `{}`, which isn't declared with `var`", name.name))
+                ).with_error_code(DiagnosticId::new("E0312"))
+                 .with_source(*span)
+                 .with_source(name.span)
+            }
+            ValueError::ImmutableParameter { name, span } => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!("Can't change the parameter `{}`; copy it into a `var` to change it", name.name))
+                ).with_error_code(DiagnosticId::new("E0315"))
+                 .with_source(*span)
+                 .with_source(name.span)
+            }
+            ValueError::ImmutableSelf(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Can't change `self` in a method that isn't `mutating`".to_string())
+                ).with_error_code(DiagnosticId::new("E0313"))
+                 .with_source(*span)
+            }
+            ValueError::MutatingMethodValue(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("A `mutating` method can only be called, not used as a value".to_string())
+                ).with_error_code(DiagnosticId::new("E0314"))
+                 .with_source(*span)
+            }
+            ValueError::AssignToCapture(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Can't assign to a local captured by a closure".to_string())
+                ).with_error_code(DiagnosticId::new("E0311"))
+                 .with_source(*span)
+            }
+            ValueError::RangeOutsideFor(span) => {
+                Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str("Ranges can only be used in a `for` loop".to_string())
+                ).with_error_code(DiagnosticId::new("E0320"))
+                 .with_source(*span)
+            }
         }
     }
 }
\ No newline at end of file
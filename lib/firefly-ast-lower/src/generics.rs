@@ -0,0 +1,413 @@
+use std::collections::HashMap;
+
+use firefly_ast::{generics::GenericParam, item::Item, protocol_def::AssociatedType as AstAssociatedType, Path, PathSegment};
+use firefly_hir::{
+    generics::{GenericParameter, GenericParameterList, Substitution},
+    items::{conformance::{AssociatedType, Conformance}, ProtocolDef},
+    resolve::{Symbol, SymbolTable},
+    ty::{HasType, Ty, TyKind},
+    value::{Value, ValueKind},
+    Entity, HirContext, Id, Visibility,
+};
+use firefly_span::{Span, Spanned};
+use itertools::Itertools;
+
+use crate::{
+    errors::{DeclarationError, TypeError},
+    AstLowerer,
+};
+
+impl AstLowerer {
+    /// Creates the generic parameters declared by a function or struct,
+    /// which can be used as types inside of it
+    pub fn link_generics(&mut self, generics: &[GenericParam], item: Id<Entity>) {
+        if generics.is_empty() {
+            return;
+        }
+
+        for generic in generics {
+            let name = self.lower_name(&generic.name);
+            let ty = Ty::new(TyKind::Generic(generic.id), name.span);
+
+            self.context.create_with_parent(item, (
+                GenericParameter { id: generic.id, bounds: Vec::new() },
+                Symbol { name, visibility: Visibility::Local, is_static: true },
+                HasType { ty },
+            ));
+        }
+
+        let parameters = generics.iter().map(|generic| generic.id).collect_vec();
+        self.context.add_component(item, GenericParameterList { parameters });
+    }
+
+    /// Creates the associated types declared by a protocol,
+    /// which its requirements use like generic parameters
+    pub fn link_associated_types(&mut self, associated_types: &[Spanned<AstAssociatedType>], protocol: Id<Entity>) {
+        for Spanned { item: associated_type, .. } in associated_types {
+            let name = self.lower_name(&associated_type.name);
+            let ty = Ty::new(TyKind::Generic(associated_type.id), name.span);
+
+            self.context.create_with_parent(protocol, (
+                GenericParameter { id: associated_type.id, bounds: Vec::new() },
+                AssociatedType { id: associated_type.id, default: None },
+                Symbol { name, visibility: Visibility::Local, is_static: true },
+                HasType { ty },
+            ));
+        }
+    }
+
+    /// Resolves the types chosen for associated
+    /// types when a conforming type doesn't choose one
+    pub fn lower_associated_defaults(&mut self, associated_types: &[Spanned<AstAssociatedType>], protocol: Id<Entity>) {
+        let symbol_table = self
+            .context
+            .try_get_computed::<SymbolTable>(protocol)
+            .cloned()
+            .expect("internal compiler error: protocol is not a namespace");
+
+        for Spanned { item: associated_type, .. } in associated_types {
+            let Some(default) = &associated_type.default else {
+                continue;
+            };
+
+            let default = self.lower_ty(default, protocol, &symbol_table);
+
+            let id = self
+                .context
+                .cast_id::<AssociatedType>(associated_type.id.as_base())
+                .expect("internal compiler error: associated type wasn't linked");
+
+            self.context.get_mut(id).default = Some(default);
+        }
+    }
+
+    /// Adds the generic parameters declared by an item to a symbol table,
+    /// hiding any outer symbols with the same name
+    pub fn insert_generics(&self, generics: &[GenericParam], symbol_table: &mut SymbolTable) {
+        for generic in generics {
+            let symbol = self
+                .context
+                .cast_id::<Symbol>(generic.id.as_base())
+                .expect("internal compiler error: generic parameter doesn't have a symbol");
+
+            symbol_table.shadow(generic.name.item.clone(), symbol);
+        }
+    }
+
+    /// Resolves the bounds of generic parameters, and the
+    /// protocols each struct declares it conforms to
+    pub fn lower_constraints(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Func(Spanned { item, .. }) => {
+                    let parent = self.context.parent(item.id.as_base()).unwrap();
+                    let mut symbol_table = self
+                        .context
+                        .try_get_computed::<SymbolTable>(parent)
+                        .cloned()
+                        .expect("internal compiler error: parent is not a namespace");
+
+                    self.insert_generics(&item.generics, &mut symbol_table);
+                    self.lower_bounds(&item.generics, parent, &symbol_table);
+                }
+
+                Item::StructDef(Spanned { item, .. }) => {
+                    let parent = self.context.parent(item.id.as_base()).unwrap();
+                    let mut symbol_table = self
+                        .context
+                        .try_get_computed::<SymbolTable>(parent)
+                        .cloned()
+                        .expect("internal compiler error: parent is not a namespace");
+
+                    self.insert_generics(&item.generics, &mut symbol_table);
+                    self.lower_bounds(&item.generics, parent, &symbol_table);
+
+                    let protocols = item
+                        .protocols
+                        .iter()
+                        .filter_map(|path| self.resolve_protocol(path, parent, &symbol_table))
+                        .collect_vec();
+
+                    self.context.add_component(item.id, Conformance { protocols, witnesses: HashMap::new(), associated: HashMap::new() });
+                    self.lower_constraints(&item.items);
+                }
+
+                Item::EnumDef(Spanned { item, .. }) => self.lower_constraints(&item.items),
+
+                _ => {}
+            }
+        }
+    }
+
+    /// Resolves the protocols each generic parameter
+    /// declared by an item is bounded by
+    fn lower_bounds(&mut self, generics: &[GenericParam], parent: Id<Entity>, symbol_table: &SymbolTable) {
+        for generic in generics {
+            let bounds = generic
+                .bounds
+                .iter()
+                .filter_map(|path| self.resolve_protocol(path, parent, symbol_table))
+                .collect_vec();
+
+            self.context.get_mut(generic.id).bounds = bounds;
+        }
+    }
+
+    /// Resolves a path that has to name a protocol
+    fn resolve_protocol(&mut self, path: &Path, parent: Id<Entity>, symbol_table: &SymbolTable) -> Option<Id<ProtocolDef>> {
+        let ty = self.resolve_type(path, parent, symbol_table)?;
+
+        let TyKind::Protocol(protocol) = ty.kind else {
+            self.emit(DeclarationError::NotAProtocol(ty));
+            return None;
+        };
+
+        Some(protocol)
+    }
+
+    /// Lowers the generic arguments written after each segment of a path
+    pub fn lower_generic_args(
+        &mut self,
+        segments: &[PathSegment],
+        parent: Id<Entity>,
+        symbol_table: &SymbolTable,
+    ) -> (Vec<Ty>, Option<Span>) {
+        let generics = segments
+            .iter()
+            .flat_map(|segment| &segment.generics)
+            .collect_vec();
+
+        let span = generics
+            .iter()
+            .map(|generic| generic.span)
+            .reduce(|first, last| first.to(last));
+
+        let generics = generics
+            .into_iter()
+            .map(|generic| self.lower_ty(generic, parent, symbol_table))
+            .collect_vec();
+
+        (generics, span)
+    }
+
+    /// Gives a function or initializer the generic arguments
+    /// written after its name
+    pub fn apply_generics(&mut self, mut value: Value, generics: Vec<Ty>, span: Option<Span>) -> Option<Value> {
+        let Some(span) = span else {
+            return Some(value);
+        };
+
+        let missing = self.missing_generics(&value).unwrap_or_default();
+
+        if missing.len() != generics.len() {
+            self.emit(TypeError::GenericArgumentCount {
+                expected: missing.len(),
+                found: generics.len(),
+                span,
+            });
+
+            return None;
+        }
+
+        if !self.check_bounds(&missing, &generics, span) {
+            return None;
+        }
+
+        complete_generics(&mut value, &missing, generics, &self.context);
+
+        Some(value)
+    }
+
+    /// Gives a generic struct the generic arguments written after its name
+    pub fn apply_type_generics(&mut self, ty: Ty, generics: Vec<Ty>, span: Span) -> Option<Ty> {
+        let TyKind::StructDef(struct_def, _) = &ty.kind else {
+            if !generics.is_empty() {
+                self.emit(TypeError::GenericArgumentCount { expected: 0, found: generics.len(), span });
+                return None;
+            }
+
+            return Some(ty);
+        };
+
+        let parameters = self
+            .context
+            .try_get::<GenericParameterList>(*struct_def)
+            .map(|list| list.parameters.clone())
+            .unwrap_or_default();
+
+        if parameters.len() != generics.len() {
+            self.emit(TypeError::GenericArgumentCount {
+                expected: parameters.len(),
+                found: generics.len(),
+                span,
+            });
+            return None;
+        }
+
+        if !self.check_bounds(&parameters, &generics, span) {
+            return None;
+        }
+
+        Some(ty.substitute(&Substitution::new(&parameters, &generics)).normalize(&self.context))
+    }
+
+    /// Infers the generic arguments of a function or initializer
+    /// that weren't written out from the arguments it is called with
+    pub fn infer_generics(&mut self, mut value: Value, args: &[Value]) -> Value {
+        let Some(missing) = self.missing_generics(&value) else {
+            return value;
+        };
+
+        if missing.is_empty() {
+            return value;
+        }
+
+        let TyKind::Func(params, _) = &value.ty.kind else {
+            return value;
+        };
+
+        let mut substitution = Substitution::default();
+
+        for (param, arg) in params.iter().zip(args) {
+            substitution.unify(param, &arg.ty, &missing);
+        }
+
+        let generics = missing
+            .iter()
+            .map(|parameter| substitution.get(*parameter).cloned())
+            .collect::<Option<Vec<_>>>();
+
+        let Some(generics) = generics else {
+            // An argument failed to lower, and an error
+            // has already been reported
+            if args.iter().any(|arg| matches!(arg.ty.kind, TyKind::Never)) {
+                return Value::error(value.span);
+            }
+
+            self.emit(TypeError::CantInferGenerics(value.span));
+            return Value::error(value.span);
+        };
+
+        if !self.check_bounds(&missing, &generics, value.span) {
+            return Value::error(value.span);
+        }
+
+        complete_generics(&mut value, &missing, generics, &self.context);
+
+        value
+    }
+
+    /// Checks that a function or initializer that isn't being
+    /// called has an argument for every generic parameter
+    pub fn require_generics(&mut self, value: Value) -> Value {
+        if self.missing_generics(&value).is_some_and(|missing| !missing.is_empty()) {
+            self.emit(TypeError::CantInferGenerics(value.span));
+            return Value::error(value.span);
+        }
+
+        value
+    }
+
+    /// Replaces the generic parameters of a struct in the types
+    /// of its members with the generic arguments of an instance.
+    ///
+    /// The requirements called on a value of a generic type use
+    /// the associated types chosen by the type, like `T.Item`
+    pub fn receiver_substitution(&self, receiver: &Value) -> Substitution {
+        match &receiver.ty.kind {
+            TyKind::StructDef(struct_def, generics) => {
+                let parameters = GenericParameterList::in_scope(struct_def.as_base(), &self.context);
+
+                Substitution::new(&parameters, generics)
+            }
+
+            TyKind::Generic(parameter) => {
+                let (parameters, generics): (Vec<_>, Vec<_>) = self
+                    .context
+                    .get(*parameter)
+                    .bounds
+                    .iter()
+                    .flat_map(|protocol| ProtocolDef::associated_types(*protocol, &self.context))
+                    .map(|associated| {
+                        let kind = TyKind::Associated(Box::new(receiver.ty.clone()), associated);
+
+                        (associated, Ty::new(kind, receiver.ty.span))
+                    })
+                    .unzip();
+
+                Substitution::new(&parameters, &generics)
+            }
+
+            _ => Substitution::default(),
+        }
+    }
+
+    /// Resolves an associated type of the protocols
+    /// a generic parameter is bounded by, like `T.Item`
+    pub fn resolve_associated_type(&self, base: Id<Entity>, segment: &PathSegment, span: Span) -> Option<Ty> {
+        let parameter = self.context.cast_id::<GenericParameter>(base)?;
+        let base = Ty::new(TyKind::Generic(parameter), span);
+
+        let associated = self
+            .context
+            .get(parameter)
+            .bounds
+            .iter()
+            .flat_map(|protocol| ProtocolDef::associated_types(*protocol, &self.context))
+            .find(|associated| {
+                self.context
+                    .try_get::<Symbol>(*associated)
+                    .is_some_and(|symbol| symbol.name.name == segment.name.item)
+            })?;
+
+        Some(Ty::new(TyKind::Associated(Box::new(base), associated), span))
+    }
+
+    /// Checks that each generic argument conforms to
+    /// the protocols its parameter is bounded by
+    fn check_bounds(&mut self, parameters: &[Id<GenericParameter>], generics: &[Ty], span: Span) -> bool {
+        let mut conforms = true;
+
+        for (parameter, generic) in parameters.iter().zip(generics) {
+            let bounds = self.context.get(*parameter).bounds.clone();
+
+            for protocol in bounds {
+                if !Conformance::conforms_to(generic, protocol, &self.context) {
+                    self.emit(TypeError::DoesntConform { ty: generic.clone(), protocol, span });
+                    conforms = false;
+                }
+            }
+        }
+
+        conforms
+    }
+
+    /// Finds the generic parameters a value still needs arguments for,
+    /// or `None` if the value isn't polymorphic
+    fn missing_generics(&self, value: &Value) -> Option<Vec<Id<GenericParameter>>> {
+        match &value.kind {
+            ValueKind::StaticFunc(func) | ValueKind::InstanceFunc(_, func) => {
+                Some(func.missing(&self.context))
+            }
+            ValueKind::InitFor(struct_def) => Some(struct_def.missing(&self.context)),
+
+            _ => None,
+        }
+    }
+}
+
+fn complete_generics(value: &mut Value, missing: &[Id<GenericParameter>], generics: Vec<Ty>, context: &HirContext) {
+    let substitution = Substitution::new(missing, &generics);
+
+    match &mut value.kind {
+        ValueKind::StaticFunc(func) | ValueKind::InstanceFunc(_, func) => {
+            func.generics.extend(generics)
+        }
+        ValueKind::InitFor(struct_def) => struct_def.generics.extend(generics),
+
+        _ => return,
+    }
+
+    // Associated types of the generic arguments are known now
+    value.ty = value.ty.substitute(&substitution).normalize(context);
+}
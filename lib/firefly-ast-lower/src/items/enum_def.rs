@@ -0,0 +1,104 @@
+use crate::{AstLowerer, Lower, SymbolDesc};
+use firefly_ast::{
+    enum_def::{EnumCase as AstEnumCase, EnumDef as AstEnum},
+    Visibility,
+};
+use firefly_hir::{
+    items::{EnumCase as HirEnumCase, EnumDef as HirEnumDef},
+    resolve::SymbolTable,
+    ty::{Ty, TyKind},
+    value::{HasValue, Value, ValueKind},
+    Entity, Id,
+};
+use firefly_span::Spanned;
+use itertools::Itertools;
+
+impl Lower for AstEnum {
+    fn id(&self) -> Id<Entity> {
+        self.id.as_base()
+    }
+
+    fn get_symbol(&self) -> Option<SymbolDesc> {
+        let name = self.name.clone();
+        let visibility = self.visibility.clone();
+
+        Some(SymbolDesc { name, visibility, static_kw: None })
+    }
+
+    fn get_type(&self) -> Option<Ty> {
+        Some(Ty::new_unspanned(TyKind::EnumDef(self.id)))
+    }
+
+    fn lower_def(&self, _: Id<Entity>, lowerer: &mut AstLowerer) {
+        lowerer.context_mut().create(HirEnumDef { id: self.id });
+
+        for case in &self.cases {
+            case.item.lower_def(self.id(), lowerer);
+        }
+    }
+
+    fn lower_code(&self, _: Id<Entity>, _: &mut AstLowerer) { }
+}
+
+impl Lower for AstEnumCase {
+    fn id(&self) -> Id<Entity> {
+        self.id.as_base()
+    }
+
+    fn get_symbol(&self) -> Option<SymbolDesc> {
+        let name = self.name.clone();
+
+        // Cases are visible wherever their enum is
+        let visibility = Some(Spanned::new(Visibility::Public, name.span));
+
+        Some(SymbolDesc { name, visibility, static_kw: None })
+    }
+
+    fn lower_def(&self, parent: Id<Entity>, lowerer: &mut AstLowerer) {
+        let Some(symbol_table) = lowerer
+            .context_mut()
+            .try_get_computed::<SymbolTable>(parent)
+            .cloned()
+        else {
+            panic!("internal compiler error: parent is not a namespace")
+        };
+
+        let enum_def = lowerer
+            .context()
+            .cast_id::<HirEnumDef>(parent)
+            .expect("internal compiler error: case is not in an enum");
+
+        let fields = self
+            .params
+            .iter()
+            .map(|param| {
+                let name = lowerer.lower_name(&param.item.name);
+                let ty = lowerer.lower_ty(&param.item.ty, parent, &symbol_table);
+
+                (name, ty)
+            })
+            .collect_vec();
+
+        let enum_ty = Ty::new_unspanned(TyKind::EnumDef(enum_def));
+        let param_tys = fields.iter().map(|(_, ty)| ty.clone()).collect_vec();
+        let init_ty = Ty::new_unspanned(TyKind::Func(param_tys, Box::new(enum_ty.clone())));
+        let init = Value::new(ValueKind::InitCase(self.id), init_ty, self.name.span);
+
+        // Cases without values are used without calling them
+        let value = match fields.is_empty() {
+            true => Value::new(ValueKind::Invoke(Box::new(init), vec![]), enum_ty, self.name.span),
+            false => init,
+        };
+
+        lowerer.context_mut().create((
+            HirEnumCase {
+                id: self.id,
+                index: self.index,
+                fields,
+            },
+            HasValue { value },
+        ));
+    }
+
+    fn lower_code(&self, _: Id<Entity>, _: &mut AstLowerer) { }
+}
@@ -1,20 +1,22 @@
-use crate::{AstLowerer, Lower, SymbolDesc};
-use firefly_ast::func::{
-    Func as AstFunc, FuncParam as AstFuncParam, FuncSignature as AstFuncSignature,
+use crate::{errors::DeclarationError, throws::ErrorHandler, AstLowerer, Lower, SymbolDesc};
+use firefly_ast::{
+    func::{Func as AstFunc, FuncParam as AstFuncParam, FuncSignature as AstFuncSignature},
+    BindingKind,
 };
 use firefly_hir::{
-    func::{Callable, Func as HirFunc, FuncParam as HirFuncParam},
+    func::{Callable, EntryPoint, Func as HirFunc, FuncParam as HirFuncParam},
+    generics::Monomorph,
     resolve::{Symbol, SymbolTable},
     stmt::Local,
     ty::{HasType, Ty, TyKind},
-    value::{HasSelf, HasValue, HasValueIn, Value, ValueKind},
+    value::{HasSelf, HasValue, HasValueIn, Mutable, Parameter, Value, ValueKind},
     Entity, Id, Name, Visibility,
 };
 use firefly_span::{Span, Spanned};
 use itertools::Itertools;
 
 impl AstLowerer {
-    fn lower_signature(
+    pub fn lower_signature(
         &mut self,
         signature: &AstFuncSignature,
         parent: Id<Entity>,
@@ -25,6 +27,10 @@ impl AstLowerer {
             .as_ref()
             .map(|return_ty| self.lower_ty(return_ty, parent, symbol_table))
             .unwrap_or_else(|| Ty::new_unspanned(TyKind::Unit));
+        let throws = signature
+            .throws
+            .as_ref()
+            .map(|throws| self.lower_ty(throws, parent, symbol_table));
         let params = signature
             .params
             .iter()
@@ -45,7 +51,9 @@ impl AstLowerer {
                 labels,
                 params,
                 return_ty,
+                throws,
                 receiver: Some(receiver),
+                mutating: false,
             };
         }
 
@@ -53,7 +61,9 @@ impl AstLowerer {
             labels,
             params,
             return_ty,
+            throws,
             receiver: None,
+            mutating: false,
         }
     }
 
@@ -66,13 +76,15 @@ impl AstLowerer {
         let ty = self.lower_ty(&param.item.ty, parent, symbol_table);
         let bind_name = self.lower_name(&param.item.name);
 
-        let id = self.create_local(parent, &bind_name, &ty);
+        let id = self.create_local(parent, &bind_name, &ty, BindingKind::Let);
+        self.context.add_component(id, Parameter);
 
         HirFuncParam { ty, bind_name, id }
     }
 
-    pub fn create_local(&mut self, parent: Id<Entity>, name: &Name, ty: &Ty) -> Id<Local> {
+    pub fn create_local(&mut self, parent: Id<Entity>, name: &Name, ty: &Ty, kind: BindingKind) -> Id<Local> {
         let local = Id::default();
+        self.capture_stack.declare(local);
 
         self.context.create_with_parent(
             parent,
@@ -90,7 +102,13 @@ impl AstLowerer {
                     value: Value::new(ValueKind::Local(local), ty.clone(), Default::default()),
                 },
             ),
-        )
+        );
+
+        if kind == BindingKind::Var {
+            self.context.add_component(local, Mutable);
+        }
+
+        local
     }
 }
 
@@ -112,7 +130,7 @@ impl Lower for AstFunc {
     }
 
     fn lower_def(&self, parent: Id<Entity>, lowerer: &mut AstLowerer) {
-        let Some(symbol_table) = lowerer
+        let Some(mut symbol_table) = lowerer
             .context_mut()
             .try_get_computed::<SymbolTable>(parent)
             .cloned()
@@ -120,11 +138,33 @@ impl Lower for AstFunc {
             panic!("internal compiler error: parent is not a namespace")
         };
 
-        let signature = lowerer.lower_signature(&self.signature, self.id.as_base(), &symbol_table);
+        // The signature can use the function's generic parameters
+        lowerer.insert_generics(&self.generics, &mut symbol_table);
+
+        let mut signature = lowerer.lower_signature(&self.signature, self.id.as_base(), &symbol_table);
+
+        // Static methods are called on the type, without a `self`
+        if self.static_kw.is_some() {
+            signature.receiver = None;
+        }
+
         let ty = signature.ty();
 
+        // Only methods have a `self` to change
+        if let Some(mutating_kw) = self.mutating_kw {
+            if self.static_kw.is_some() || signature.receiver.is_none() {
+                lowerer.emit(DeclarationError::MutatingOutsideMethod(mutating_kw));
+            } else {
+                signature.mutating = true;
+            }
+        }
+
         if let Some(receiver) = &signature.receiver {
-            let self_id = lowerer.create_local(self.id(), &Name::internal("self"), &receiver);
+            let self_kind = match signature.mutating {
+                true => BindingKind::Var,
+                false => BindingKind::Let,
+            };
+            let self_id = lowerer.create_local(self.id(), &Name::internal("self"), &receiver, self_kind);
 
             lowerer.context_mut().create((
                 HirFunc { id: self.id },
@@ -136,7 +176,7 @@ impl Lower for AstFunc {
                 signature,
             ));
         } else {
-            let value = Value::new(ValueKind::StaticFunc(self.id), ty, Span::default());
+            let value = Value::new(ValueKind::StaticFunc(Monomorph::new(self.id)), ty, Span::default());
 
             lowerer
                 .context_mut()
@@ -145,14 +185,12 @@ impl Lower for AstFunc {
     }
 
     fn lower_code(&self, _: Id<Entity>, lowerer: &mut AstLowerer) {
-        let old_self_value = if let Some(HasSelf { local, ty }) = lowerer.context().try_get(self.id)
-        {
-            let self_value = Value::new(ValueKind::Local(*local), ty.clone(), Span::default());
-
-            lowerer.self_value.replace(self_value)
-        } else {
-            None
-        };
+        // Only methods have a `self`
+        let self_value = lowerer
+            .context()
+            .try_get::<HasSelf>(self.id)
+            .map(|HasSelf { local, ty }| Value::new(ValueKind::Local(*local), ty.clone(), Span::default()));
+        let old_self_value = std::mem::replace(&mut lowerer.self_value, self_value);
 
         let mut code_symbol_table = lowerer
             .context_mut()
@@ -160,10 +198,58 @@ impl Lower for AstFunc {
             .cloned()
             .expect("internal compiler error: function is not a namespace");
 
-        lowerer.lower_code_block(&self.body, self.id.as_base(), &mut code_symbol_table);
+        let Callable { return_ty, throws, .. } = lowerer
+            .context()
+            .try_get::<Callable>(self.id)
+            .cloned()
+            .expect("internal compiler error: function doesn't have a signature");
+        let old_return_ty = lowerer.return_ty.replace(return_ty.clone());
+        let old_error_handler = std::mem::replace(&mut lowerer.error_handler, ErrorHandler::Func(throws));
+
+        let body = lowerer.lower_code_block(&self.body, self.id.as_base(), &mut code_symbol_table);
+
+        lowerer.check_func_body(body, &return_ty);
+        lowerer.return_ty = old_return_ty;
+        lowerer.error_handler = old_error_handler;
+
+        lowerer.self_value = old_self_value;
+    }
+}
+
+impl AstLowerer {
+    /// Checks that there is exactly one `main` function,
+    /// and that the program can start by calling it
+    pub fn check_entry_point(&mut self) {
+        let entry_point = EntryPoint::new(self.context());
+
+        let main = match &entry_point.candidates[..] {
+            [] => {
+                self.emit(DeclarationError::MissingMain);
+                return;
+            }
+            [main] => *main,
+            candidates => {
+                self.emit(DeclarationError::MultipleMains(candidates.to_vec()));
+                return;
+            }
+        };
+
+        let Some(Callable { params, return_ty, throws, .. }) = self.context().try_get(main).cloned() else {
+            return;
+        };
+
+        for param in params {
+            if !EntryPoint::is_valid_param(&param.ty) {
+                self.emit(DeclarationError::MainParamType(param.ty));
+            }
+        }
+
+        if !EntryPoint::is_valid_return(&return_ty) {
+            self.emit(DeclarationError::MainReturnType(return_ty));
+        }
 
-        if let Some(old_self_value) = old_self_value {
-            lowerer.self_value.replace(old_self_value);
+        if let Some(throws) = throws {
+            self.emit(DeclarationError::MainThrows(throws));
         }
     }
 }
@@ -1,8 +1,8 @@
-use firefly_ast::struct_def::Field;
+use firefly_ast::{struct_def::Field, BindingKind};
 use firefly_hir::{
-    items::{Field as HirField, Global, SourceFile},
+    items::{init_order::GlobalInitOrder, EnumDef, Field as HirField, Global, SourceFile},
     resolve::SymbolTable,
-    value::{HasValue, HasValueIn, Value, ValueKind},
+    value::{HasValue, HasValueIn, Mutable, Value, ValueKind},
     Entity, Id,
 };
 
@@ -43,25 +43,29 @@ impl Lower for Field {
 
             let value = Value::new(ValueKind::Global(id), ty.clone(), self.name.span);
             lowerer.context_mut().add_component(id, HasValue { value });
+
+            if self.kind == BindingKind::Var {
+                lowerer.context_mut().add_component(id, Mutable);
+            }
+        } else if lowerer.context().has::<EnumDef>(parent) {
+            lowerer.emit(DeclarationError::EnumField(self.name.clone()));
         } else {
             let id = unsafe { self.id.cast::<HirField>() };
 
-            lowerer.context_mut().create(HirField { id, ty });
+            lowerer.context_mut().create(HirField { id, ty, default: None, has_default: self.default.is_some() });
             lowerer
                 .context_mut()
                 .add_component(id, HasValueIn::Field(id));
+
+            if self.kind == BindingKind::Var {
+                lowerer.context_mut().add_component(id, Mutable);
+            }
         }
     }
 
     fn lower_code(&self, parent: Id<Entity>, lowerer: &mut AstLowerer) {
         let is_static = lowerer.context().has::<SourceFile>(parent) || self.static_kw.is_some();
 
-        if !is_static {
-            return;
-        }
-
-        let id = unsafe { self.id.cast::<Global>() };
-
         let Some(mut symbol_table) = lowerer
             .context_mut()
             .try_get_computed::<SymbolTable>(parent)
@@ -70,15 +74,24 @@ impl Lower for Field {
             panic!("internal compiler error: parent is not a namespace")
         };
 
+        if !is_static {
+            lowerer.lower_field_default(self, parent, &mut symbol_table);
+            return;
+        }
+
+        let id = unsafe { self.id.cast::<Global>() };
+
         let Some(default) = &self.default else {
             lowerer.emit(DeclarationError::GlobalVarNoDefault(self.name.clone()));
             return;
         };
 
         let ty = lowerer.lower_ty(&self.ty, parent, &symbol_table);
-        let default_value =
+        let mut default_value =
             lowerer.lower_value(&default, parent, &mut symbol_table, Default::default());
 
+        lowerer.check_assign(&ty, &mut default_value);
+
         lowerer.context_mut().create(Global {
             id,
             ty,
@@ -86,3 +99,34 @@ impl Lower for Field {
         });
     }
 }
+
+impl AstLowerer {
+    /// Lowers the default value of a stored field,
+    /// which it starts with when an `init` is called
+    fn lower_field_default(&mut self, field: &Field, parent: Id<Entity>, symbol_table: &mut SymbolTable) {
+        let Some(default) = &field.default else {
+            return;
+        };
+
+        let Some(id) = self.context().cast_id::<HirField>(field.id) else {
+            return;
+        };
+
+        let ty = self.context().get(id).ty.clone();
+        let mut default_value = self.lower_value(default, parent, symbol_table, Default::default());
+
+        self.check_assign(&ty, &mut default_value);
+
+        self.context_mut().get_mut(id).default = Some(default_value);
+    }
+
+    /// Globals are initialized before main, so their
+    /// default values can't depend on each other
+    pub fn check_global_cycles(&mut self) {
+        let init_order = GlobalInitOrder::new(self.context());
+
+        for cycle in init_order.cycles {
+            self.emit(DeclarationError::CyclicGlobals(cycle));
+        }
+    }
+}
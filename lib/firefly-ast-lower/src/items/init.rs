@@ -0,0 +1,430 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{errors::InitError, throws::ErrorHandler, AstLowerer, Lower, SymbolDesc};
+use firefly_ast::{
+    struct_def::{Init as AstInit, StructDef as AstStruct},
+    BindingKind,
+};
+use firefly_hir::{
+    func::{Callable, Func as HirFunc, Initializer},
+    generics::Monomorph,
+    items::Field,
+    resolve::SymbolTable,
+    stmt::{CodeBlock, Local, StmtKind},
+    ty::{HasType, Ty, TyKind},
+    value::{ElseValue, HasValue, IfValue, Value, ValueKind},
+    Entity, HirContext, Id, Name,
+};
+use firefly_span::{Span, Spanned};
+use itertools::Itertools;
+
+impl Lower for AstInit {
+    fn id(&self) -> Id<Entity> {
+        self.id.as_base()
+    }
+
+    fn get_symbol(&self) -> Option<SymbolDesc> {
+        let name = Spanned::new(Initializer::NAME.to_string(), self.init_kw);
+        let visibility = self.visibility.clone();
+
+        // Inits are called on the struct, not on an instance
+        let static_kw = Some(self.init_kw);
+
+        Some(SymbolDesc {
+            name,
+            visibility,
+            static_kw,
+        })
+    }
+
+    fn lower_def(&self, parent: Id<Entity>, lowerer: &mut AstLowerer) {
+        let struct_ty = match lowerer.context().try_get::<HasType>(parent) {
+            Some(HasType { ty: ty @ Ty { kind: TyKind::StructDef(..), .. } }) => ty.clone(),
+            _ => {
+                lowerer.emit(InitError::OutsideStruct(self.init_kw));
+                return;
+            }
+        };
+
+        let TyKind::StructDef(struct_def, _) = struct_ty.kind else {
+            unreachable!()
+        };
+
+        let Some(symbol_table) = lowerer
+            .context_mut()
+            .try_get_computed::<SymbolTable>(parent)
+            .cloned()
+        else {
+            panic!("internal compiler error: parent is not a namespace")
+        };
+
+        // An init returns the struct it creates,
+        // instead of taking it as a receiver
+        let mut signature = lowerer.lower_signature(&self.signature, self.id.as_base(), &symbol_table);
+        signature.receiver = None;
+        signature.return_ty = struct_ty.clone();
+
+        // `self` can be changed freely while it's being created
+        let local = lowerer.create_local(self.id(), &Name::internal("self"), &struct_ty, BindingKind::Var);
+        let value = Value::new(ValueKind::StaticFunc(Monomorph::new(self.id)), signature.ty(), Span::default());
+
+        lowerer.context_mut().create((
+            HirFunc { id: self.id },
+            HasValue { value },
+            Initializer { struct_def, local },
+            signature,
+        ));
+    }
+
+    fn lower_code(&self, _: Id<Entity>, lowerer: &mut AstLowerer) {
+        let Some(Initializer { local, .. }) = lowerer.context().try_get::<Initializer>(self.id).cloned() else {
+            return;
+        };
+
+        let Callable { return_ty, throws, .. } = lowerer
+            .context()
+            .try_get::<Callable>(self.id)
+            .cloned()
+            .expect("internal compiler error: init doesn't have a signature");
+
+        let self_value = Value::new(ValueKind::Local(local), return_ty, Span::default());
+        let old_self_value = lowerer.self_value.replace(self_value);
+
+        let mut code_symbol_table = lowerer
+            .context_mut()
+            .try_get_computed::<SymbolTable>(self.id)
+            .cloned()
+            .expect("internal compiler error: init is not a namespace");
+
+        // `return` in an init doesn't take a value, since it returns `self`
+        let unit = Ty::new_unspanned(TyKind::Unit);
+        let old_return_ty = lowerer.return_ty.replace(unit.clone());
+        let old_error_handler = std::mem::replace(&mut lowerer.error_handler, ErrorHandler::Func(throws));
+
+        let body = lowerer.lower_code_block(&self.body, self.id.as_base(), &mut code_symbol_table);
+
+        lowerer.check_func_body(body, &unit);
+        lowerer.return_ty = old_return_ty;
+        lowerer.error_handler = old_error_handler;
+        lowerer.self_value = old_self_value;
+    }
+}
+
+impl AstLowerer {
+    /// Checks that each init of a struct initializes every
+    /// field before it uses `self` or returns
+    pub fn check_initializers(&mut self, struct_def: &AstStruct) {
+        let fields = self
+            .context()
+            .children(struct_def.id.as_base())
+            .iter()
+            .filter_map(|child| self.context().cast_id::<Field>(*child))
+            .collect_vec();
+
+        // Fields with a default value start out initialized
+        let defaulted = fields
+            .iter()
+            .copied()
+            .filter(|field| self.context().get(*field).default.is_some())
+            .collect::<HashSet<_>>();
+
+        let inits = struct_def.items.iter().filter_map(|item| match item {
+            firefly_ast::item::Item::Init(Spanned { item, .. }) => Some(item),
+            _ => None,
+        });
+
+        for init in inits {
+            let Some(Initializer { local, .. }) = self.context().try_get::<Initializer>(init.id).cloned() else {
+                continue;
+            };
+
+            let Some(body) = self
+                .context()
+                .children(init.id.as_base())
+                .iter()
+                .find_map(|child| self.context().cast_id::<CodeBlock>(*child))
+            else {
+                continue;
+            };
+
+            let mut checker = InitChecker {
+                context: self.context(),
+                local,
+                fields: &fields,
+                breaks: HashMap::new(),
+            };
+
+            let mut assigned = Some(defaulted.clone());
+            checker.code_block(body, &mut assigned);
+            checker.returns(init.init_kw, &mut assigned);
+        }
+    }
+}
+
+/// The fields that are assigned on every path to a point
+/// in an init, or `None` if the point can't be reached
+type Assigned = Option<HashSet<Id<Field>>>;
+
+/// Follows the paths through the body of an init, keeping
+/// track of which fields of `self` have been assigned
+struct InitChecker<'a> {
+    context: &'a HirContext,
+    local: Id<Local>,
+    fields: &'a [Id<Field>],
+
+    /// The fields assigned on every `break` out of each loop body
+    breaks: HashMap<Id<CodeBlock>, Assigned>,
+}
+
+impl InitChecker<'_> {
+    fn value(&mut self, value: &Value, assigned: &mut Assigned) {
+        // Nothing is checked in code that can't be reached
+        if assigned.is_none() {
+            return;
+        }
+
+        match &value.kind {
+            ValueKind::Local(local) if *local == self.local => self.use_self(value.span, assigned),
+            ValueKind::FieldOf(parent, field) if self.is_self(parent) => {
+                self.read_field(*field, value.span, assigned)
+            }
+            ValueKind::Assign(place, new_value) => {
+                self.value(new_value, assigned);
+                self.assign(place, assigned);
+            }
+
+            ValueKind::Return(inner) => {
+                self.value(inner, assigned);
+                self.returns(value.span, assigned);
+            }
+            ValueKind::Throw(inner) => {
+                self.value(inner, assigned);
+                *assigned = None;
+            }
+            ValueKind::Break(code_block, inner) => {
+                if let Some(inner) = inner {
+                    self.value(inner, assigned);
+                }
+
+                self.break_out(*code_block, assigned);
+            }
+            ValueKind::Continue(_) => *assigned = None,
+
+            // Fields assigned in a value that might not be
+            // evaluated aren't assigned afterwards
+            ValueKind::Invoke(function, args) if is_short_circuit(function) => {
+                self.value(&args[0], assigned);
+                self.value(&args[1], &mut assigned.clone());
+            }
+            ValueKind::Coalesce(optional, default) => {
+                self.value(optional, assigned);
+                self.value(default, &mut assigned.clone());
+            }
+            ValueKind::OptionalChain(chain) => {
+                self.value(&chain.optional, assigned);
+                self.value(&chain.value, &mut assigned.clone());
+            }
+
+            // Loops might not run their body, or might
+            // break out of it before a field is assigned
+            ValueKind::While(while_value) => {
+                self.value(&while_value.condition, assigned);
+                self.code_block(while_value.body, &mut assigned.clone());
+            }
+            ValueKind::For(for_value) => {
+                self.value(&for_value.start, assigned);
+                self.value(&for_value.end, assigned);
+                self.code_block(for_value.body, &mut assigned.clone());
+            }
+            ValueKind::ForEach(for_each) => {
+                self.value(&for_each.sequence, assigned);
+                self.code_block(for_each.body, &mut assigned.clone());
+            }
+            // A `loop` is only left by breaking out of it
+            ValueKind::Loop(loop_value) => {
+                self.code_block(loop_value.body, &mut assigned.clone());
+
+                *assigned = self.breaks.remove(&loop_value.body).flatten();
+            }
+            ValueKind::Repeat(repeat_value) => {
+                let mut body_assigned = assigned.clone();
+                self.code_block(repeat_value.body, &mut body_assigned);
+                self.value(&repeat_value.condition, &mut body_assigned);
+            }
+
+            ValueKind::If(if_value) => self.if_value(if_value, assigned),
+            ValueKind::Match(match_value) => {
+                self.value(&match_value.scrutinee, assigned);
+
+                let mut after = None;
+
+                for arm in &match_value.arms {
+                    let mut arm_assigned = assigned.clone();
+                    self.code_block(arm.body, &mut arm_assigned);
+
+                    after = merge(after, arm_assigned);
+                }
+
+                *assigned = after;
+            }
+            ValueKind::Do(do_value) => {
+                // The body can throw before it assigns anything
+                let mut catch_assigned = assigned.clone();
+
+                self.code_block(do_value.body, assigned);
+                self.code_block(do_value.catch, &mut catch_assigned);
+
+                *assigned = merge(assigned.take(), catch_assigned);
+            }
+            ValueKind::Try(try_value) => self.value(&try_value.call, assigned),
+
+            // A closure that captures `self` keeps a copy of it
+            ValueKind::Closure(closure) => {
+                if closure.captures.contains(&self.local) {
+                    self.use_self(value.span, assigned);
+                }
+            }
+
+            ValueKind::Invoke(function, args) => {
+                self.value(function, assigned);
+                args.iter().for_each(|arg| self.value(arg, assigned));
+            }
+            ValueKind::InstanceFunc(receiver, _) => self.value(receiver, assigned),
+            ValueKind::Tuple(items) => items.iter().for_each(|item| self.value(item, assigned)),
+            ValueKind::TupleMember(parent, _) | ValueKind::FieldOf(parent, _) => self.value(parent, assigned),
+            ValueKind::Existential(inner) | ValueKind::Wrap(inner) | ValueKind::Unwrap(inner) => {
+                self.value(inner, assigned)
+            }
+
+            ValueKind::Unit
+            | ValueKind::Nil
+            | ValueKind::Literal(_)
+            | ValueKind::StaticFunc(_)
+            | ValueKind::InitFor(_)
+            | ValueKind::InitCase(_)
+            | ValueKind::FieldDefault(..)
+            | ValueKind::BuiltinFunc(_)
+            | ValueKind::Local(_)
+            | ValueKind::Global(_) => {}
+        }
+    }
+
+    fn if_value(&mut self, if_value: &IfValue, assigned: &mut Assigned) {
+        self.value(&if_value.condition, assigned);
+
+        let mut negative_assigned = assigned.clone();
+
+        self.code_block(if_value.positive, assigned);
+
+        match &if_value.negative {
+            Some(ElseValue::Else(negative)) => self.code_block(*negative, &mut negative_assigned),
+            Some(ElseValue::ElseIf(negative)) => self.if_value(negative, &mut negative_assigned),
+            None => {}
+        }
+
+        *assigned = merge(assigned.take(), negative_assigned);
+    }
+
+    fn code_block(&mut self, code_block: Id<CodeBlock>, assigned: &mut Assigned) {
+        let code_block = self.context.get(code_block);
+
+        for stmt in &code_block.stmts {
+            match &stmt.kind {
+                StmtKind::Value(value) => self.value(value, assigned),
+                StmtKind::Bind(_, _, _, value) => self.value(value, assigned),
+            }
+        }
+
+        if let Some(yields) = &code_block.yields {
+            self.value(yields, assigned);
+        }
+    }
+
+    /// Breaking out of a loop carries the assigned
+    /// fields to the code after it
+    fn break_out(&mut self, code_block: Id<CodeBlock>, assigned: &mut Assigned) {
+        let breaks = self.breaks.remove(&code_block).flatten();
+        self.breaks.insert(code_block, merge(breaks, assigned.take()));
+    }
+
+    /// Assigning to `self` or one of its fields initializes it
+    fn assign(&mut self, place: &Value, assigned: &mut Assigned) {
+        let Some(fields) = assigned else {
+            return;
+        };
+
+        match &place.kind {
+            ValueKind::Local(local) if *local == self.local => fields.extend(self.fields),
+            ValueKind::FieldOf(parent, field) if self.is_self(parent) => {
+                fields.insert(*field);
+            }
+
+            _ => self.value(place, assigned),
+        }
+    }
+
+    /// Reading a field needs it to be initialized
+    fn read_field(&mut self, field: Id<Field>, span: Span, assigned: &mut Assigned) {
+        let Some(fields) = assigned else {
+            return;
+        };
+
+        if fields.insert(field) {
+            self.context.emit(InitError::FieldBeforeInitialized { field, span });
+        }
+    }
+
+    /// Using `self` as a value needs every field to be initialized
+    fn use_self(&mut self, span: Span, assigned: &mut Assigned) {
+        let missing = self.missing(assigned);
+
+        if !missing.is_empty() {
+            self.context.emit(InitError::SelfBeforeInitialized { fields: missing, span });
+        }
+
+        // Only report each field once
+        if let Some(fields) = assigned {
+            fields.extend(self.fields);
+        }
+    }
+
+    /// Returning needs every field to be initialized
+    fn returns(&mut self, span: Span, assigned: &mut Assigned) {
+        for field in self.missing(assigned) {
+            self.context.emit(InitError::FieldNotInitialized { field, span });
+        }
+
+        *assigned = None;
+    }
+
+    fn missing(&self, assigned: &Assigned) -> Vec<Id<Field>> {
+        let Some(fields) = assigned else {
+            return Vec::new();
+        };
+
+        self.fields
+            .iter()
+            .copied()
+            .filter(|field| !fields.contains(field))
+            .collect()
+    }
+
+    fn is_self(&self, value: &Value) -> bool {
+        matches!(value.kind, ValueKind::Local(local) if local == self.local)
+    }
+}
+
+/// The fields assigned on both paths, where a
+/// path that can't be reached assigns everything
+fn merge(first: Assigned, second: Assigned) -> Assigned {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(first.intersection(&second).copied().collect()),
+        (first, None) => first,
+        (None, second) => second,
+    }
+}
+
+/// `&&` and `||` only evaluate their right operand if they need to
+fn is_short_circuit(function: &Value) -> bool {
+    matches!(function.kind, ValueKind::BuiltinFunc("logical_and" | "logical_or"))
+}
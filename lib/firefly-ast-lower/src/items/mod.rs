@@ -1,4 +1,8 @@
+mod enum_def;
 mod func;
 mod import;
 mod global;
+mod init;
+mod protocol_def;
+mod type_alias;
 mod struct_def;
\ No newline at end of file
@@ -0,0 +1,68 @@
+use crate::{AstLowerer, Lower, SymbolDesc};
+use firefly_ast::protocol_def::{ProtocolDef as AstProtocol, Requirement as AstRequirement};
+use firefly_hir::{
+    func::Func as HirFunc,
+    items::ProtocolDef as HirProtocolDef,
+    resolve::SymbolTable,
+    ty::{Ty, TyKind},
+    value::HasValueIn,
+    Entity, Id,
+};
+
+impl Lower for AstProtocol {
+    fn id(&self) -> Id<Entity> {
+        self.id.as_base()
+    }
+
+    fn get_symbol(&self) -> Option<SymbolDesc> {
+        let name = self.name.clone();
+        let visibility = self.visibility.clone();
+
+        Some(SymbolDesc { name, visibility, static_kw: None })
+    }
+
+    fn get_type(&self) -> Option<Ty> {
+        Some(Ty::new_unspanned(TyKind::Protocol(self.id)))
+    }
+
+    fn lower_def(&self, _: Id<Entity>, lowerer: &mut AstLowerer) {
+        lowerer.context_mut().create(HirProtocolDef { id: self.id });
+    }
+
+    fn lower_code(&self, _: Id<Entity>, _: &mut AstLowerer) {}
+}
+
+impl Lower for AstRequirement {
+    fn id(&self) -> Id<Entity> {
+        self.id.as_base()
+    }
+
+    fn get_symbol(&self) -> Option<SymbolDesc> {
+        let name = self.name.clone();
+
+        Some(SymbolDesc { name, visibility: None, static_kw: None })
+    }
+
+    fn lower_def(&self, parent: Id<Entity>, lowerer: &mut AstLowerer) {
+        let Some(symbol_table) = lowerer
+            .context_mut()
+            .try_get_computed::<SymbolTable>(parent)
+            .cloned()
+        else {
+            panic!("internal compiler error: protocol is not a namespace")
+        };
+
+        // Requirements don't have a body, so they're only
+        // ever called through a conforming type
+        let mut signature = lowerer.lower_signature(&self.signature, self.id.as_base(), &symbol_table);
+        signature.mutating = self.mutating_kw.is_some();
+
+        lowerer.context_mut().create((
+            HirFunc { id: self.id },
+            HasValueIn::Method(self.id),
+            signature,
+        ));
+    }
+
+    fn lower_code(&self, _: Id<Entity>, _: &mut AstLowerer) {}
+}
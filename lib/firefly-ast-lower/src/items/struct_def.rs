@@ -1,6 +1,21 @@
-use crate::{AstLowerer, Lower, SymbolDesc};
-use firefly_ast::struct_def::StructDef as AstStruct;
-use firefly_hir::{items::{Field, StructDef as HirStructDef}, ty::{Ty, TyKind}, value::{HasValue, Value, ValueKind}, Entity, Id};
+use crate::{errors::DeclarationError, AstLowerer, Lower, SymbolDesc};
+use firefly_ast::{
+    item::Item,
+    struct_def::{StructDef as AstStruct, StructKind},
+};
+use firefly_hir::{
+    func::{Callable, Func, Initializer},
+    generics::{GenericParameter, GenericParameterList, Monomorph, Substitution},
+    items::{
+        conformance::{AssociatedType, Conformance},
+        Field, ProtocolDef, StructDef as HirStructDef,
+    },
+    resolve::{InstanceMemberTable, Symbol},
+    ty::{HasType, Ty, TyKind},
+    value::{HasValue, Value, ValueKind},
+    Entity, Id,
+};
+use firefly_span::Spanned;
 use itertools::Itertools;
 
 
@@ -17,10 +32,31 @@ impl Lower for AstStruct {
     }
 
     fn get_type(&self) -> Option<firefly_hir::ty::Ty> {
-        Some(Ty::new_unspanned(TyKind::StructDef(self.id)))
+        Some(struct_type(self))
     }
 
     fn lower_def(&self, _: Id<Entity>, lowerer: &mut AstLowerer) {
+        let struct_def = HirStructDef {
+            id: self.id,
+            is_class: self.kind == StructKind::Class,
+        };
+
+        if struct_def.is_class {
+            lowerer.check_class_methods(self);
+        }
+
+        // A struct with inits is only created by calling them
+        let has_inits = lowerer
+            .context()
+            .children(self.id())
+            .iter()
+            .any(|child| lowerer.context().has::<Initializer>(*child));
+
+        if has_inits {
+            lowerer.context_mut().create(struct_def);
+            return;
+        }
+
         let fields = lowerer.context()
                             .children(self.id())
                             .iter()
@@ -28,19 +64,222 @@ impl Lower for AstStruct {
                             .map(|field| field.ty.clone())
                             .collect_vec();
         
-        let my_type = Ty::new_unspanned(TyKind::StructDef(self.id));
-        let init_type = Ty::new_unspanned(TyKind::Func(fields, Box::new(my_type)));
+        let init_type = Ty::new_unspanned(TyKind::Func(fields, Box::new(struct_type(self))));
         let value = Value {
-            kind: ValueKind::InitFor(self.id),
+            kind: ValueKind::InitFor(Monomorph::new(self.id)),
             ty: init_type,
             span: Default::default(),
         };
 
         lowerer.context_mut().create((
-            HirStructDef { id: self.id },
+            struct_def,
             HasValue { value }
         ));
     }
 
-    fn lower_code(&self, _: Id<Entity>, _: &mut AstLowerer) { }
-}
\ No newline at end of file
+    fn lower_code(&self, _: Id<Entity>, lowerer: &mut AstLowerer) {
+        for error in lowerer.conformance_errors.remove(&self.id).unwrap_or_default() {
+            lowerer.emit(error);
+        }
+
+        lowerer.check_initializers(self);
+    }
+}
+
+impl AstLowerer {
+    /// Reports `mutating` methods in a class, which can
+    /// change its fields without being `mutating`
+    fn check_class_methods(&mut self, class: &AstStruct) {
+        for item in &class.items {
+            let Item::Func(Spanned { item: func, .. }) = item else {
+                continue;
+            };
+            let Some(mutating_kw) = func.mutating_kw else {
+                continue;
+            };
+            let Some(callable) = self.context().cast_id::<Callable>(func.id) else {
+                continue;
+            };
+
+            // Static methods have already been reported
+            if self.context().get(callable).mutating {
+                self.emit(DeclarationError::MutatingInClass(mutating_kw));
+                self.context_mut().get_mut(callable).mutating = false;
+            }
+        }
+    }
+
+    /// Checks the conformances of every struct declared in the items
+    pub fn lower_conformances(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::StructDef(Spanned { item, .. }) => {
+                    self.check_conformances(item);
+                    self.lower_conformances(&item.items);
+                }
+                Item::EnumDef(Spanned { item, .. }) => self.lower_conformances(&item.items),
+
+                _ => {}
+            }
+        }
+    }
+
+    /// Finds the method implementing each requirement of the
+    /// protocols a struct conforms to, and the type it chose
+    /// for each of their associated types
+    fn check_conformances(&mut self, struct_def: &AstStruct) {
+        let conformance = self
+            .context()
+            .cast_id::<Conformance>(struct_def.id)
+            .expect("internal compiler error: struct doesn't have a conformance");
+        let protocols = self.context().get(conformance).protocols.clone();
+        let mut errors = Vec::new();
+
+        let member_table = self
+            .context_mut()
+            .try_get_computed::<InstanceMemberTable>(struct_def.id)
+            .cloned()
+            .expect("internal compiler error: struct doesn't have an instance member table");
+
+        for protocol in protocols {
+            let associated_types = ProtocolDef::associated_types(protocol, self.context());
+            let mut chosen = self.explicit_associated_types(&associated_types, &member_table);
+
+            for requirement in ProtocolDef::requirements(protocol, self.context()) {
+                let name = &self
+                    .context()
+                    .try_get::<Symbol>(requirement)
+                    .expect("internal compiler error: requirement doesn't have a symbol")
+                    .name
+                    .name;
+
+                let methods = member_table
+                    .lookup(name)
+                    .map(|collection| collection.symbols)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|symbol| self.context().cast_id::<Func>(symbol))
+                    .collect_vec();
+
+                let required = self
+                    .context()
+                    .try_get::<Callable>(requirement)
+                    .cloned()
+                    .expect("internal compiler error: requirement doesn't have a signature");
+
+                let witness = methods.iter().copied().find_map(|method| {
+                    // Methods with their own generic parameters
+                    // can't be called through a witness table
+                    if self.context().has::<GenericParameterList>(method) {
+                        return None;
+                    }
+
+                    // Associated types that weren't chosen
+                    // explicitly are inferred from the witness
+                    let callable = self.context().try_get::<Callable>(method)?;
+                    let mut substitution = chosen.clone();
+
+                    let inferred = substitution.unify_callable(&required, callable, &associated_types);
+
+                    (inferred && callable.implements(&required.substitute(&substitution))).then_some((method, substitution))
+                });
+
+                match (witness, methods.first()) {
+                    (Some((witness, substitution)), _) => {
+                        chosen = substitution;
+
+                        self.context_mut()
+                            .get_mut(conformance)
+                            .witnesses
+                            .insert(requirement, witness);
+                    }
+                    (None, Some(method)) => {
+                        errors.push(DeclarationError::RequirementSignature { requirement, method: *method });
+                    }
+                    (None, None) => {
+                        errors.push(DeclarationError::MissingRequirement {
+                            requirement,
+                            span: struct_def.name.span,
+                        });
+                    }
+                }
+            }
+
+            for associated in associated_types {
+                let default = self
+                    .context()
+                    .try_get::<AssociatedType>(associated)
+                    .and_then(|associated| associated.default.as_ref());
+
+                let ty = match (chosen.get(associated), default) {
+                    (Some(ty), _) => ty.clone(),
+                    (None, Some(default)) => default.substitute(&chosen),
+                    (None, None) => {
+                        errors.push(DeclarationError::MissingAssociatedType {
+                            associated,
+                            span: struct_def.name.span,
+                        });
+                        continue;
+                    }
+                };
+
+                self.context_mut()
+                    .get_mut(conformance)
+                    .associated
+                    .insert(associated, ty);
+            }
+        }
+
+        self.conformance_errors.insert(struct_def.id, errors);
+    }
+
+    /// Finds the associated types a struct chose
+    /// with a type alias of the same name
+    fn explicit_associated_types(
+        &mut self,
+        associated_types: &[Id<GenericParameter>],
+        member_table: &InstanceMemberTable,
+    ) -> Substitution {
+        let mut chosen = Substitution::default();
+
+        for associated in associated_types {
+            let name = &self
+                .context()
+                .try_get::<Symbol>(*associated)
+                .expect("internal compiler error: associated type doesn't have a symbol")
+                .name
+                .name;
+
+            let alias = member_table
+                .lookup(name)
+                .map(|collection| collection.symbols)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|symbol| symbol.as_base())
+                .find(|symbol| self.type_aliases.contains_key(symbol));
+
+            let Some(alias) = alias else {
+                continue;
+            };
+
+            self.lower_type_alias(alias);
+
+            if let Some(HasType { ty }) = self.context().try_get::<HasType>(alias) {
+                chosen.insert(*associated, ty.clone());
+            }
+        }
+
+        chosen
+    }
+}
+
+/// The type of the struct, generic over its own parameters
+fn struct_type(struct_def: &AstStruct) -> Ty {
+    let generics = struct_def
+        .generics
+        .iter()
+        .map(|generic| Ty::new(TyKind::Generic(generic.id), generic.name.span))
+        .collect_vec();
+
+    Ty::new_unspanned(TyKind::StructDef(struct_def.id, generics))
+}
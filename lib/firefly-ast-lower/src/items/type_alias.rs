@@ -0,0 +1,72 @@
+use crate::{errors::DeclarationError, AstLowerer, Lower, SymbolDesc};
+use firefly_ast::type_alias::TypeAlias as AstTypeAlias;
+use firefly_hir::{
+    items::TypeAlias as HirTypeAlias,
+    resolve::SymbolTable,
+    ty::{HasType, Ty, TyKind},
+    Entity, Id,
+};
+
+impl Lower for AstTypeAlias {
+    fn id(&self) -> Id<Entity> {
+        self.id.as_base()
+    }
+
+    fn get_symbol(&self) -> Option<SymbolDesc> {
+        let name = self.name.clone();
+        let visibility = self.visibility.clone();
+
+        Some(SymbolDesc { name, visibility, static_kw: None })
+    }
+
+    fn lower_def(&self, _: Id<Entity>, lowerer: &mut AstLowerer) {
+        lowerer.lower_type_alias(self.id());
+    }
+
+    fn lower_code(&self, _: Id<Entity>, _: &mut AstLowerer) {}
+}
+
+impl AstLowerer {
+    /// Resolves the type a type alias stands for. Aliases can
+    /// use each other, so they're resolved when they're first used
+    pub fn lower_type_alias(&mut self, entity: Id<Entity>) {
+        if self.context.has::<HasType>(entity) {
+            return;
+        }
+
+        let Some((alias, ast_ty)) = self.type_aliases.get(&entity).cloned() else {
+            return;
+        };
+
+        if let Some(position) = self.alias_stack.iter().position(|other| *other == alias) {
+            let cycle = self.alias_stack[position..].to_vec();
+
+            self.cyclic_aliases.extend(cycle.iter().copied());
+            self.emit(DeclarationError::CyclicTypeAliases(cycle));
+            return;
+        }
+
+        let parent = self.context.parent(entity).unwrap();
+        let symbol_table = self
+            .context
+            .try_get_computed::<SymbolTable>(parent)
+            .cloned()
+            .expect("internal compiler error: parent is not a namespace");
+
+        self.alias_stack.push(alias);
+        let ty = self.lower_ty(&ast_ty, parent, &symbol_table);
+        self.alias_stack.pop();
+
+        // An alias in a cycle doesn't stand for any type
+        let ty = match self.cyclic_aliases.contains(&alias) {
+            true => Ty::new(TyKind::Never, ty.span),
+            false => ty,
+        };
+        let ty = ty.with_alias(alias);
+
+        self.context.create((
+            HirTypeAlias { id: alias, ty: ty.clone() },
+            HasType { ty },
+        ));
+    }
+}
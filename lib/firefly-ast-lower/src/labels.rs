@@ -1,8 +1,15 @@
-use firefly_hir::{stmt::CodeBlock, Id, Name};
+use firefly_hir::{stmt::CodeBlock, ty::Ty, Id, Name};
 
 pub struct LoopLabel {
     pub label: Option<Name>,
     pub code_block: Id<CodeBlock>,
+
+    /// Whether the loop can be broken out of with a value
+    pub takes_value: bool,
+    /// Whether anything breaks out of the loop
+    pub has_break: bool,
+    /// The type of the values the loop is broken out with
+    pub break_ty: Option<Ty>,
 }
 
 pub struct LabelStack {
@@ -14,12 +21,12 @@ impl LabelStack {
         Self { stack: Vec::new() }
     }
 
-    pub fn push(&mut self, label: Option<Name>, code_block: Id<CodeBlock>) {
-        self.stack.push(LoopLabel { label, code_block });
+    pub fn push(&mut self, label: Option<Name>, code_block: Id<CodeBlock>, takes_value: bool) {
+        self.stack.push(LoopLabel { label, code_block, takes_value, has_break: false, break_ty: None });
     }
 
-    pub fn pop(&mut self) {
-        self.stack.pop();
+    pub fn pop(&mut self) -> Option<LoopLabel> {
+        self.stack.pop()
     }
 
     pub fn last(&self) -> Option<&LoopLabel> {
@@ -37,4 +44,8 @@ impl LabelStack {
 
         return None;
     }
-}
\ No newline at end of file
+
+    pub fn get_mut(&mut self, code_block: Id<CodeBlock>) -> Option<&mut LoopLabel> {
+        self.stack.iter_mut().rev().find(|loop_label| loop_label.code_block == code_block)
+    }
+}
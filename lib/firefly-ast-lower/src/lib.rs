@@ -1,26 +1,66 @@
-use std::sync::Arc;
+use std::{collections::{HashMap, HashSet}, sync::Arc};
 
-use firefly_ast::{item::Item, Visibility};
+use firefly_ast::{item::Item, ty::Ty as AstTy, Visibility};
 use firefly_errors::emitter::Emitter;
-use firefly_hir::{ty::Ty, value::Value, Entity, HirContext, Id, IntoDiagnostic};
+use firefly_hir::{
+    items::{StructDef as HirStructDef, TypeAlias},
+    resolve::{Namespace, Passthrough, Symbol},
+    stmt::Local,
+    ty::{HasType, Ty},
+    value::Value,
+    Entity, HirContext, Id, IntoDiagnostic, Visibility as HirVisibility,
+};
 use firefly_span::{Span, Spanned};
+use closure::CaptureStack;
+use errors::DeclarationError;
 use labels::LabelStack;
+use throws::{ErrorHandler, TryKind};
 
+mod closure;
 pub mod errors;
+mod generics;
 mod items;
 mod labels;
 mod link;
+mod mutability;
+mod optional;
+mod pattern;
 mod resolve;
 mod resolve_condition;
 mod stmt;
+mod throws;
 mod ty;
+mod typecheck;
 mod util;
 mod value;
 
 pub struct AstLowerer {
     context: HirContext,
     pub(crate) self_value: Option<Value>,
+    pub(crate) return_ty: Option<Ty>,
+    pub(crate) error_handler: ErrorHandler,
+    pub(crate) try_kind: Option<TryKind>,
     pub(crate) label_stack: LabelStack,
+    pub(crate) capture_stack: CaptureStack,
+
+    // Type aliases declared in the source, which are
+    // resolved the first time they're used
+    pub(crate) type_aliases: HashMap<Id<Entity>, (Id<TypeAlias>, Spanned<AstTy>)>,
+    pub(crate) alias_stack: Vec<Id<TypeAlias>>,
+    pub(crate) cyclic_aliases: HashSet<Id<TypeAlias>>,
+
+    // Conformances are checked with the definitions, but their
+    // errors are reported with the code of the struct, so a
+    // bad conformance doesn't hide errors in the code
+    pub(crate) conformance_errors: HashMap<Id<HirStructDef>, Vec<DeclarationError>>,
+
+    // Types declared with the name of an earlier type, which
+    // were already reported and are ignored when resolving
+    pub(crate) redeclared_types: HashSet<Id<Entity>>,
+
+    // The optionals whose insides are bound in a chain,
+    // so errors about changing the binding name the optional
+    pub(crate) chained_optionals: HashMap<Id<Local>, Value>,
 }
 
 impl AstLowerer {
@@ -31,7 +71,18 @@ impl AstLowerer {
         let mut lowerer = Self {
             context,
             self_value: None,
+            return_ty: None,
+            error_handler: ErrorHandler::Func(None),
+            try_kind: None,
             label_stack: LabelStack::new(),
+            capture_stack: CaptureStack::new(),
+
+            type_aliases: HashMap::new(),
+            alias_stack: Vec::new(),
+            cyclic_aliases: HashSet::new(),
+            conformance_errors: HashMap::new(),
+            redeclared_types: HashSet::new(),
+            chained_optionals: HashMap::new(),
         };
 
         lowerer.resolve_type_aliases();
@@ -40,6 +91,81 @@ impl AstLowerer {
     }
 
     pub fn lower_item_defs(&mut self, items: &[Item]) {
+        // Redeclared types are reported once here,
+        // rather than everywhere they're used
+        self.check_redeclared_types(items);
+
+        // Generic arguments in signatures are checked against
+        // bounds and conformances, so they're lowered first
+        self.lower_constraints(items);
+        self.lower_defs(items);
+
+        // Code can use the associated types a struct chose,
+        // so conformances are checked before any code is lowered
+        self.lower_conformances(items);
+    }
+
+    /// Lowers imports, which change what's in scope, so
+    /// they have to be lowered before any symbol tables are built
+    pub fn lower_imports(&mut self, items: &[Item]) {
+        for item in items {
+            match item {
+                Item::Import(Spanned { item, .. }) => {
+                    let parent = self.context.parent(item.id()).unwrap();
+                    item.lower_def(parent, self);
+                }
+                Item::StructDef(Spanned { item, .. }) => self.lower_imports(&item.items),
+                Item::EnumDef(Spanned { item, .. }) => self.lower_imports(&item.items),
+
+                _ => {}
+            }
+        }
+    }
+
+    fn check_redeclared_types(&mut self, items: &[Item]) {
+        for item in items {
+            let (id, name) = match item {
+                Item::StructDef(Spanned { item, .. }) => {
+                    self.check_redeclared_types(&item.items);
+                    (item.id.as_base(), &item.name)
+                }
+                Item::EnumDef(Spanned { item, .. }) => {
+                    self.check_redeclared_types(&item.items);
+                    (item.id.as_base(), &item.name)
+                }
+                Item::ProtocolDef(Spanned { item, .. }) => (item.id.as_base(), &item.name),
+                Item::TypeAlias(Spanned { item, .. }) => (item.id.as_base(), &item.name),
+                _ => continue,
+            };
+
+            // Source files share the namespace of their module
+            let mut scope = self.context.parent(id).unwrap();
+            if self.context.has::<Passthrough>(scope) {
+                scope = self.context.parent(scope).unwrap();
+            }
+
+            let Some(namespace) = self.context.try_get_computed::<Namespace>(scope).cloned() else {
+                continue;
+            };
+
+            let original = namespace.symbols.into_iter().find(|&symbol| {
+                let Symbol { name: symbol_name, visibility, .. } = self.context.get(symbol);
+
+                symbol_name.name == name.item
+                    && !matches!(visibility, HirVisibility::Local)
+                    && (self.context.has::<HasType>(symbol) || self.type_aliases.contains_key(&symbol.as_base()))
+            });
+
+            if let Some(original) = original.filter(|original| original.as_base() != id) {
+                let original = self.context.get(original).name.span;
+
+                self.redeclared_types.insert(id);
+                self.emit(DeclarationError::RedeclaredType(name.clone(), original));
+            }
+        }
+    }
+
+    fn lower_defs(&mut self, items: &[Item]) {
         for item in items {
             self.lower_item_def(item);
         }
@@ -55,11 +181,33 @@ impl AstLowerer {
         let item: &dyn Lower = match item {
             Item::Func(Spanned { item, .. }) => item,
             Item::Field(Spanned { item, .. }) => item,
-            Item::Import(Spanned { item, .. }) => item,
+            Item::Init(Spanned { item, .. }) => item,
+            Item::TypeAlias(Spanned { item, .. }) => item,
             Item::StructDef(Spanned { item, .. }) => {
-                self.lower_item_defs(&item.items);
+                self.lower_defs(&item.items);
                 item
             }
+            Item::EnumDef(Spanned { item, .. }) => {
+                // Cases and methods refer to the enum, so it's lowered first
+                let parent = self.context.parent(item.id()).unwrap();
+                item.lower_def(parent, self);
+
+                self.lower_defs(&item.items);
+                return;
+            }
+            Item::ProtocolDef(Spanned { item, .. }) => {
+                // Requirements take the protocol as their receiver
+                let parent = self.context.parent(item.id()).unwrap();
+                item.lower_def(parent, self);
+
+                self.lower_associated_defaults(&item.associated_types, item.id());
+
+                for requirement in &item.requirements {
+                    requirement.item.lower_def(item.id(), self);
+                }
+                return;
+            }
+            Item::Import(_) => return,
             Item::Module(_) => return,
             Item::Error => return,
         };
@@ -74,11 +222,18 @@ impl AstLowerer {
         let item: &dyn Lower = match item {
             Item::Func(Spanned { item, .. }) => item,
             Item::Field(Spanned { item, .. }) => item,
+            Item::Init(Spanned { item, .. }) => item,
             Item::Import(Spanned { item, .. }) => item,
             Item::StructDef(Spanned { item, .. }) => {
                 self.lower_item_codes(&item.items);
                 item
             }
+            Item::EnumDef(Spanned { item, .. }) => {
+                self.lower_item_codes(&item.items);
+                item
+            }
+            Item::ProtocolDef(_) => return,
+            Item::TypeAlias(_) => return,
             Item::Module(_) => return,
             Item::Error => return,
         };
@@ -30,10 +30,35 @@ impl AstLowerer {
             self.link_item(item, parent, is_static);
 
             match item {
+                Item::Func(Spanned { item, .. }) => {
+                    self.link_generics(&item.generics, item.id.as_base());
+                }
+
                 Item::StructDef(Spanned { item, .. }) => {
+                    self.link_generics(&item.generics, item.id.as_base());
+                    self.link_items(&item.items, item.id.as_base(), false);
+                }
+
+                Item::EnumDef(Spanned { item, .. }) => {
+                    for case in &item.cases {
+                        self.link_lowerable(&case.item, item.id.as_base(), true);
+                    }
+
                     self.link_items(&item.items, item.id.as_base(), false);
                 }
 
+                Item::TypeAlias(Spanned { item, .. }) => {
+                    self.type_aliases.insert(item.id.as_base(), (item.id, item.ty.clone()));
+                }
+
+                Item::ProtocolDef(Spanned { item, .. }) => {
+                    self.link_associated_types(&item.associated_types, item.id.as_base());
+
+                    for requirement in &item.requirements {
+                        self.link_lowerable(&requirement.item, item.id.as_base(), false);
+                    }
+                }
+
                 _ => {}
             }
         }
@@ -43,12 +68,20 @@ impl AstLowerer {
         let item: &dyn Lower = match item {
             Item::Func(Spanned { item, .. }) => item,
             Item::Field(Spanned { item, .. }) => item,
+            Item::Init(Spanned { item, .. }) => item,
             Item::StructDef(Spanned { item, .. }) => item,
+            Item::EnumDef(Spanned { item, .. }) => item,
+            Item::ProtocolDef(Spanned { item, .. }) => item,
+            Item::TypeAlias(Spanned { item, .. }) => item,
             Item::Import(Spanned { item, .. }) => item,
 
             _ => return,
         };
 
+        self.link_lowerable(item, parent, is_static);
+    }
+
+    fn link_lowerable(&mut self, item: &dyn Lower, parent: Id<Entity>, is_static: bool) {
         let id = item.id();
 
         if let Some(SymbolDesc { name, visibility, static_kw }) = item.get_symbol() {
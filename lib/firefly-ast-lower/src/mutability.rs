@@ -0,0 +1,128 @@
+use firefly_hir::{
+    func::{Callable, Initializer},
+    resolve::Symbol,
+    stmt::Local,
+    value::{HasSelf, Parameter, Value, ValueKind},
+    Entity, Id,
+};
+use firefly_span::Span;
+
+use crate::{closure::root_local, errors::ValueError, AstLowerer};
+
+impl AstLowerer {
+    /// Checks that a value can be assigned to, or
+    /// have a `mutating` method called on it
+    pub(crate) fn check_mutable(&self, place: &Value) {
+        if !place.is_mutable(self.context()) && !self.is_init_field(place) {
+            self.emit(self.not_mutable(place, place.span));
+            return;
+        }
+
+        if root_local(place).is_some_and(|local| self.capture_stack.is_captured(local)) {
+            self.emit(ValueError::AssignToCapture(place.span));
+        }
+    }
+
+    /// Checks that a `mutating` method is
+    /// called on a value that can be changed
+    pub(crate) fn check_mutating_call(&self, function: &Value) {
+        if let Some(receiver) = self.mutating_receiver(function) {
+            self.check_mutable(receiver);
+        }
+    }
+
+    /// Reports a `mutating` method used as a value, since the
+    /// changes it makes couldn't be stored back into its receiver
+    pub(crate) fn require_non_mutating(&self, value: Value) -> Value {
+        if self.mutating_receiver(&value).is_some() {
+            self.emit(ValueError::MutatingMethodValue(value.span));
+            return Value::error(value.span);
+        }
+
+        value
+    }
+
+    /// Whether a value is a call to a `mutating` method
+    pub(crate) fn is_mutating_call(&self, call: &Value) -> bool {
+        match &call.kind {
+            ValueKind::Invoke(function, _) => self.mutating_receiver(function).is_some(),
+            _ => false,
+        }
+    }
+
+    /// The receiver of a `mutating` method, which the method changes
+    fn mutating_receiver<'a>(&self, function: &'a Value) -> Option<&'a Value> {
+        let ValueKind::InstanceFunc(receiver, func) = &function.kind else {
+            return None;
+        };
+
+        self.context()
+            .try_get::<Callable>(func.id)
+            .is_some_and(|callable| callable.mutating)
+            .then_some(receiver)
+    }
+
+    /// An init can assign every field of the struct it's
+    /// creating, including ones declared with `let`
+    fn is_init_field(&self, place: &Value) -> bool {
+        let ValueKind::FieldOf(parent, _) = &place.kind else {
+            return false;
+        };
+        let ValueKind::Local(local) = parent.kind else {
+            return false;
+        };
+
+        self.context()
+            .parent(local.as_base())
+            .and_then(|init| self.context().try_get::<Initializer>(init))
+            .is_some_and(|initializer| initializer.local == local)
+    }
+
+    /// Finds the part of a value that can't be
+    /// changed, and reports why it can't be
+    fn not_mutable(&self, place: &Value, span: Span) -> ValueError {
+        let binding: Id<Entity> = match &place.kind {
+            // The fields of a class don't depend on the value
+            // holding it, so only the field can be at fault
+            ValueKind::FieldOf(parent, _)
+                if !parent.ty.is_class(self.context()) && !parent.is_mutable(self.context()) =>
+            {
+                return self.not_mutable(parent, span);
+            }
+            ValueKind::Local(local) if self.is_method_self(*local) => {
+                return ValueError::ImmutableSelf(span);
+            }
+            // The inside of an optional in a chain can
+            // only be changed if the optional can be
+            ValueKind::Local(local) if self.chained_optionals.contains_key(local) => {
+                return self.not_mutable(&self.chained_optionals[local], span);
+            }
+
+            ValueKind::FieldOf(_, field) => field.as_base(),
+            ValueKind::Local(local) => local.as_base(),
+            ValueKind::Global(global) => global.as_base(),
+
+            _ => return ValueError::NotMutable(span),
+        };
+
+        match self.context().try_get::<Symbol>(binding) {
+            Some(symbol) if self.context().has::<Parameter>(binding) => ValueError::ImmutableParameter {
+                name: symbol.name.clone(),
+                span,
+            },
+            Some(symbol) => ValueError::ImmutableBinding {
+                name: symbol.name.clone(),
+                span,
+            },
+            None => ValueError::NotMutable(span),
+        }
+    }
+
+    /// Whether a local is the `self` of a method
+    fn is_method_self(&self, local: Id<Local>) -> bool {
+        self.context()
+            .parent(local.as_base())
+            .and_then(|method| self.context().try_get::<HasSelf>(method))
+            .is_some_and(|has_self| has_self.local == local)
+    }
+}
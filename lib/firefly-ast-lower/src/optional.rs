@@ -0,0 +1,114 @@
+use firefly_ast::{value::Value as AstValue, BindingKind};
+use firefly_hir::{
+    resolve::SymbolTable,
+    ty::{Ty, TyKind},
+    value::{OptionalChainValue, Value, ValueKind},
+    Entity, Id, Name,
+};
+use firefly_span::{Span, Spanned};
+
+use crate::{errors::TypeError, typecheck::is_coercible, AstLowerer};
+
+impl AstLowerer {
+    /// Returns the type of the value inside an optional,
+    /// reporting an error if the value isn't an optional
+    pub fn optional_inner(&self, value: &Value) -> Option<Ty> {
+        match &value.ty.kind {
+            TyKind::Optional(inner) => Some(inner.as_ref().clone()),
+
+            // An error has already been reported
+            TyKind::Never => None,
+
+            _ => {
+                self.emit(TypeError::NotOptional { ty: value.ty.clone(), span: value.span });
+                None
+            }
+        }
+    }
+
+    /// Lowers a value that uses the inside of an optional,
+    /// like `person?.name`, which is `nil` if the optional is
+    ///
+    /// `chained` is given the inside of the optional, and
+    /// returns the value to evaluate with it
+    pub fn lower_optional_chain(
+        &mut self,
+        optional: &Spanned<AstValue>,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+        span: Span,
+        chained: impl FnOnce(&mut Self, Value, &mut SymbolTable) -> Option<Value>,
+    ) -> Value {
+        let optional = self.lower_value(optional, parent, symbol_table, Default::default());
+
+        let Some(inner_ty) = self.optional_inner(&optional) else {
+            return Value::error(span);
+        };
+
+        // The inside of the optional is stored in a local that can't
+        // be named, so it's only evaluated once. It can be changed if
+        // the optional can, and then it's stored back into the optional
+        let kind = match optional.is_mutable(self.context()) {
+            true => BindingKind::Var,
+            false => BindingKind::Let,
+        };
+        let writes_back = kind == BindingKind::Var && !inner_ty.is_class(self.context());
+
+        let name = Name { name: String::new(), span: optional.span };
+        let binding = self.create_local(parent, &name, &inner_ty, kind);
+        let unwrapped = Value::new(ValueKind::Local(binding), inner_ty, optional.span);
+
+        self.chained_optionals.insert(binding, optional.clone());
+
+        let Some(mut value) = chained(self, unwrapped, symbol_table) else {
+            return Value::error(span);
+        };
+
+        // Chaining to an optional doesn't nest optionals
+        let ty = match &value.ty.kind {
+            TyKind::Never => return Value::error(span),
+            TyKind::Optional(_) => Ty::new(value.ty.kind.clone(), span),
+            _ => {
+                let ty = Ty::new(TyKind::Optional(Box::new(value.ty.clone())), span);
+                self.coerce(&mut value, &ty);
+
+                ty
+            }
+        };
+
+        let chain = OptionalChainValue { optional, binding, value, writes_back };
+
+        Value::new(ValueKind::OptionalChain(Box::new(chain)), ty, span)
+    }
+
+    /// Lowers `optional ?? default`, which is the inside
+    /// of the optional, or the default if it is `nil`
+    ///
+    /// The default can also be an optional, and then
+    /// so is the result
+    pub fn lower_coalesce(&mut self, optional: Value, mut default: Value, span: Span) -> Value {
+        let Some(inner_ty) = self.optional_inner(&optional) else {
+            return Value::error(span);
+        };
+
+        let expected = if is_coercible(&default, &inner_ty, self.context()) {
+            Ty::new(inner_ty.kind.clone(), span)
+        } else {
+            Ty::new(optional.ty.kind.clone(), span)
+        };
+
+        self.coerce(&mut default, &expected);
+
+        if !self.is_assignable(&default.ty, &expected) {
+            self.emit(TypeError::CoalesceType {
+                expected: inner_ty,
+                found: default.ty.clone(),
+                span: default.span,
+            });
+
+            return Value::error(span);
+        }
+
+        Value::new(ValueKind::Coalesce(Box::new(optional), Box::new(default)), expected, span)
+    }
+}
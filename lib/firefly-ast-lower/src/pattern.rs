@@ -0,0 +1,422 @@
+use firefly_ast::{
+    pattern::{CasePath, MatchArm as AstMatchArm, MatchStatement, Pattern as AstPattern},
+    BindingKind, Name as AstName, Path,
+};
+use firefly_hir::{
+    items::{EnumCase, EnumDef},
+    resolve::{Symbol, SymbolTable},
+    ty::{Ty, TyKind},
+    value::{
+        MatchArm, MatchCheck, MatchValue, Pattern, PatternKind, Value, ValueKind,
+    },
+    Entity, Id, Name,
+};
+use firefly_span::{Span, Spanned};
+use itertools::Itertools;
+
+use crate::{
+    errors::{PatternError, TypeError},
+    AstLowerer,
+};
+
+/// Keeps track of what a pattern binds while it's lowered
+#[derive(Default)]
+struct PatternBindings {
+    bindings: Vec<(Name, Id<Symbol>)>,
+    has_errors: bool,
+}
+
+impl AstLowerer {
+    pub(crate) fn lower_match_statement(
+        &mut self,
+        match_stmt: &MatchStatement,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+        span: Span,
+    ) -> (ValueKind, Ty) {
+        let scrutinee =
+            self.lower_value(&match_stmt.scrutinee, parent, symbol_table, Default::default());
+
+        let mut has_errors = matches!(scrutinee.ty.kind, TyKind::Never);
+
+        let arms = match_stmt
+            .arms
+            .iter()
+            .map(|arm| {
+                let (arm, arm_has_errors) =
+                    self.lower_match_arm(arm, &scrutinee.ty, parent, symbol_table);
+
+                has_errors |= arm_has_errors;
+                arm
+            })
+            .collect_vec();
+
+        let match_value = MatchValue { scrutinee, arms };
+
+        // Patterns that failed to lower match anything,
+        // which would make the checks misleading
+        if !has_errors {
+            self.check_match_arms(&match_value, &match_stmt.arms);
+        }
+
+        let ty = self.check_match_arm_types(&match_value, span);
+
+        (ValueKind::Match(Box::new(match_value)), ty)
+    }
+
+    fn lower_match_arm(
+        &mut self,
+        arm: &AstMatchArm,
+        scrutinee_ty: &Ty,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+    ) -> (MatchArm, bool) {
+        let mut bindings = PatternBindings::default();
+
+        let pattern = self.lower_pattern(&arm.pattern, scrutinee_ty, parent, symbol_table, &mut bindings);
+
+        // The bindings are only visible in the body of the arm
+        symbol_table.push_scope();
+
+        for (name, symbol) in bindings.bindings {
+            symbol_table.insert(name.name, symbol);
+        }
+
+        let body = self.lower_code_block(&arm.body, parent, symbol_table);
+
+        symbol_table.pop_scope();
+
+        (MatchArm { pattern, body }, bindings.has_errors)
+    }
+
+    fn lower_pattern(
+        &mut self,
+        pattern: &Spanned<AstPattern>,
+        expected: &Ty,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+        bindings: &mut PatternBindings,
+    ) -> Pattern {
+        let span = pattern.span;
+
+        let kind = match &pattern.item {
+            AstPattern::Wildcard => PatternKind::Wildcard,
+
+            AstPattern::Binding(name) => {
+                if bindings.bindings.iter().any(|(bound, _)| bound.name == name.item) {
+                    self.emit(PatternError::DuplicateBinding(name.clone()));
+                    bindings.has_errors = true;
+                }
+
+                let name = self.lower_name(name);
+                let local = self.create_local(parent, &name, expected, BindingKind::Let);
+                let symbol = self
+                    .context
+                    .cast_id::<Symbol>(local)
+                    .expect("internal compiler error: local doesn't have a symbol");
+
+                bindings.bindings.push((name, symbol));
+
+                PatternKind::Binding(local)
+            }
+
+            AstPattern::Literal(literal) => {
+                let value = self.lower_value(literal, parent, symbol_table, Default::default());
+
+                self.lower_literal_pattern(value, expected, bindings)
+            }
+
+            AstPattern::Tuple(items) if items.len() == 1 => {
+                return self.lower_pattern(&items[0], expected, parent, symbol_table, bindings);
+            }
+
+            AstPattern::Tuple(items) => {
+                let item_tys = match &expected.kind {
+                    TyKind::Tuple(item_tys) if item_tys.len() == items.len() => item_tys.clone(),
+                    TyKind::Unit if items.is_empty() => vec![],
+                    TyKind::Never => vec![expected.clone(); items.len()],
+
+                    _ => {
+                        self.emit(PatternError::TupleMismatch {
+                            expected: expected.clone(),
+                            found: items.len(),
+                            span,
+                        });
+                        bindings.has_errors = true;
+
+                        vec![Ty::new_unspanned(TyKind::Never); items.len()]
+                    }
+                };
+
+                let items = items
+                    .iter()
+                    .zip(&item_tys)
+                    .map(|(item, ty)| self.lower_pattern(item, ty, parent, symbol_table, bindings))
+                    .collect();
+
+                PatternKind::Tuple(items)
+            }
+
+            AstPattern::Case(case_path, values) => {
+                let case = match case_path {
+                    CasePath::Implicit(name) => self.find_case(name, expected, span),
+                    CasePath::Path(path) => self.resolve_case(path, expected, parent, symbol_table),
+                };
+
+                let Some(case) = case else {
+                    bindings.has_errors = true;
+
+                    return Pattern::new(PatternKind::Wildcard, expected.clone(), span);
+                };
+
+                self.lower_case_pattern(case, values.as_deref(), parent, symbol_table, bindings, span)
+            }
+
+            AstPattern::Path(path) => {
+                let Some(value) = self.resolve_value(path, parent, symbol_table) else {
+                    bindings.has_errors = true;
+
+                    return Pattern::new(PatternKind::Wildcard, expected.clone(), span);
+                };
+
+                match Self::case_of(&value) {
+                    Some(case) => {
+                        let case = self.check_case_enum(case, expected, span);
+                        let Some(case) = case else {
+                            bindings.has_errors = true;
+
+                            return Pattern::new(PatternKind::Wildcard, expected.clone(), span);
+                        };
+
+                        self.lower_case_pattern(case, None, parent, symbol_table, bindings, span)
+                    }
+                    None => self.lower_literal_pattern(value, expected, bindings),
+                }
+            }
+        };
+
+        Pattern::new(kind, expected.clone(), span)
+    }
+
+    fn lower_literal_pattern(
+        &mut self,
+        mut value: Value,
+        expected: &Ty,
+        bindings: &mut PatternBindings,
+    ) -> PatternKind {
+        let ValueKind::Literal(_) = &value.kind else {
+            if !matches!(value.ty.kind, TyKind::Never) {
+                self.emit(PatternError::NotAPattern(value.span));
+            }
+            bindings.has_errors = true;
+
+            return PatternKind::Wildcard;
+        };
+
+        self.coerce(&mut value, expected);
+
+        if !self.is_assignable(&value.ty, expected) {
+            self.emit(PatternError::TypeMismatch {
+                expected: expected.clone(),
+                found: value.ty.clone(),
+                span: value.span,
+            });
+            bindings.has_errors = true;
+        }
+
+        let ValueKind::Literal(literal) = value.kind else {
+            unreachable!()
+        };
+
+        PatternKind::Literal(literal)
+    }
+
+    fn lower_case_pattern(
+        &mut self,
+        case: Id<EnumCase>,
+        values: Option<&[Spanned<AstPattern>]>,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+        bindings: &mut PatternBindings,
+        span: Span,
+    ) -> PatternKind {
+        let field_tys = self
+            .context
+            .get(case)
+            .fields
+            .iter()
+            .map(|(_, ty)| ty.clone())
+            .collect_vec();
+
+        // A case without values matches the
+        // case, whatever values it carries
+        let Some(values) = values else {
+            let wildcards = field_tys
+                .into_iter()
+                .map(|ty| Pattern::new(PatternKind::Wildcard, ty, span))
+                .collect();
+
+            return PatternKind::Case(case, wildcards);
+        };
+
+        if values.len() != field_tys.len() {
+            let case_name = self.context.get::<Symbol>(unsafe { case.cast() }).name.name.clone();
+
+            self.emit(PatternError::CaseValueCount {
+                case: case_name,
+                expected: field_tys.len(),
+                found: values.len(),
+                span,
+            });
+            bindings.has_errors = true;
+
+            return PatternKind::Wildcard;
+        }
+
+        let values = values
+            .iter()
+            .zip(&field_tys)
+            .map(|(value, ty)| self.lower_pattern(value, ty, parent, symbol_table, bindings))
+            .collect();
+
+        PatternKind::Case(case, values)
+    }
+
+    /// Finds the case of the enum being matched with the given name
+    fn find_case(&mut self, name: &AstName, expected: &Ty, span: Span) -> Option<Id<EnumCase>> {
+        let TyKind::EnumDef(enum_def) = expected.kind else {
+            if !matches!(expected.kind, TyKind::Never) {
+                self.emit(PatternError::NotAnEnum { expected: expected.clone(), span });
+            }
+
+            return None;
+        };
+
+        let case = self
+            .context
+            .children(enum_def.as_base())
+            .iter()
+            .filter_map(|child| self.context.cast_id::<EnumCase>(*child))
+            .find(|case| {
+                self.context
+                    .try_get::<Symbol>(*case)
+                    .is_some_and(|symbol| symbol.name.name == name.item)
+            });
+
+        if case.is_none() {
+            self.emit(PatternError::NoSuchCase { expected: expected.clone(), name: name.clone() });
+        }
+
+        case
+    }
+
+    /// Resolves a path to a case, like `Shape.circle`
+    fn resolve_case(
+        &mut self,
+        path: &Path,
+        expected: &Ty,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+    ) -> Option<Id<EnumCase>> {
+        let value = self.resolve_value(path, parent, symbol_table)?;
+
+        let Some(case) = Self::case_of(&value) else {
+            self.emit(PatternError::NotAPattern(path.span));
+            return None;
+        };
+
+        self.check_case_enum(case, expected, path.span)
+    }
+
+    /// Checks that a case belongs to the enum being matched
+    fn check_case_enum(&mut self, case: Id<EnumCase>, expected: &Ty, span: Span) -> Option<Id<EnumCase>> {
+        let enum_def = self
+            .context
+            .parent(case.as_base())
+            .and_then(|parent| self.context.cast_id::<EnumDef>(parent))
+            .expect("internal compiler error: case is not in an enum");
+
+        let found = Ty::new_unspanned(TyKind::EnumDef(enum_def));
+
+        if !self.is_assignable(&found, expected) {
+            self.emit(PatternError::TypeMismatch { expected: expected.clone(), found, span });
+            return None;
+        }
+
+        Some(case)
+    }
+
+    /// Returns the case a value constructs, if it is a case
+    fn case_of(value: &Value) -> Option<Id<EnumCase>> {
+        match &value.kind {
+            ValueKind::InitCase(case) => Some(*case),
+            ValueKind::Invoke(init, args) if args.is_empty() => match init.kind {
+                ValueKind::InitCase(case) => Some(case),
+                _ => None,
+            },
+
+            _ => None,
+        }
+    }
+
+    /// Reports arms that can never match, and
+    /// values that no arm matches
+    fn check_match_arms(&mut self, match_value: &MatchValue, arms: &[AstMatchArm]) {
+        let check = MatchCheck::new(match_value, &self.context);
+
+        for index in check.unreachable {
+            self.emit(PatternError::UnreachableArm(arms[index].pattern.span));
+        }
+
+        if let Some(missing) = check.missing {
+            self.emit(PatternError::NonExhaustive { missing, span: match_value.scrutinee.span });
+        }
+    }
+
+    /// Finds the type of a match from the values its arms
+    /// yield, checking that they all have the same type
+    ///
+    /// If any arm doesn't yield a value, the match doesn't either
+    fn check_match_arm_types(&mut self, match_value: &MatchValue, span: Span) -> Ty {
+        let arm_tys = match_value
+            .arms
+            .iter()
+            .filter(|arm| !self.block_diverges(arm.body))
+            .map(|arm| {
+                let yields = self.context.get(arm.body).yields.as_ref();
+
+                yields.map_or(Ty::new(TyKind::Unit, span), |yields| yields.ty.clone())
+            })
+            .collect_vec();
+
+        if arm_tys.is_empty() {
+            return Ty::new(TyKind::Never, span);
+        }
+
+        if arm_tys.iter().any(|ty| matches!(ty.kind, TyKind::Unit)) {
+            return Ty::new(TyKind::Unit, span);
+        }
+
+        let expected = Ty::new(arm_tys[0].kind.clone(), span);
+
+        for arm in &match_value.arms {
+            let Some(mut yields) = self.context.get_mut(arm.body).yields.take() else {
+                continue;
+            };
+
+            self.coerce(&mut yields, &expected);
+
+            if !self.is_assignable(&yields.ty, &expected) {
+                self.emit(TypeError::MatchArmType {
+                    expected: expected.clone(),
+                    found: yields.ty.clone(),
+                    span: yields.span,
+                });
+            }
+
+            self.context.get_mut(arm.body).yields = Some(yields);
+        }
+
+        expected
+    }
+}
@@ -1,5 +1,7 @@
 use firefly_hir::{
-    func::Callable,
+    func::{Callable, Initializer},
+    generics::Monomorph,
+    items::StructDef,
     resolve::{
         InstanceMemberTable, StaticMemberTable, Symbol, SymbolCollection, SymbolTable,
         VisibleWithin,
@@ -8,12 +10,11 @@ use firefly_hir::{
     value::{HasValue, HasValueIn, Value, ValueKind},
     Entity, Id,
 };
-use firefly_span::Span;
-use itertools::Itertools;
+use firefly_span::{Span, Spanned};
 
 use crate::{
-    errors::SymbolError,
-    resolve_condition::{ResolveCondition, UnconditionalResolveCondition},
+    errors::{SymbolError, TypeError},
+    resolve_condition::{CallableResolveCondition, ResolveCondition, UnconditionalResolveCondition},
     AstLowerer,
 };
 use firefly_ast::{
@@ -39,39 +40,30 @@ impl AstLowerer {
         condition: impl ResolveCondition + Clone,
     ) -> Option<Value> {
         let (symbol_collection, member_segments) = self.resolve_path(path, from, symbol_table)?;
+        let symbol_collection = self.initializers_of(symbol_collection, path, from, &condition)?;
+
+        let static_segments = path.segments.len() - member_segments.len();
+        let (generics, generics_span) =
+            self.lower_generic_args(&path.segments[..static_segments], from, symbol_table);
 
         let filtered_symbols =
             symbol_collection.symbols_matching(|id| condition.matches(id, &self.context));
 
-        let value_node = match (filtered_symbols.single(), symbol_collection.single()) {
+        // Prefer a symbol that matches without any coercions,
+        // and a symbol declared in the source over a builtin
+        let exact_symbols =
+            filtered_symbols.symbols_matching(|id| condition.matches_exactly(id, &self.context));
+        let matching_symbol = filtered_symbols
+            .single()
+            .or_else(|| exact_symbols.single())
+            .or_else(|| self.declared_symbols(&filtered_symbols).single())
+            .or_else(|| self.declared_symbols(&exact_symbols).single());
+
+        let value_node = match (matching_symbol, symbol_collection.single()) {
             (Some(value_node), _) => value_node,
             (None, Some(value_node)) => value_node,
             (None, None) => {
-                let condition_format = condition.format_for_error();
-
-                if filtered_symbols.is_empty() {
-                    let symbol_collection_spans = symbol_collection
-                        .symbols
-                        .iter()
-                        .map(|sym| self.context().get(*sym).name.span)
-                        .collect_vec();
-
-                    self.emit(SymbolError::NoMatchingSymbol(
-                        condition_format,
-                        symbol_collection_spans,
-                    ));
-                } else {
-                    let filtered_symbol_spans = filtered_symbols
-                        .symbols
-                        .iter()
-                        .map(|sym| self.context().get(*sym).name.span)
-                        .collect_vec();
-
-                    self.emit(SymbolError::AmbiguousSymbol(
-                        condition_format,
-                        filtered_symbol_spans,
-                    ));
-                }
+                self.emit_unresolved(&symbol_collection, &filtered_symbols, &condition, path.span);
                 return None;
             }
         };
@@ -82,7 +74,19 @@ impl AstLowerer {
                 ..has_value.value.clone()
             }
         } else if let Some(has_value_in) = self.context.try_get::<HasValueIn>(value_node) {
-            let self_value = self.self_value.clone().unwrap(); // todo: error
+            let Some(self_value) = self.self_value.clone() else {
+                let name = &self
+                    .context
+                    .try_get::<Symbol>(value_node)
+                    .expect("internal compiler error: doesn't have a symbol")
+                    .name;
+
+                self.emit(SymbolError::InstanceMemberWithoutSelf(
+                    Spanned::new(name.name.clone(), path.span),
+                    name.span,
+                ));
+                return None;
+            };
 
             self.get_member_of(self_value, path.span, has_value_in)
         } else {
@@ -97,11 +101,18 @@ impl AstLowerer {
             return None;
         };
 
+        value = self.apply_generics(value, generics, generics_span)?;
+
+        self.use_value(&value);
+
         for segment in member_segments {
+            let (generics, generics_span) =
+                self.lower_generic_args(std::slice::from_ref(&segment), from, symbol_table);
+
             let child =
                 self.resolve_instance_member_with(value, segment, from, condition.clone())?;
 
-            value = child;
+            value = self.apply_generics(child, generics, generics_span)?;
         }
 
         return Some(value);
@@ -113,14 +124,19 @@ impl AstLowerer {
         value: Value,
         span: Span,
     ) -> Option<Value> {
+        let TyKind::Integer(kind) = value.ty.kind else {
+            return None;
+        };
+        let int = TyKind::Integer(kind);
+
         let (builtin_name, return_type_kind) = match operator {
-            PrefixOperator::Identity => ("identity", TyKind::Integer),
-            PrefixOperator::Invert => ("bitnot", TyKind::Integer),
-            PrefixOperator::Negate => ("negate", TyKind::Integer),
+            PrefixOperator::Identity => ("identity", int.clone()),
+            PrefixOperator::Invert => ("bitnot", int.clone()),
+            PrefixOperator::Negate => ("negate", int.clone()),
         };
 
         let op_func_kind = TyKind::Func(
-            vec![Ty::new_unspanned(TyKind::Integer)],
+            vec![Ty::new_unspanned(int)],
             Box::new(Ty::new_unspanned(return_type_kind.clone())),
         );
 
@@ -147,7 +163,7 @@ impl AstLowerer {
         let (builtin_name, return_type_kind) = match operator {
             PrefixOperator::Identity => ("identity_float", TyKind::Float),
             PrefixOperator::Invert => return None,
-            PrefixOperator::Negate => ("identity_negate", TyKind::Float),
+            PrefixOperator::Negate => ("negate_float", TyKind::Float),
         };
 
         let op_func_kind = TyKind::Func(
@@ -177,12 +193,12 @@ impl AstLowerer {
     ) -> Option<Value> {
         let (builtin_name, return_type_kind) = match operator {
             PrefixOperator::Identity => return None,
-            PrefixOperator::Invert => ("not", TyKind::Integer),
+            PrefixOperator::Invert => ("not", TyKind::Bool),
             PrefixOperator::Negate => return None,
         };
 
         let op_func_kind = TyKind::Func(
-            vec![Ty::new_unspanned(TyKind::Integer)],
+            vec![Ty::new_unspanned(TyKind::Bool)],
             Box::new(Ty::new_unspanned(return_type_kind.clone())),
         );
 
@@ -207,25 +223,35 @@ impl AstLowerer {
         right: Value,
         span: Span,
     ) -> Option<Value> {
+        let TyKind::Integer(kind) = left.ty.kind else {
+            return None;
+        };
+        let int = TyKind::Integer(kind);
+
         let (builtin_name, return_type_kind) = match operator {
-            InfixOperator::Add => ("add", TyKind::Integer),
-            InfixOperator::Subtract => ("sub", TyKind::Integer),
-            InfixOperator::Multiply => ("mul", TyKind::Integer),
-            InfixOperator::Divide => ("div", TyKind::Integer),
-            InfixOperator::Modulo => ("rem", TyKind::Integer),
-            InfixOperator::ShiftLeft => ("left_shift", TyKind::Integer),
-            InfixOperator::ShiftRight => ("right_shift", TyKind::Integer),
-            InfixOperator::BitAnd => ("bitand", TyKind::Integer),
-            InfixOperator::BitXor => ("bitxor", TyKind::Integer),
-            InfixOperator::BitOr => ("bitor", TyKind::Integer),
+            InfixOperator::Add => ("add", int.clone()),
+            InfixOperator::Subtract => ("sub", int.clone()),
+            InfixOperator::Multiply => ("mul", int.clone()),
+            InfixOperator::Divide => ("div", int.clone()),
+            InfixOperator::Modulo => ("rem", int.clone()),
+            InfixOperator::ShiftLeft => ("left_shift", int.clone()),
+            InfixOperator::ShiftRight => ("right_shift", int.clone()),
+            InfixOperator::BitAnd => ("bitand", int.clone()),
+            InfixOperator::BitXor => ("bitxor", int.clone()),
+            InfixOperator::BitOr => ("bitor", int.clone()),
             InfixOperator::CompareLessThan => ("lt_int", TyKind::Bool),
             InfixOperator::CompareGreaterThan => ("gt_int", TyKind::Bool),
             InfixOperator::CompareLessThanOrEqual => ("leq_int", TyKind::Bool),
             InfixOperator::CompareGreaterThanOrEqual => ("geq_int", TyKind::Bool),
             InfixOperator::CompareEqual => ("eq_int", TyKind::Bool),
             InfixOperator::CompareNotEqual => ("neq_int", TyKind::Bool),
+            InfixOperator::Identical => return None,
+            InfixOperator::NotIdentical => return None,
             InfixOperator::LogicalAnd => return None,
             InfixOperator::LogicalOr => return None,
+            InfixOperator::Coalesce => return None,
+            InfixOperator::HalfOpenRange => return None,
+            InfixOperator::ClosedRange => return None,
             InfixOperator::AddAssign => return None,
             InfixOperator::SubtractAssign => return None,
             InfixOperator::MultiplyAssign => return None,
@@ -240,10 +266,7 @@ impl AstLowerer {
         };
 
         let op_func_kind = TyKind::Func(
-            vec![
-                Ty::new_unspanned(TyKind::Integer),
-                Ty::new_unspanned(TyKind::Integer),
-            ],
+            vec![Ty::new_unspanned(int.clone()), Ty::new_unspanned(int)],
             Box::new(Ty::new_unspanned(return_type_kind.clone())),
         );
 
@@ -269,11 +292,11 @@ impl AstLowerer {
         span: Span,
     ) -> Option<Value> {
         let (builtin_name, return_type_kind) = match operator {
-            InfixOperator::Add => ("fadd", TyKind::Integer),
-            InfixOperator::Subtract => ("fsub", TyKind::Integer),
-            InfixOperator::Multiply => ("fmul", TyKind::Integer),
-            InfixOperator::Divide => ("fdiv", TyKind::Integer),
-            InfixOperator::Modulo => ("frem", TyKind::Integer),
+            InfixOperator::Add => ("fadd", TyKind::Float),
+            InfixOperator::Subtract => ("fsub", TyKind::Float),
+            InfixOperator::Multiply => ("fmul", TyKind::Float),
+            InfixOperator::Divide => ("fdiv", TyKind::Float),
+            InfixOperator::Modulo => ("frem", TyKind::Float),
             InfixOperator::ShiftLeft
             | InfixOperator::ShiftRight
             | InfixOperator::BitAnd
@@ -285,8 +308,13 @@ impl AstLowerer {
             InfixOperator::CompareGreaterThanOrEqual => ("geq_float", TyKind::Bool),
             InfixOperator::CompareEqual => ("eq_float", TyKind::Bool),
             InfixOperator::CompareNotEqual => ("neq_float", TyKind::Bool),
+            InfixOperator::Identical => return None,
+            InfixOperator::NotIdentical => return None,
             InfixOperator::LogicalAnd => return None,
             InfixOperator::LogicalOr => return None,
+            InfixOperator::Coalesce => return None,
+            InfixOperator::HalfOpenRange => return None,
+            InfixOperator::ClosedRange => return None,
             InfixOperator::AddAssign => return None,
             InfixOperator::SubtractAssign => return None,
             InfixOperator::MultiplyAssign => return None,
@@ -346,9 +374,16 @@ impl AstLowerer {
             | InfixOperator::CompareGreaterThanOrEqual => return None,
             InfixOperator::CompareEqual => ("eq_bool", TyKind::Bool),
             InfixOperator::CompareNotEqual => ("neq_bool", TyKind::Bool),
-            InfixOperator::LogicalAnd => ("and", TyKind::Bool),
-            InfixOperator::LogicalOr => ("or", TyKind::Bool),
-            InfixOperator::AddAssign
+            // Only the operators short-circuit, so they use builtins
+            // that can't be called like the `and` and `or` functions
+            InfixOperator::LogicalAnd => ("logical_and", TyKind::Bool),
+            InfixOperator::LogicalOr => ("logical_or", TyKind::Bool),
+            InfixOperator::Identical
+            | InfixOperator::NotIdentical
+            | InfixOperator::Coalesce
+            | InfixOperator::HalfOpenRange
+            | InfixOperator::ClosedRange
+            | InfixOperator::AddAssign
             | InfixOperator::SubtractAssign
             | InfixOperator::MultiplyAssign
             | InfixOperator::DivideAssign
@@ -382,6 +417,131 @@ impl AstLowerer {
             span,
         ));
     }
+
+    /// Enums compare equal when they are the same
+    /// case, carrying equal values
+    pub fn get_enum_operator(
+        &mut self,
+        operator: &InfixOperator,
+        left: Value,
+        right: Value,
+        span: Span,
+    ) -> Option<Value> {
+        let builtin_name = match operator {
+            InfixOperator::CompareEqual => "eq_enum",
+            InfixOperator::CompareNotEqual => "neq_enum",
+            _ => return None,
+        };
+
+        let op_func_kind = TyKind::Func(
+            vec![left.ty.clone(), left.ty.clone()],
+            Box::new(Ty::new_unspanned(TyKind::Bool)),
+        );
+
+        Some(Value::new(
+            ValueKind::Invoke(
+                Box::new(Value::new(
+                    ValueKind::BuiltinFunc(builtin_name),
+                    Ty::new(op_func_kind, span),
+                    span,
+                )),
+                vec![left, right],
+            ),
+            Ty::new(TyKind::Bool, span),
+            span,
+        ))
+    }
+
+    /// Instances of classes are identical when they
+    /// are the same instance, not just equal values
+    pub fn get_identity_operator(
+        &mut self,
+        operator: &InfixOperator,
+        left: Value,
+        right: Value,
+        span: Span,
+    ) -> Option<Value> {
+        let builtin_name = match operator {
+            InfixOperator::Identical => "eq_ref",
+            InfixOperator::NotIdentical => "neq_ref",
+            _ => return None,
+        };
+
+        if !left.ty.is_class(self.context()) {
+            return None;
+        }
+
+        let op_func_kind = TyKind::Func(
+            vec![left.ty.clone(), left.ty.clone()],
+            Box::new(Ty::new_unspanned(TyKind::Bool)),
+        );
+
+        Some(Value::new(
+            ValueKind::Invoke(
+                Box::new(Value::new(
+                    ValueKind::BuiltinFunc(builtin_name),
+                    Ty::new(op_func_kind, span),
+                    span,
+                )),
+                vec![left, right],
+            ),
+            Ty::new(TyKind::Bool, span),
+            span,
+        ))
+    }
+
+    /// Resolves an operator on a user-defined type to a call
+    /// of the instance method named by the operator's verb.
+    ///
+    /// Returns `None` if the type has no method with that name
+    pub fn get_method_operator(
+        &mut self,
+        verb: &'static str,
+        receiver: Value,
+        args: Vec<Value>,
+        span: Span,
+        from: Id<Entity>,
+    ) -> Option<Value> {
+        if !self.has_instance_member(&receiver, verb) {
+            return None;
+        }
+
+        let condition = CallableResolveCondition {
+            labels: args.iter().map(|_| None).collect(),
+            args: args.clone(),
+        };
+
+        let segment = PathSegment::new(Spanned::new(verb.into(), span));
+
+        let Some(method) = self.resolve_instance_member_with(receiver, segment, from, condition) else {
+            return Some(Value::error(span));
+        };
+        let method = self.infer_generics(method, &args);
+
+        let return_type = match &method.ty.kind {
+            TyKind::Func(_, return_type) => return_type.as_ref().clone(),
+            _ => method.ty.clone(),
+        };
+
+        Some(Value::new(
+            ValueKind::Invoke(Box::new(method), args),
+            return_type,
+            span,
+        ))
+    }
+
+    /// Checks if a value has an instance member with a name,
+    /// without emitting an error if it doesn't
+    fn has_instance_member(&mut self, value: &Value, name: &str) -> bool {
+        let Some(instance) = self.member_owner(&value.ty, name) else {
+            return false;
+        };
+
+        self.context
+            .try_get_computed::<InstanceMemberTable>(instance)
+            .is_some_and(|member_table| member_table.lookup(name).is_some())
+    }
+
     pub fn resolve_instance_member(
         &mut self,
         value: Value,
@@ -403,8 +563,15 @@ impl AstLowerer {
         from: Id<Entity>,
         condition: impl ResolveCondition,
     ) -> Option<Value> {
-        let Some(instance) = value.ty.defined_by() else {
-            self.emit(SymbolError::NoMembersOf(value.clone()));
+        let Some(instance) = self.member_owner(&value.ty, &segment.name.item) else {
+            // A value that failed to lower has already been reported
+            match value.ty.kind {
+                TyKind::Never => {}
+                TyKind::Optional(_) => {
+                    self.emit(TypeError::OptionalMember { ty: value.ty.clone(), span: value.span })
+                }
+                _ => self.emit(SymbolError::NoMembersOf(value.clone())),
+            }
             return None;
         };
 
@@ -421,36 +588,21 @@ impl AstLowerer {
         let filtered_symbols =
             symbol_collection.symbols_matching(|id| condition.matches(id, &self.context));
 
-        let symbol = match (filtered_symbols.single(), symbol_collection.single()) {
+        // Prefer a symbol that matches without any coercions
+        let exact_symbols =
+            filtered_symbols.symbols_matching(|id| condition.matches_exactly(id, &self.context));
+        let matching_symbol = filtered_symbols.single().or_else(|| exact_symbols.single());
+
+        let symbol = match (matching_symbol, symbol_collection.single()) {
             (Some(value_node), _) => value_node,
             (None, Some(value_node)) => value_node,
             (None, None) => {
-                let condition_format = condition.format_for_error();
-
-                if filtered_symbols.is_empty() {
-                    let symbol_collection_spans = symbol_collection
-                        .symbols
-                        .iter()
-                        .map(|sym| self.context().get(*sym).name.span)
-                        .collect_vec();
-
-                    self.emit(SymbolError::NoMatchingSymbol(
-                        condition_format,
-                        symbol_collection_spans,
-                    ));
-                } else {
-                    let filtered_symbol_spans = filtered_symbols
-                        .symbols
-                        .iter()
-                        .map(|sym| self.context().get(*sym).name.span)
-                        .collect_vec();
-
-                    self.emit(SymbolError::AmbiguousSymbol(
-                        condition_format,
-                        filtered_symbol_spans,
-                    ));
-                }
-
+                self.emit_unresolved(
+                    &symbol_collection,
+                    &filtered_symbols,
+                    &condition,
+                    segment.name.span,
+                );
                 return None;
             }
         };
@@ -481,6 +633,114 @@ impl AstLowerer {
         return Some(self.get_member_of(value, span, value_in));
     }
 
+    /// Finds the entity whose instance members a value of a type has.
+    ///
+    /// Values of a generic type have the members of
+    /// the protocols its parameter is bounded by
+    pub fn member_owner(&mut self, ty: &Ty, name: &str) -> Option<Id<Entity>> {
+        if let Some(owner) = ty.defined_by() {
+            return Some(owner);
+        }
+
+        let TyKind::Generic(parameter) = &ty.kind else {
+            return None;
+        };
+
+        let bounds = self.context.get(*parameter).bounds.clone();
+
+        let owner = bounds.iter().find(|protocol| {
+            self.context
+                .try_get_computed::<InstanceMemberTable>(**protocol)
+                .is_some_and(|member_table| member_table.lookup(name).is_some())
+        });
+
+        owner.or(bounds.first()).map(|protocol| protocol.as_base())
+    }
+
+    /// A struct with inits is created by calling them, so
+    /// they replace the struct when it is used as a value
+    fn initializers_of(
+        &mut self,
+        symbol_collection: SymbolCollection,
+        path: &Path,
+        from: Id<Entity>,
+        condition: &impl ResolveCondition,
+    ) -> Option<SymbolCollection> {
+        let Some(struct_def) = symbol_collection
+            .single()
+            .and_then(|symbol| self.context.cast_id::<StructDef>(symbol))
+        else {
+            return Some(symbol_collection);
+        };
+
+        let inits = self
+            .context
+            .try_get_computed::<StaticMemberTable>(struct_def)
+            .and_then(|member_table| member_table.lookup(Initializer::NAME))
+            .unwrap_or_default();
+
+        if inits.is_empty() {
+            return Some(symbol_collection);
+        }
+
+        let mut visible = SymbolCollection::default();
+        let mut hidden = SymbolCollection::default();
+
+        for init in &inits.symbols {
+            let Some(VisibleWithin(scope)) = self.context.try_get_computed(*init).cloned() else {
+                panic!("internal compiler error: can't calculate visibility")
+            };
+
+            if self.has_ancestor(from, scope) {
+                visible.add(*init);
+            } else {
+                hidden.add(*init);
+            }
+        }
+
+        // Calling an init that can't be seen from here says so,
+        // instead of saying that no init matches the call
+        let hidden_match = hidden.symbols_matching(|id| condition.matches(id, &self.context));
+        let visible_match = visible.symbols_matching(|id| condition.matches(id, &self.context));
+
+        if visible.is_empty() || (visible_match.is_empty() && !hidden_match.is_empty()) {
+            let name = path.segments.last()?.name.clone();
+            let init = hidden_match.symbols.first().unwrap_or(&inits.symbols[0]);
+            let init_name = self.context.get(*init).name.span;
+
+            self.emit(SymbolError::InitNotVisible(name, init_name));
+            return None;
+        }
+
+        Some(visible)
+    }
+
+    /// Reports that no single symbol matched the condition,
+    /// listing the symbols that could have been meant
+    fn emit_unresolved(
+        &self,
+        symbol_collection: &SymbolCollection,
+        filtered_symbols: &SymbolCollection,
+        condition: &impl ResolveCondition,
+        span: Span,
+    ) {
+        let condition_format = condition.format_for_error(self.context());
+
+        if filtered_symbols.is_empty() {
+            self.emit(SymbolError::NoMatchingSymbol(
+                condition_format,
+                span,
+                symbol_collection.symbols.clone(),
+            ));
+        } else if !condition.has_errors() {
+            self.emit(SymbolError::AmbiguousSymbol(
+                condition_format,
+                span,
+                filtered_symbols.symbols.clone(),
+            ));
+        }
+    }
+
     pub fn resolve_type(
         &mut self,
         path: &Path,
@@ -489,10 +749,32 @@ impl AstLowerer {
     ) -> Option<Ty> {
         let (symbol_collection, member_segments) = self.resolve_path(path, from, symbol_table)?;
 
+        let static_segments = path.segments.len() - member_segments.len();
+        let (generics, generics_span) =
+            self.lower_generic_args(&path.segments[..static_segments], from, symbol_table);
+
+        for symbol in &symbol_collection.symbols {
+            self.lower_type_alias(symbol.as_base());
+        }
+
+        // An alias in a cycle was already reported
+        if symbol_collection.symbols.iter().any(|symbol| {
+            self.type_aliases.contains_key(&symbol.as_base()) && !self.context.has::<HasType>(*symbol)
+        }) {
+            return None;
+        }
+
         // filter the symbols to only include types
         let type_symbols =
             symbol_collection.symbols_matching(|id| self.context().has::<HasType>(id));
 
+        // An associated type of a generic parameter, like `T.Item`
+        if let ([segment], Some(base)) = (&member_segments[..], symbol_collection.single()) {
+            if let Some(ty) = self.resolve_associated_type(base.as_base(), segment, path.span) {
+                return Some(ty);
+            }
+        }
+
         // todo: handle member_segments
         if !member_segments.is_empty() {
             let non_type_symbol = symbol_collection
@@ -510,8 +792,15 @@ impl AstLowerer {
                 member_segments[0].name.clone(),
                 symbol_name_span,
             ));
+            return None;
         }
 
+        // Redeclared types were already reported, so the first declaration is used
+        let type_symbols = match type_symbols.symbols_matching(|id| !self.redeclared_types.contains(&id.as_base())) {
+            declared if declared.is_empty() => type_symbols,
+            declared => declared,
+        };
+
         if let Some(type_node) = type_symbols.single() {
             let has_type = self
                 .context
@@ -520,7 +809,8 @@ impl AstLowerer {
 
             let mut ty = has_type.ty.clone();
             ty.span = path.span;
-            return Some(ty);
+
+            return self.apply_type_generics(ty, generics, generics_span.unwrap_or(path.span));
         }
         // handle the zero case
         else if type_symbols.is_empty() {
@@ -540,8 +830,10 @@ impl AstLowerer {
         }
         // handle the ambiguous case
         else {
-            // todo: throw an ambiguous type error
-            todo!();
+            let name = &path.segments.last().expect("a path has at least one segment").name.item;
+
+            self.emit(SymbolError::AmbiguousSymbol(format!("`{name}`"), path.span, type_symbols.symbols));
+            None
         }
     }
 
@@ -581,12 +873,16 @@ impl AstLowerer {
                 break;
             };
 
-            // Get the static member table for the current entity
+            // Get the static member table for the current entity. Entities
+            // without one can only have instance members
             let Some(static_member_table) = self
                 .context
                 .try_get_computed::<StaticMemberTable>(single_entity)
             else {
-                todo!();
+                return Some((
+                    SymbolCollection::new_single(single_entity),
+                    path.segments[i..].to_vec(),
+                ));
             };
 
             // Look up the current segment in the static member table
@@ -599,28 +895,35 @@ impl AstLowerer {
                 ));
             };
 
-            // We need a single symbol to continue resolution
-            let Some(single_symbol) = symbol.single() else {
-                // Handle ambiguous symbols
-                todo!();
-            };
+            // Only the symbols visible from the 'from' entity can be used
+            // todo: if it becomes a performance concern, cache ancestors
+            let mut visible = SymbolCollection::default();
 
-            // Check if the symbol is visible from the 'from' entity
-            let Some(VisibleWithin(scope)) = self.context.try_get_computed(single_symbol).cloned()
-            else {
-                panic!("internal compiler error: can't calculate visibility")
-            };
+            for &candidate in &symbol.symbols {
+                let Some(VisibleWithin(scope)) = self.context.try_get_computed(candidate).cloned() else {
+                    panic!("internal compiler error: can't calculate visibility")
+                };
 
-            // Check if 'from' is within the visibility scope of the symbol
-            // todo: if it becomes a performance concern, cache ancestors
-            if !self.has_ancestor(from, scope) {
-                let symbol_name = self.context.get(single_symbol).name.span;
+                if self.has_ancestor(from, scope) {
+                    visible.add(candidate);
+                }
+            }
+
+            if visible.is_empty() {
+                let symbol_name = self.context.get(symbol.symbols[0]).name.span;
                 self.emit(SymbolError::NotVisible(segment.name.clone(), symbol_name));
                 return None;
             }
 
+            // Overloads are chosen by the caller, but only
+            // a single symbol can have members looked up in it
+            if visible.single().is_none() && i + 1 < path.segments.len() {
+                self.emit(SymbolError::AmbiguousSymbol(format!("`{}`", segment.name.item), segment.name.span, visible.symbols));
+                return None;
+            }
+
             // Update current_entity to continue resolution
-            current_entity = symbol;
+            current_entity = visible;
         }
 
         // Convert the final symbol collection to the expected return format
@@ -628,13 +931,24 @@ impl AstLowerer {
         return Some((current_entity, vec![]));
     }
 
+    /// Leaves out the builtins, so functions declared
+    /// in the source are chosen over them
+    fn declared_symbols(&self, symbols: &SymbolCollection) -> SymbolCollection {
+        symbols.symbols_matching(|symbol| {
+            !self
+                .context
+                .try_get::<HasValue>(symbol)
+                .is_some_and(|has_value| matches!(has_value.value.kind, ValueKind::BuiltinFunc(_)))
+        })
+    }
+
     fn get_member_of(&self, value: Value, span: Span, value_in: &HasValueIn) -> Value {
         match value_in {
             HasValueIn::Field(field_id) => {
                 let field = self.context.get(*field_id);
 
+                let mut ty = field.ty.substitute(&self.receiver_substitution(&value)).normalize(&self.context);
                 let kind = ValueKind::FieldOf(Box::new(value), field.id);
-                let mut ty = field.ty.clone();
 
                 ty.span = span;
 
@@ -644,9 +958,16 @@ impl AstLowerer {
             HasValueIn::Method(method_id) => {
                 let signature = self.context.try_get::<Callable>(*method_id).unwrap();
 
-                let kind = ValueKind::InstanceFunc(Box::new(value), *method_id);
+                // The method is generic over the parameters
+                // of the struct it is declared in
+                let mut ty = signature.ty().substitute(&self.receiver_substitution(&value)).normalize(&self.context);
+                let generics = match &value.ty.kind {
+                    TyKind::StructDef(_, generics) => generics.clone(),
+                    _ => Vec::new(),
+                };
+
+                let kind = ValueKind::InstanceFunc(Box::new(value), Monomorph::with_generics(*method_id, generics));
 
-                let mut ty = signature.ty();
                 ty.span = span;
 
                 Value { kind, ty, span }
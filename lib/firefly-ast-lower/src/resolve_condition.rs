@@ -1,29 +1,46 @@
-use firefly_hir::{func::Callable, resolve::Symbol, HirContext, Id};
+use firefly_hir::{
+    func::Callable,
+    generics::{GenericParameter, GenericParameterList, Substitution},
+    items::StructDef,
+    resolve::Symbol,
+    ty::{Ty, TyKind},
+    value::{HasValue, Value, ValueKind},
+    HirContext, Id, Name,
+};
 use firefly_span::Spanned;
 use itertools::Itertools;
 
+use crate::typecheck::{is_assignable, is_coercible};
+
 pub trait ResolveCondition {
     fn matches(&self, symbol: Id<Symbol>, context: &HirContext) -> bool;
 
-    fn format_for_error(&self) -> String;
+    /// Used to pick between several matching symbols,
+    /// by matching without any implicit conversions
+    fn matches_exactly(&self, symbol: Id<Symbol>, context: &HirContext) -> bool {
+        self.matches(symbol, context)
+    }
+
+    /// Whether the condition was built from values that failed
+    /// to lower, which make any symbol ambiguous
+    fn has_errors(&self) -> bool {
+        false
+    }
+
+    fn format_for_error(&self, context: &HirContext) -> String;
 }
 
+/// Matches functions that can be called with
+/// the given argument labels and values
 #[derive(Debug, Clone)]
 pub struct CallableResolveCondition {
     pub labels: Vec<Option<Spanned<String>>>,
+    pub args: Vec<Value>,
 }
 
-impl ResolveCondition for CallableResolveCondition {
-    fn matches(&self, id: Id<Symbol>, context: &HirContext) -> bool {
-        let Some(symbol) = context.try_get::<Callable>(id) else {
-            return false;
-        };
-
-        if symbol.labels.len() != self.labels.len() {
-            return false;
-        }
-
-        for (label, expected_label) in symbol.labels.iter().zip(self.labels.iter()) {
+impl CallableResolveCondition {
+    fn matches_labels<'a>(&self, labels: impl Iterator<Item = Option<&'a Name>>) -> bool {
+        for (label, expected_label) in labels.zip(self.labels.iter()) {
             match (label, expected_label) {
                 (Some(label), Some(expected_label)) if label.name == expected_label.item => {}
                 (None, None) => {}
@@ -31,20 +48,147 @@ impl ResolveCondition for CallableResolveCondition {
             }
         }
 
-        return true;
+        true
+    }
+
+    /// Matches the arguments against the parameters, inferring
+    /// any generic parameters from the arguments
+    fn matches_params<'a>(
+        &self,
+        params: impl Iterator<Item = &'a Ty> + Clone,
+        generics: &[Id<GenericParameter>],
+        exactly: bool,
+        context: &HirContext,
+    ) -> bool {
+        let mut substitution = Substitution::default();
+
+        if !generics.is_empty() {
+            for (param, arg) in params.clone().zip(self.args.iter()) {
+                substitution.unify(param, &arg.ty, generics);
+            }
+        }
+
+        params.zip(self.args.iter()).all(|(param, arg)| {
+            let param = param.substitute(&substitution).normalize(context);
+
+            match exactly {
+                true => is_assignable(&arg.ty, &param),
+                false => is_coercible(arg, &param, context),
+            }
+        })
     }
 
-    fn format_for_error(&self) -> String {
+    fn matches_callable(&self, id: Id<Symbol>, context: &HirContext, exactly: bool) -> bool {
+        if let Some(symbol) = context.try_get::<Callable>(id) {
+            if symbol.labels.len() != self.labels.len() {
+                return false;
+            }
+
+            let generics = GenericParameterList::in_scope(id.as_base(), context);
+
+            return self.matches_labels(symbol.labels.iter().map(Option::as_ref))
+                && self.matches_params(symbol.params.iter().map(|param| &param.ty), &generics, exactly, context);
+        }
+
+        // Builtin functions and values of function type
+        // can be called, but don't have labels
+        if let Some(HasValue { value }) = context.try_get::<HasValue>(id) {
+            let TyKind::Func(params, _) = &value.ty.kind else {
+                return false;
+            };
+
+            // Fields with defaults can be left out
+            // of the memberwise initializer
+            if let ValueKind::InitFor(struct_def) = &value.kind {
+                let labels = self.labels.iter().map(|label| label.as_ref().map(|label| label.item.as_str())).collect_vec();
+
+                let Some(args) = StructDef::memberwise_args(struct_def.id, &labels, context) else {
+                    return false;
+                };
+
+                let params = params.iter().zip(args).filter(|(_, arg)| arg.is_some()).map(|(param, _)| param);
+                let generics = struct_def.missing(context);
+
+                return self.matches_params(params, &generics, exactly, context);
+            }
+
+            if params.len() != self.labels.len() {
+                return false;
+            }
+
+            return self.matches_labels(params.iter().map(|_| None))
+                && self.matches_params(params.iter(), &[], exactly, context);
+        }
+
+        false
+    }
+}
+
+impl ResolveCondition for CallableResolveCondition {
+    fn matches(&self, id: Id<Symbol>, context: &HirContext) -> bool {
+        self.matches_callable(id, context, false)
+    }
+
+    fn matches_exactly(&self, id: Id<Symbol>, context: &HirContext) -> bool {
+        self.matches_callable(id, context, true)
+    }
+
+    fn has_errors(&self) -> bool {
+        self.args.iter().any(|arg| matches!(arg.ty.kind, TyKind::Never))
+    }
+
+    fn format_for_error(&self, context: &HirContext) -> String {
         return format!(
             "func ({})",
             self.labels
                 .iter()
-                .map(|label| label.as_ref().map_or("", |label| label.item.as_str()))
+                .zip(self.args.iter())
+                .map(|(label, arg)| match label {
+                    Some(label) => format!("{}: {}", label.item, arg.ty.display(context)),
+                    None => format!("{}", arg.ty.display(context)),
+                })
                 .join(", ")
         );
     }
 }
 
+/// Matches values of a type, so the type a value is
+/// used as can pick between overloaded functions
+#[derive(Debug, Clone)]
+pub struct TypeResolveCondition {
+    pub ty: Ty,
+}
+
+impl TypeResolveCondition {
+    fn ty_of(id: Id<Symbol>, context: &HirContext) -> Option<Ty> {
+        // Generic functions would need their generic
+        // arguments inferred from the type
+        if context.has::<GenericParameterList>(id) {
+            return None;
+        }
+
+        if let Some(callable) = context.try_get::<Callable>(id) {
+            return Some(callable.ty());
+        }
+
+        context.try_get::<HasValue>(id).map(|HasValue { value }| value.ty.clone())
+    }
+}
+
+impl ResolveCondition for TypeResolveCondition {
+    fn matches(&self, id: Id<Symbol>, context: &HirContext) -> bool {
+        Self::ty_of(id, context).is_some_and(|ty| is_assignable(&ty, &self.ty))
+    }
+
+    fn has_errors(&self) -> bool {
+        matches!(self.ty.kind, TyKind::Never)
+    }
+
+    fn format_for_error(&self, context: &HirContext) -> String {
+        format!("type `{}`", self.ty.display(context))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct UnconditionalResolveCondition;
 
@@ -53,7 +197,7 @@ impl ResolveCondition for UnconditionalResolveCondition {
         true
     }
 
-    fn format_for_error(&self) -> String {
+    fn format_for_error(&self, _: &HirContext) -> String {
         return "blank".into();
     }
 }
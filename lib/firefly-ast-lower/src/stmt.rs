@@ -1,8 +1,10 @@
-use crate::AstLowerer;
+use crate::{errors::TypeError, AstLowerer};
 use firefly_ast::stmt::{CodeBlock as AstCodeBlock, Stmt as AstStmt};
 use firefly_hir::{
     resolve::{Symbol, SymbolTable},
     stmt::{CodeBlock as HirCodeBlock, Stmt as HirStmt, StmtKind as HirStmtKind},
+    ty::{Ty, TyKind},
+    value::ValueKind as HirValueKind,
     Entity, Id,
 };
 use firefly_span::Spanned;
@@ -48,7 +50,7 @@ impl AstLowerer {
             id: code_block.id,
             stmts,
             yields,
-            span: Default::default(),
+            span: code_block.span,
         });
 
         symbol_table.pop_scope();
@@ -70,17 +72,33 @@ impl AstLowerer {
                 HirStmt::new(HirStmtKind::Value(value), stmt.span)
             }
 
-            AstStmt::Bind(name, ty, value) => {
+            AstStmt::Bind(kind, name, ty, value) => {
                 let name = self.lower_name(name);
-                let value =
-                    self.lower_value(&value, parent.as_base(), symbol_table, Default::default());
                 let ty = ty
                     .as_ref()
-                    .map(|ty| self.lower_ty(&ty, parent.as_base(), symbol_table))
-                    .unwrap_or_else(|| value.ty.clone());
+                    .map(|ty| self.lower_ty(&ty, parent.as_base(), symbol_table));
+
+                // The annotation picks between overloaded functions
+                let mut value = match &ty {
+                    Some(ty) => self.lower_value_as(value, ty, parent.as_base(), symbol_table),
+                    None => self.lower_value(value, parent.as_base(), symbol_table, Default::default()),
+                };
+
+                // `nil` only has a type when it's used as one, so the
+                // variable is given the error type to avoid reporting more
+                let ty = match ty {
+                    Some(ty) => ty,
+                    None if matches!(value.kind, HirValueKind::Nil) => {
+                        self.emit(TypeError::CantInferNil(value.span));
+                        Ty::new(TyKind::Never, value.span)
+                    }
+                    None => value.ty.clone(),
+                };
+
+                self.check_assign(&ty, &mut value);
 
                 // Create a local so we can reference the symbol
-                let local = self.create_local(parent.as_base(), &name, &ty);
+                let local = self.create_local(parent.as_base(), &name, &ty, *kind);
                 let local_symbol = self
                     .context
                     .cast_id::<Symbol>(local)
@@ -0,0 +1,219 @@
+use firefly_ast::{
+    value::{DoStatement, Value as AstValue},
+    BindingKind,
+};
+use firefly_hir::{
+    func::Callable,
+    resolve::{Symbol, SymbolTable},
+    ty::{Ty, TyKind},
+    value::{DoValue, TryValue, Value, ValueKind},
+    Entity, Id, Name,
+};
+use firefly_span::{Span, Spanned};
+
+use crate::{errors::ThrowError, AstLowerer};
+
+/// Where an error thrown by the code being lowered goes
+#[derive(Clone)]
+pub enum ErrorHandler {
+    /// Out of the current function, which can throw
+    /// errors of its type if it has one
+    Func(Option<Ty>),
+
+    /// To the `catch` block of a `do` statement, which
+    /// catches errors of the type first thrown in its body
+    Catch(Option<Ty>),
+}
+
+/// How calls to functions that can throw are marked
+#[derive(Clone, Copy)]
+pub enum TryKind {
+    /// `try`, which passes the error on
+    Try,
+
+    /// `try!`, which stops the program if there is an error
+    ForceTry,
+}
+
+impl AstLowerer {
+    /// Lowers `throw error`, which passes the
+    /// error to the current error handler
+    pub fn lower_throw(
+        &mut self,
+        error: &Spanned<AstValue>,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+        span: Span,
+    ) -> Value {
+        let mut error = self.lower_value(error, parent, symbol_table, Default::default());
+
+        if let ErrorHandler::Func(Some(expected)) | ErrorHandler::Catch(Some(expected)) = &self.error_handler {
+            let expected = expected.clone();
+            self.coerce(&mut error, &expected);
+        }
+
+        self.handle_error(&error.ty, error.span);
+
+        Value::new(ValueKind::Throw(Box::new(error)), Ty::new(TyKind::Never, span), span)
+    }
+
+    /// Lowers a value marked with `try` or `try!`, where
+    /// functions that can throw are allowed to be called
+    pub fn lower_try(
+        &mut self,
+        value: &Spanned<AstValue>,
+        kind: TryKind,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+    ) -> Value {
+        let old_try_kind = self.try_kind.replace(kind);
+        let value = self.lower_value(value, parent, symbol_table, Default::default());
+        self.try_kind = old_try_kind;
+
+        value
+    }
+
+    /// Wraps a call to a function that can throw,
+    /// checking that the error is handled
+    pub fn lower_throwing_call(&mut self, call: Value, function: &Value) -> Value {
+        let Some(error_ty) = self.thrown_error(function) else {
+            return call;
+        };
+
+        let force = match self.try_kind {
+            Some(TryKind::Try) => {
+                self.handle_error(&error_ty, call.span);
+                false
+            }
+            Some(TryKind::ForceTry) => true,
+            None => {
+                self.emit(ThrowError::MissingTry(call.span));
+
+                // Still let a `do` block know what it catches, without
+                // reporting anything else about the missing `try`
+                if let ErrorHandler::Catch(None) = self.error_handler {
+                    self.handle_error(&error_ty, call.span);
+                }
+                false
+            }
+        };
+
+        let ty = call.ty.clone();
+        let span = call.span;
+        let try_value = TryValue { call, error_ty, force };
+
+        Value::new(ValueKind::Try(Box::new(try_value)), ty, span)
+    }
+
+    /// Lowers a `do` statement, whose `catch` block
+    /// handles the errors thrown in its body
+    pub fn lower_do_statement(
+        &mut self,
+        do_stmt: &DoStatement,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+        span: Span,
+    ) -> Value {
+        // The body has its own handler, and a `try`
+        // outside of the statement doesn't cover it
+        let old_handler = std::mem::replace(&mut self.error_handler, ErrorHandler::Catch(None));
+        let old_try_kind = self.try_kind.take();
+
+        let body = self.lower_code_block(&do_stmt.body, parent, symbol_table);
+
+        let caught = std::mem::replace(&mut self.error_handler, old_handler);
+        self.try_kind = old_try_kind;
+
+        let error_ty = match caught {
+            ErrorHandler::Catch(Some(error_ty)) => error_ty,
+            _ => {
+                self.emit(ThrowError::UnreachableCatch(do_stmt.catch.span));
+                Ty::new(TyKind::Never, span)
+            }
+        };
+
+        // The error can be bound to a name in the `catch` block
+        let binding = match &do_stmt.binding {
+            Some(binding) => self.lower_name(binding),
+            None => Name { name: String::new(), span },
+        };
+
+        let local = self.create_local(parent, &binding, &error_ty, BindingKind::Let);
+        let local_symbol = self
+            .context()
+            .cast_id::<Symbol>(local)
+            .expect("internal compiler error: local doesn't have a symbol");
+
+        symbol_table.push_scope();
+        if do_stmt.binding.is_some() {
+            symbol_table.shadow(binding.name.clone(), local_symbol);
+        }
+        let catch = self.lower_code_block(&do_stmt.catch, parent, symbol_table);
+        symbol_table.pop_scope();
+
+        let do_value = DoValue { body, binding: local, catch };
+
+        Value::new(ValueKind::Do(Box::new(do_value)), Ty::new(TyKind::Unit, span), span)
+    }
+
+    /// Reports a function that can throw used as a value,
+    /// since function types don't say what they throw
+    pub fn require_non_throwing(&mut self, value: Value) -> Value {
+        if self.thrown_error(&value).is_some() {
+            self.emit(ThrowError::ThrowingFuncValue(value.span));
+            return Value::error(value.span);
+        }
+
+        value
+    }
+
+    /// Checks that an error thrown here is handled by the
+    /// current error handler, and has the type it expects
+    fn handle_error(&mut self, ty: &Ty, span: Span) {
+        // An error has already been reported
+        if let TyKind::Never = ty.kind {
+            return;
+        }
+
+        match &self.error_handler {
+            ErrorHandler::Func(None) => {
+                self.emit(ThrowError::Unhandled { ty: ty.clone(), span });
+            }
+
+            ErrorHandler::Func(Some(expected)) | ErrorHandler::Catch(Some(expected)) => {
+                if !self.is_assignable(ty, expected) {
+                    self.emit(ThrowError::ErrorType {
+                        expected: expected.clone(),
+                        found: ty.clone(),
+                        span,
+                    });
+                }
+            }
+
+            // The first error thrown decides what is caught
+            ErrorHandler::Catch(None) => {
+                self.error_handler = ErrorHandler::Catch(Some(Ty::new(ty.kind.clone(), span)));
+            }
+        }
+    }
+
+    /// The type of error a function value can throw, with the
+    /// generic arguments it is called with filled in
+    fn thrown_error(&self, function: &Value) -> Option<Ty> {
+        let (func, receiver) = match &function.kind {
+            ValueKind::StaticFunc(func) => (func, None),
+            ValueKind::InstanceFunc(receiver, func) => (func, Some(receiver)),
+
+            _ => return None,
+        };
+
+        let throws = self.context().try_get::<Callable>(func.id)?.throws.as_ref()?;
+        let mut error_ty = throws.substitute(&func.substitution(self.context()));
+
+        if let Some(receiver) = receiver {
+            error_ty = error_ty.substitute(&self.receiver_substitution(receiver));
+        }
+
+        Some(error_ty.normalize(self.context()))
+    }
+}
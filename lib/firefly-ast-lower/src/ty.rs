@@ -1,11 +1,11 @@
 use firefly_ast::ty::Ty as AstTy;
 use firefly_hir::{
-    items::TypeAlias, resolve::SymbolTable, ty::{HasType, Ty as HirTy, TyKind as HirTyKind}, Entity, Id
+    items::{ProtocolDef, TypeAlias}, resolve::SymbolTable, ty::{HasType, Ty as HirTy, TyKind as HirTyKind}, Entity, Id
 };
 use firefly_span::Spanned;
 use itertools::Itertools;
 
-use crate::AstLowerer;
+use crate::{errors::TypeError, AstLowerer};
 
 impl AstLowerer {
     pub fn lower_ty(&mut self, ty: &Spanned<AstTy>, parent: Id<Entity>, symbol_table: &SymbolTable) -> HirTy {
@@ -26,10 +26,39 @@ impl AstLowerer {
             }
 
             AstTy::Path(path) => match self.resolve_type(path, parent, symbol_table) {
-                Some(ty) => ty.kind,
-                None => HirTyKind::Unit,
+                // Keeps the alias the type was written with
+                Some(resolved) => {
+                    // Values of the protocol wouldn't know its associated types
+                    if let HirTyKind::Protocol(protocol) = resolved.kind {
+                        if !ProtocolDef::associated_types(protocol, &self.context).is_empty() {
+                            self.emit(TypeError::ProtocolWithAssociatedTypes { protocol, span: ty.span });
+                        }
+                    }
+
+                    return HirTy { span: ty.span, ..resolved };
+                }
+
+                // The error has been reported, and the never
+                // type keeps it from causing mismatches
+                None => HirTyKind::Never,
             },
 
+            AstTy::Func(params, return_ty) => {
+                let params = params
+                    .iter()
+                    .map(|param| self.lower_ty(param, parent, symbol_table))
+                    .collect_vec();
+                let return_ty = self.lower_ty(return_ty, parent, symbol_table);
+
+                HirTyKind::Func(params, Box::new(return_ty))
+            }
+
+            AstTy::Optional(inner) => {
+                let inner = self.lower_ty(inner, parent, symbol_table);
+
+                HirTyKind::Optional(Box::new(inner))
+            }
+
             AstTy::Error => unreachable!()
         };
 
@@ -0,0 +1,352 @@
+use firefly_hir::{
+    func::Callable,
+    items::conformance::Conformance,
+    stmt::{CodeBlock, StmtKind},
+    ty::{Ty, TyKind},
+    value::{ElseValue, IfValue, LiteralValue, Value, ValueKind},
+    HirContext, Id,
+};
+use firefly_span::Span;
+
+use crate::{errors::TypeError, AstLowerer};
+
+/// Checks that a value of type `found` can be used
+/// where a value of type `expected` is required
+///
+/// Diverging values have the never type, and can
+/// be used anywhere. Types that failed to resolve are
+/// also the never type, and accept any value, since
+/// the error has already been reported
+pub fn is_assignable(found: &Ty, expected: &Ty) -> bool {
+    if let (TyKind::Never, _) | (_, TyKind::Never) = (&found.kind, &expected.kind) {
+        return true;
+    }
+
+    found == expected
+}
+
+/// Checks that a value can be used where a value of
+/// type `expected` is required, once literals are coerced
+///
+/// Integer literals can become any integer type, values
+/// can become any protocol their type conforms to, and
+/// `nil` and values can become an optional
+pub fn is_coercible(value: &Value, expected: &Ty, context: &HirContext) -> bool {
+    match (&value.kind, &expected.kind) {
+        (ValueKind::Literal(LiteralValue::Integer(_)), TyKind::Integer(_)) => true,
+        (ValueKind::Tuple(items), TyKind::Tuple(item_tys)) if items.len() == item_tys.len() => {
+            items.iter().zip(item_tys).all(|(item, ty)| is_coercible(item, ty, context))
+        }
+        (_, TyKind::Protocol(protocol)) if Conformance::conforms_to(&value.ty, *protocol, context) => true,
+        (ValueKind::Nil, TyKind::Optional(_)) => true,
+        (_, TyKind::Optional(inner)) if value.ty != *expected && is_coercible(value, inner, context) => true,
+
+        _ => is_assignable(&value.ty, expected),
+    }
+}
+
+impl AstLowerer {
+    /// Checks that a value of type `found` can be used
+    /// where a value of type `expected` is required
+    pub fn is_assignable(&self, found: &Ty, expected: &Ty) -> bool {
+        is_assignable(found, expected)
+    }
+
+    /// Gives integer literals the integer type they are
+    /// used as, checking that they fit in that type, and
+    /// wraps values used as a protocol they conform to,
+    /// or as an optional
+    pub fn coerce(&self, value: &mut Value, expected: &Ty) {
+        match (&mut value.kind, &expected.kind) {
+            (ValueKind::Literal(literal @ LiteralValue::Integer(_)), TyKind::Integer(kind)) => {
+                if value.ty.kind == expected.kind {
+                    return;
+                }
+
+                if !literal.integer_value().is_some_and(|n| kind.contains(n)) {
+                    self.emit(TypeError::LiteralOutOfRange {
+                        ty: expected.clone(),
+                        span: value.span,
+                    });
+                }
+
+                value.ty = Ty::new(expected.kind.clone(), value.ty.span);
+            }
+            (ValueKind::Tuple(items), TyKind::Tuple(item_tys)) if items.len() == item_tys.len() => {
+                for (item, ty) in items.iter_mut().zip(item_tys) {
+                    self.coerce(item, ty);
+                }
+
+                let tys = items.iter().map(|item| item.ty.clone()).collect();
+                value.ty = Ty::new(TyKind::Tuple(tys), value.ty.span);
+            }
+            (_, TyKind::Protocol(protocol)) => {
+                if !Conformance::conforms_to(&value.ty, *protocol, self.context()) {
+                    return;
+                }
+
+                let inner = std::mem::replace(value, Value::error(value.span));
+                let span = inner.span;
+
+                *value = Value::new(
+                    ValueKind::Existential(Box::new(inner)),
+                    Ty::new(expected.kind.clone(), span),
+                    span,
+                );
+            }
+            (ValueKind::Nil, TyKind::Optional(_)) => {
+                value.ty = Ty::new(expected.kind.clone(), value.ty.span);
+            }
+            (_, TyKind::Optional(inner)) => {
+                if value.ty == *expected
+                    || matches!(value.ty.kind, TyKind::Never)
+                    || !is_coercible(value, inner, self.context())
+                {
+                    return;
+                }
+
+                self.coerce(value, inner);
+
+                let inner = std::mem::replace(value, Value::error(value.span));
+                let span = inner.span;
+
+                *value = Value::new(
+                    ValueKind::Wrap(Box::new(inner)),
+                    Ty::new(expected.kind.clone(), span),
+                    span,
+                );
+            }
+
+            _ => {}
+        }
+    }
+
+    /// Checks the arguments of a call against the
+    /// parameters of the function being called
+    pub fn check_call_args(&self, function: &Value, args: &mut [Value], span: Span) {
+        let TyKind::Func(params, _) = &function.ty.kind else {
+            return;
+        };
+
+        self.check_mutating_call(function);
+
+        if params.len() != args.len() {
+            self.emit(TypeError::ArgumentCount {
+                expected: params.len(),
+                found: args.len(),
+                span,
+            });
+            return;
+        }
+
+        let param_spans = self.param_spans(function);
+
+        for (i, (param, arg)) in params.iter().zip(args).enumerate() {
+            self.coerce(arg, param);
+
+            if self.is_assignable(&arg.ty, param) {
+                continue;
+            }
+
+            self.emit(TypeError::ArgumentType {
+                expected: param.clone(),
+                found: arg.ty.clone(),
+                span: arg.span,
+                param: param_spans.as_ref().map(|spans| spans[i]),
+            });
+        }
+    }
+
+    /// Checks that the condition of an if
+    /// statement or a loop is a `bool`
+    pub fn check_condition(&self, condition: &Value) {
+        let bool_ty = Ty::new_unspanned(TyKind::Bool);
+
+        if !self.is_assignable(&condition.ty, &bool_ty) {
+            self.emit(TypeError::ConditionType {
+                found: condition.ty.clone(),
+                span: condition.span,
+            });
+        }
+    }
+
+    /// Checks that a value can be stored in
+    /// a place of type `expected`
+    pub fn check_assign(&self, expected: &Ty, value: &mut Value) {
+        self.coerce(value, expected);
+
+        if !self.is_assignable(&value.ty, expected) {
+            self.emit(TypeError::AssignType {
+                expected: expected.clone(),
+                found: value.ty.clone(),
+                span: value.span,
+            });
+        }
+    }
+
+    /// Checks the operands of an operator that was
+    /// lowered to a function call
+    pub fn check_operator_args(&self, value: &mut Value) {
+        if let ValueKind::Invoke(function, args) = &mut value.kind {
+            self.check_call_args(function, args, value.span);
+        }
+    }
+
+    /// Finds where the parameters of a function were declared
+    fn param_spans(&self, function: &Value) -> Option<Vec<Span>> {
+        let func = match &function.kind {
+            ValueKind::StaticFunc(func) => func.id,
+            ValueKind::InstanceFunc(_, func) => func.id,
+
+            _ => return None,
+        };
+
+        let callable = self.context().try_get::<Callable>(func)?;
+
+        Some(callable.params.iter().map(|param| param.bind_name.span).collect())
+    }
+
+    /// Checks that a value returned with `return` matches
+    /// the return type of the current function
+    pub fn check_return_value(&self, value: &mut Value) {
+        let Some(return_ty) = &self.return_ty else {
+            return;
+        };
+
+        self.coerce(value, return_ty);
+
+        if !self.is_assignable(&value.ty, return_ty) {
+            self.emit(TypeError::ReturnType {
+                expected: return_ty.clone(),
+                found: value.ty.clone(),
+                span: value.span,
+            });
+        }
+    }
+
+    /// Checks that every path through the body of a
+    /// function returns a value of the right type
+    pub fn check_func_body(&mut self, body: Id<CodeBlock>, return_ty: &Ty) {
+        // Functions that return nothing discard the value
+        // of their last expression
+        if let TyKind::Unit = return_ty.kind {
+            return;
+        }
+
+        if let Some(mut yields) = self.context.get_mut(body).yields.take() {
+            self.coerce(&mut yields, return_ty);
+            self.context.get_mut(body).yields = Some(yields);
+        }
+
+        let code_block = self.context().get(body);
+
+        // A block that ends in a statement, like an if
+        // without an else, doesn't yield a value
+        let yields = code_block
+            .yields
+            .as_ref()
+            .filter(|yields| !(matches!(yields.ty.kind, TyKind::Unit) && is_statement(yields)));
+
+        if let Some(yields) = yields {
+            if !self.diverges(yields) && !self.is_assignable(&yields.ty, return_ty) {
+                self.emit(TypeError::ReturnType {
+                    expected: return_ty.clone(),
+                    found: yields.ty.clone(),
+                    span: yields.span,
+                });
+            }
+
+            return;
+        }
+
+        if !self.block_diverges(body) {
+            self.emit(TypeError::MissingReturn {
+                expected: return_ty.clone(),
+                span: code_block.span.last(),
+            });
+        }
+    }
+
+    /// Returns whether evaluating a value always leaves
+    /// the current block, by returning, breaking, or continuing
+    pub fn diverges(&self, value: &Value) -> bool {
+        if let TyKind::Never = value.ty.kind {
+            return true;
+        }
+
+        match &value.kind {
+            ValueKind::Tuple(items) => items.iter().any(|item| self.diverges(item)),
+            ValueKind::TupleMember(parent, _) => self.diverges(parent),
+            ValueKind::FieldOf(parent, _) => self.diverges(parent),
+            ValueKind::InstanceFunc(parent, _) => self.diverges(parent),
+            ValueKind::Wrap(inner) | ValueKind::Unwrap(inner) => self.diverges(inner),
+            ValueKind::Coalesce(optional, _) => self.diverges(optional),
+            ValueKind::OptionalChain(chain) => self.diverges(&chain.optional),
+            ValueKind::Assign(place, value) => self.diverges(place) || self.diverges(value),
+            ValueKind::Invoke(function, args) => {
+                self.diverges(function) || args.iter().any(|arg| self.diverges(arg))
+            }
+            ValueKind::Try(try_value) => self.diverges(&try_value.call),
+            ValueKind::Do(do_value) => {
+                self.block_diverges(do_value.body) && self.block_diverges(do_value.catch)
+            }
+            ValueKind::If(if_value) => self.if_diverges(if_value),
+            ValueKind::While(while_value) => self.diverges(&while_value.condition),
+            ValueKind::For(for_value) => {
+                self.diverges(&for_value.start) || self.diverges(&for_value.end)
+            }
+            ValueKind::ForEach(for_each) => self.diverges(&for_each.sequence),
+            ValueKind::Match(match_value) => {
+                self.diverges(&match_value.scrutinee)
+                    || match_value.arms.iter().all(|arm| self.block_diverges(arm.body))
+            }
+
+            _ => false,
+        }
+    }
+
+    /// An if statement diverges if its condition diverges,
+    /// or if every branch diverges
+    fn if_diverges(&self, if_value: &IfValue) -> bool {
+        if self.diverges(&if_value.condition) {
+            return true;
+        }
+
+        let negative_diverges = match &if_value.negative {
+            Some(ElseValue::Else(negative)) => self.block_diverges(*negative),
+            Some(ElseValue::ElseIf(negative)) => self.if_diverges(negative),
+            None => false,
+        };
+
+        negative_diverges && self.block_diverges(if_value.positive)
+    }
+
+    /// Returns whether a code block always diverges
+    pub fn block_diverges(&self, code_block: Id<CodeBlock>) -> bool {
+        let code_block = self.context().get(code_block);
+
+        let stmts_diverge = code_block.stmts.iter().any(|stmt| match &stmt.kind {
+            StmtKind::Value(value) => self.diverges(value),
+            StmtKind::Bind(_, _, _, value) => self.diverges(value),
+        });
+
+        stmts_diverge || code_block.yields.as_ref().is_some_and(|yields| self.diverges(yields))
+    }
+}
+
+/// Whether a value is only used for its effects, like a loop,
+/// rather than an expression that happens to yield `()`
+fn is_statement(value: &Value) -> bool {
+    matches!(
+        value.kind,
+        ValueKind::If(_)
+            | ValueKind::While(_)
+            | ValueKind::For(_)
+            | ValueKind::ForEach(_)
+            | ValueKind::Loop(_)
+            | ValueKind::Repeat(_)
+            | ValueKind::Match(_)
+            | ValueKind::Do(_)
+            | ValueKind::Assign(..)
+    )
+}
@@ -1,24 +1,29 @@
 use crate::{
     errors::{StringError, TypeError, ValueError},
     labels::LoopLabel,
-    resolve_condition::CallableResolveCondition,
+    throws::TryKind,
+    resolve_condition::{CallableResolveCondition, ResolveCondition, TypeResolveCondition, UnconditionalResolveCondition},
     AstLowerer,
 };
 use firefly_ast::{
-    operator::InfixOperator,
-    value::{ElseStatement, IfStatement, Value as AstValue},
-    PathSegment,
+    operator::{InfixOperator, PrefixOperator},
+    value::{ElseStatement, ForStatement, IfStatement, Value as AstValue},
+    BindingKind, Name as AstName, PathSegment,
 };
 use firefly_hir::{
-    resolve::SymbolTable,
-    ty::{Ty, TyKind},
+    items::{EnumCase, StructDef},
+    resolve::{InstanceMemberTable, Symbol, SymbolTable},
+    stmt::{CodeBlock, Local},
+    ty::{IntegerKind, Ty, TyKind},
     value::{
-        ElseValue, IfValue, LiteralValue, Value as HirValue, ValueKind as HirValueKind, WhileValue,
+        ElseValue, ForEachValue, ForValue, IfValue, LiteralValue, LoopValue, RepeatValue, Value as HirValue,
+        ValueKind as HirValueKind, WhileValue,
     },
-    Entity, Id,
+    Entity, Id, Name,
 };
 use firefly_span::{Span, Spanned};
 use itertools::Itertools;
+use std::iter::Peekable;
 
 #[derive(Copy, Clone)]
 pub struct LowerValueContext {
@@ -61,10 +66,7 @@ impl AstLowerer {
                 // Remove the underscores
                 let santized_num = num.item.replace("_", "");
 
-                let int_kind = HirValueKind::Literal(LiteralValue::Integer(santized_num));
-                let int_type = Ty::new(TyKind::Integer, span);
-
-                (int_kind, int_type)
+                return self.lower_integer_literal(santized_num, span);
             }
 
             AstValue::StringLiteral(string) => {
@@ -85,45 +87,94 @@ impl AstLowerer {
                 (float_kind, float_type)
             }
 
-            AstValue::Call(function, args) => {
-                let labels = args.iter().map(|arg| arg.label.clone()).collect_vec();
-
-                let function_value =
-                    self.lower_func_value(function, parent, symbol_table, labels, context.reset());
+            AstValue::Nil => {
+                // Nil takes on the optional type it's used as
+                let inner_ty = Ty::new(TyKind::Never, span);
 
-                let TyKind::Func(_, return_ty) = &function_value.ty.kind else {
-                    self.emit(TypeError::CantCall(function_value.span));
+                (HirValueKind::Nil, Ty::new(TyKind::Optional(Box::new(inner_ty)), span))
+            }
 
-                    return HirValue::default();
-                };
-                let return_ty = return_ty.as_ref().clone();
+            AstValue::Call(function, args) => {
+                let labels = args.iter().map(|arg| arg.label.clone()).collect_vec();
 
+                // The arguments are lowered first, so their
+                // types can be used to pick an overload
                 let args = args
                     .iter()
                     .map(|arg| self.lower_value(&arg.value, parent, symbol_table, context.reset()))
                     .collect_vec();
 
-                let invoke = HirValueKind::Invoke(Box::new(function_value), args);
+                // Calling a method through an optional
+                // is `nil` if the optional is
+                if let AstValue::OptionalMember(optional, member) = &function.item {
+                    return self.lower_optional_chain(optional, parent, symbol_table, span, |this, unwrapped, symbol_table| {
+                        let condition = CallableResolveCondition { labels, args: args.clone() };
+                        let function_value = this.resolve_member(unwrapped, member, parent, symbol_table, condition)?;
+
+                        Some(this.lower_call(function_value, args, span))
+                    });
+                }
 
-                (invoke, return_ty)
+                let function_value = self.lower_func_value(
+                    function,
+                    parent,
+                    symbol_table,
+                    labels.clone(),
+                    args.clone(),
+                    context.reset(),
+                );
+
+                if let HirValueKind::InitFor(_) = &function_value.kind {
+                    return self.lower_memberwise_call(function_value, &labels, args, span);
+                }
+
+                if let HirValueKind::InitCase(case) = &function_value.kind {
+                    self.check_case_labels(*case, &labels);
+                }
+
+                return self.lower_call(function_value, args, span);
             }
 
             AstValue::Path(path) => match self.resolve_value(path, parent, symbol_table) {
-                Some(value) => return value,
-                None => (HirValueKind::Unit, Ty::new(TyKind::Unit, span)),
+                Some(value) => {
+                    let value = self.require_generics(value);
+                    let value = self.require_non_mutating(value);
+                    return self.require_non_throwing(value);
+                }
+                None => return HirValue::error(span),
             },
 
             AstValue::Member(parent_val, member) => {
                 let parent_val =
                     self.lower_value(parent_val, parent, symbol_table, context.reset());
 
-                if let Some(member) =
-                    self.resolve_instance_member(parent_val, member.clone(), parent)
-                {
-                    return member;
+                if let Some(member) = self.resolve_member(parent_val, member, parent, symbol_table, UnconditionalResolveCondition) {
+                    let member = self.require_generics(member);
+                    let member = self.require_non_mutating(member);
+                    return self.require_non_throwing(member);
                 }
 
-                return HirValue::default();
+                return HirValue::error(span);
+            }
+
+            AstValue::OptionalMember(optional, member) => {
+                return self.lower_optional_chain(optional, parent, symbol_table, span, |this, unwrapped, symbol_table| {
+                    let member = this.resolve_member(unwrapped, member, parent, symbol_table, UnconditionalResolveCondition)?;
+
+                    let member = this.require_generics(member);
+                    let member = this.require_non_mutating(member);
+                    Some(this.require_non_throwing(member))
+                });
+            }
+
+            AstValue::Unwrap(optional) => {
+                let optional = self.lower_value(optional, parent, symbol_table, context.reset());
+
+                let Some(inner_ty) = self.optional_inner(&optional) else {
+                    return HirValue::error(span);
+                };
+
+                (HirValueKind::Unwrap(Box::new(optional)), Ty::new(inner_ty.kind, span))
             }
 
             AstValue::TupleMember(parent_val, index) => {
@@ -137,12 +188,12 @@ impl AstLowerer {
 
                 let TyKind::Tuple(items) = &parent_val.ty.kind else {
                     // error
-                    return HirValue::default();
+                    return HirValue::error(span);
                 };
 
                 if index_num >= items.len() {
                     // error
-                    return HirValue::default();
+                    return HirValue::error(span);
                 }
 
                 let ty = items[index_num].clone();
@@ -154,13 +205,15 @@ impl AstLowerer {
             }
 
             AstValue::Return(return_value) => {
-                let return_value = if let Some(return_value) = return_value {
+                let mut return_value = if let Some(return_value) = return_value {
                     self.lower_value(return_value, parent, symbol_table, context.reset())
                 } else {
                     let span = value.span;
                     HirValue::new(HirValueKind::Unit, Ty::new(TyKind::Unit, span), span)
                 };
 
+                self.check_return_value(&mut return_value);
+
                 (
                     HirValueKind::Return(Box::new(return_value)),
                     Ty::new(TyKind::Never, value.span),
@@ -170,11 +223,42 @@ impl AstLowerer {
             AstValue::If(if_statement) => {
                 let if_value =
                     self.lower_if_statement(&if_statement, parent, symbol_table, context);
+                let ty = self.check_if_branch_types(&if_value, value.span);
 
-                (
-                    HirValueKind::If(Box::new(if_value)),
-                    Ty::new(TyKind::Unit, value.span),
-                )
+                (HirValueKind::If(Box::new(if_value)), ty)
+            }
+
+            AstValue::For(for_statement) => {
+                let Some(for_value) = self.lower_for_statement(for_statement, parent, symbol_table)
+                else {
+                    return HirValue::error(span);
+                };
+
+                (for_value, Ty::new(TyKind::Unit, value.span))
+            }
+
+            AstValue::Throw(error) => {
+                return self.lower_throw(error, parent, symbol_table, span);
+            }
+
+            AstValue::Try(value) => {
+                return self.lower_try(value, TryKind::Try, parent, symbol_table);
+            }
+
+            AstValue::ForceTry(value) => {
+                return self.lower_try(value, TryKind::ForceTry, parent, symbol_table);
+            }
+
+            AstValue::Do(do_statement) => {
+                return self.lower_do_statement(do_statement, parent, symbol_table, span);
+            }
+
+            AstValue::Closure(closure) => {
+                return self.lower_closure(closure, parent, symbol_table, span);
+            }
+
+            AstValue::Match(match_statement) => {
+                self.lower_match_statement(match_statement, parent, symbol_table, value.span)
             }
 
             AstValue::While(while_statement) => {
@@ -188,9 +272,10 @@ impl AstLowerer {
                     symbol_table,
                     context.reset(),
                 );
+                self.check_condition(&condition);
 
                 self.label_stack
-                    .push(label.clone(), while_statement.body.id);
+                    .push(label.clone(), while_statement.body.id, false);
                 let body = self.lower_code_block(&while_statement.body, parent, symbol_table);
                 self.label_stack.pop();
 
@@ -206,27 +291,59 @@ impl AstLowerer {
                 )
             }
 
-            AstValue::Break(label) => {
-                let found_label = if let Some(label) = label {
-                    self.label_stack.find(&label.item)
-                } else {
-                    self.label_stack.last()
+            AstValue::Loop(loop_statement) => {
+                let label = loop_statement.label.as_ref().map(|label| self.lower_name(label));
+
+                self.label_stack.push(label.clone(), loop_statement.body.id, true);
+                let body = self.lower_code_block(&loop_statement.body, parent, symbol_table);
+                let loop_label = self
+                    .label_stack
+                    .pop()
+                    .expect("internal compiler error: loop label was popped");
+
+                // A loop that is never broken out of never finishes
+                let ty_kind = match (loop_label.has_break, loop_label.break_ty) {
+                    (false, _) => TyKind::Never,
+                    (true, Some(break_ty)) => break_ty.kind,
+                    (true, None) => TyKind::Unit,
                 };
 
-                match found_label {
-                    Some(LoopLabel { code_block, .. }) => (
-                        HirValueKind::Break(*code_block),
-                        Ty::new(TyKind::Never, value.span),
-                    ),
-                    None => {
-                        if let Some(label) = label {
-                            self.emit(ValueError::UndefinedBreakLabel(label.clone()));
-                        } else {
-                            self.emit(ValueError::BreakOutsideLoop(value.span));
-                        }
-                        (HirValueKind::Unit, Ty::new(TyKind::Never, value.span))
-                    }
-                }
+                (
+                    HirValueKind::Loop(Box::new(LoopValue { label, body })),
+                    Ty::new(ty_kind, value.span),
+                )
+            }
+
+            AstValue::Repeat(repeat_statement) => {
+                let label = repeat_statement.label.as_ref().map(|label| self.lower_name(label));
+
+                self.label_stack.push(label.clone(), repeat_statement.body.id, false);
+                let body = self.lower_code_block(&repeat_statement.body, parent, symbol_table);
+                self.label_stack.pop();
+
+                // Locals from the body aren't visible in the condition
+                let condition = self.lower_value(
+                    &repeat_statement.condition,
+                    parent,
+                    symbol_table,
+                    context.reset(),
+                );
+                self.check_condition(&condition);
+
+                let repeat_value = RepeatValue {
+                    label,
+                    body,
+                    condition,
+                };
+
+                (
+                    HirValueKind::Repeat(Box::new(repeat_value)),
+                    Ty::new(TyKind::Unit, value.span),
+                )
+            }
+
+            AstValue::Break(label, break_value) => {
+                return self.lower_break(label.as_ref(), break_value.as_deref(), parent, symbol_table, span);
             }
 
             AstValue::Continue(label) => {
@@ -254,11 +371,12 @@ impl AstLowerer {
 
             AstValue::Assign(place, assignee) => {
                 let place = self.lower_value(place, parent, symbol_table, context.reset());
-                let assignee = self.lower_value(assignee, parent, symbol_table, context.reset());
+                let mut assignee =
+                    self.lower_value(assignee, parent, symbol_table, context.reset());
 
-                if !place.is_mutable() {
-                    self.emit(ValueError::NotMutable(place.span));
-                }
+                self.check_mutable(&place);
+
+                self.check_assign(&place.ty, &mut assignee);
 
                 (
                     HirValueKind::Assign(Box::new(place), Box::new(assignee)),
@@ -267,69 +385,106 @@ impl AstLowerer {
             }
 
             AstValue::Prefix(op, value) => {
+                // Negative integer literals are folded, so
+                // they can take on any signed integer type
+                if let (PrefixOperator::Negate, AstValue::IntegerLiteral(num)) = (op, &value.item) {
+                    let santized_num = format!("-{}", num.item.replace("_", ""));
+
+                    return self.lower_integer_literal(santized_num, span);
+                }
+
                 let unit = self.lower_value(value, parent, symbol_table, context.reset());
 
-                if let TyKind::Integer = unit.ty.kind {
-                    return self.get_integer_prefix_operator(&op, unit, span).unwrap();
-                } else if let TyKind::Float = unit.ty.kind {
-                    return self.get_float_prefix_operator(&op, unit, span).unwrap();
-                } else if let TyKind::Bool = unit.ty.kind {
-                    return self.get_boolean_prefix_operator(&op, unit, span).unwrap();
-                } else if let Some(operator_func) = self.resolve_instance_member(
-                    unit,
-                    PathSegment::new(Spanned::new(op.get_verb().into(), value.span)),
-                    parent,
-                ) {
-                    let return_type = match &operator_func.ty.kind {
-                        TyKind::Func(_, return_type) => return_type.as_ref().clone(),
-                        _ => operator_func.ty.clone(),
-                    };
+                let builtin = match unit.ty.kind {
+                    TyKind::Integer(_) => self.get_integer_prefix_operator(&op, unit.clone(), span),
+                    TyKind::Float => self.get_float_prefix_operator(&op, unit.clone(), span),
+                    TyKind::Bool => self.get_boolean_prefix_operator(&op, unit.clone(), span),
+                    _ => None,
+                };
 
-                    (
-                        HirValueKind::Invoke(Box::new(operator_func), vec![]),
-                        return_type,
-                    )
-                } else {
-                    todo!()
-                }
+                // Other types define operators as methods
+                let operator_value = builtin.or_else(|| {
+                    self.get_method_operator(op.get_verb(), unit.clone(), vec![], span, parent)
+                });
+
+                let Some(mut operator_value) = operator_value else {
+                    return self.operator_not_defined(op.symbol(), vec![op.get_verb()], &unit, span);
+                };
+
+                self.check_operator_args(&mut operator_value);
+
+                return operator_value;
             }
 
             AstValue::Infix(lhs, op, rhs) => {
                 let (lhs, op, rhs) = self.reorganize(lhs, op, rhs, context.is_in_operator);
 
-                let left = self.lower_value(&lhs, parent, symbol_table, context.in_operator());
-                let right = self.lower_value(&rhs, parent, symbol_table, context.in_operator());
-
-                if let InfixOperator::Assign = op {
-                    return HirValue::new(
-                        HirValueKind::Assign(Box::new(left), Box::new(right)),
-                        Ty::new(TyKind::Unit, span),
-                        span,
-                    );
-                } else if let TyKind::Integer = left.ty.kind {
-                    return self.get_integer_operator(&op, left, right, span).unwrap();
-                } else if let TyKind::Float = left.ty.kind {
-                    return self.get_float_operator(&op, left, right, span).unwrap();
-                } else if let TyKind::Bool = left.ty.kind {
-                    return self.get_boolean_operator(&op, left, right, span).unwrap();
-                } else if let Some(operator_func) = self.resolve_instance_member(
-                    left,
-                    PathSegment::new(Spanned::new(op.get_verb().into(), span)),
-                    parent,
-                ) {
-                    let return_type = match &operator_func.ty.kind {
-                        TyKind::Func(_, return_type) => return_type.as_ref().clone(),
-                        _ => operator_func.ty.clone(),
+                // Ranges don't have a type yet, so they
+                // can only be looped over
+                if let InfixOperator::HalfOpenRange | InfixOperator::ClosedRange = op {
+                    self.emit(ValueError::RangeOutsideFor(span));
+                    return HirValue::error(span);
+                }
+
+                let is_assignment = matches!(op, InfixOperator::Assign) || op.assign_operator().is_some();
+
+                // Assigning through an optional, like `person?.name = "Ann"`,
+                // only evaluates the assignment if the optional isn't `nil`
+                if let (true, AstValue::OptionalMember(optional, member)) = (is_assignment, &lhs.item) {
+                    return self.lower_optional_chain(optional, parent, symbol_table, span, |this, unwrapped, symbol_table| {
+                        let left = this.resolve_member(unwrapped, member, parent, symbol_table, UnconditionalResolveCondition)?;
+                        let right = this.lower_value(&rhs, parent, symbol_table, context.in_operator());
+
+                        Some(this.lower_assignment(left, &op, right, span, parent))
+                    });
+                }
+
+                let mut left = self.lower_value(&lhs, parent, symbol_table, context.in_operator());
+                let mut right = self.lower_value(&rhs, parent, symbol_table, context.in_operator());
+
+                if let InfixOperator::Coalesce = op {
+                    return self.lower_coalesce(left, right, span);
+                }
+
+                if is_assignment {
+                    return self.lower_assignment(left, &op, right, span, parent);
+                }
+
+                // An integer literal takes on the type of the other operand
+                self.coerce(&mut left, &right.ty.clone());
+                self.coerce(&mut right, &left.ty.clone());
+
+                // Only instances of classes have an identity to compare
+                if let InfixOperator::Identical | InfixOperator::NotIdentical = op {
+                    let Some(mut operator_value) = self.get_identity_operator(&op, left.clone(), right, span) else {
+                        return self.identity_not_defined(op.symbol(), &left, span);
                     };
 
-                    return HirValue::new(
-                        HirValueKind::Invoke(Box::new(operator_func), vec![right]),
-                        return_type,
-                        span,
-                    );
-                } else {
-                    todo!()
+                    self.check_operator_args(&mut operator_value);
+
+                    return operator_value;
                 }
+
+                let builtin = match &left.ty.kind {
+                    TyKind::Integer(_) => self.get_integer_operator(&op, left.clone(), right.clone(), span),
+                    TyKind::Float => self.get_float_operator(&op, left.clone(), right.clone(), span),
+                    TyKind::Bool => self.get_boolean_operator(&op, left.clone(), right.clone(), span),
+                    TyKind::EnumDef(_) => self.get_enum_operator(&op, left.clone(), right.clone(), span),
+                    _ => None,
+                };
+
+                // Other types define operators as methods
+                let operator_value = builtin.or_else(|| {
+                    self.get_method_operator(op.get_verb(), left.clone(), vec![right], span, parent)
+                });
+
+                let Some(mut operator_value) = operator_value else {
+                    return self.operator_not_defined(op.symbol(), vec![op.get_verb()], &left, span);
+                };
+
+                self.check_operator_args(&mut operator_value);
+
+                return operator_value;
             }
 
             AstValue::Error => unreachable!(),
@@ -338,29 +493,176 @@ impl AstLowerer {
         HirValue::new(kind, ty, span)
     }
 
+    /// Reports an operator used on a type that doesn't
+    /// define it, naming the methods that were looked for
+    fn operator_not_defined(
+        &mut self,
+        operator: &'static str,
+        methods: Vec<&'static str>,
+        value: &HirValue,
+        span: Span,
+    ) -> HirValue {
+        // Don't cascade errors from the operand
+        if !matches!(value.ty.kind, TyKind::Never) {
+            self.emit(TypeError::OperatorNotDefined {
+                operator,
+                methods,
+                ty: value.ty.clone(),
+                span,
+            });
+        }
+
+        HirValue::error(span)
+    }
+
+    /// Reports an identity operator used on a value that
+    /// isn't an instance of a class
+    fn identity_not_defined(&mut self, operator: &'static str, value: &HirValue, span: Span) -> HirValue {
+        // Don't cascade errors from the operand
+        if !matches!(value.ty.kind, TyKind::Never) {
+            self.emit(TypeError::NotIdentifiable {
+                operator,
+                ty: value.ty.clone(),
+                span,
+            });
+        }
+
+        HirValue::error(span)
+    }
+
+    /// Lowers a call to a function value, inferring its generic
+    /// arguments and checking the arguments it is called with
+    pub fn lower_call(&mut self, function_value: HirValue, mut args: Vec<HirValue>, span: Span) -> HirValue {
+        let function_value = self.infer_generics(function_value, &args);
+
+        let TyKind::Func(_, return_ty) = &function_value.ty.kind else {
+            // An error has already been reported
+            if let TyKind::Never = function_value.ty.kind {
+                return HirValue::error(span);
+            }
+
+            self.emit(TypeError::CantCall(function_value.span));
+
+            return HirValue::error(span);
+        };
+        let return_ty = return_ty.as_ref().clone();
+
+        self.check_call_args(&function_value, &mut args, span);
+
+        let function = function_value.clone();
+        let invoke = HirValueKind::Invoke(Box::new(function_value), args);
+        let call = HirValue::new(invoke, return_ty, span);
+
+        self.lower_throwing_call(call, &function)
+    }
+
+    /// Checks the labels passed to a case, which can
+    /// be left out, but must name the case's values
+    fn check_case_labels(&self, case: Id<EnumCase>, labels: &[Option<Spanned<String>>]) {
+        let EnumCase { fields, .. } = self.context().get(case);
+
+        for ((expected, _), label) in fields.iter().zip(labels) {
+            match label {
+                Some(label) if label.item != expected.name => {
+                    self.emit(TypeError::ArgumentLabel { expected: expected.clone(), found: label.clone() });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Lowers a call to the memberwise initializer of a struct,
+    /// where fields with defaults can be left out
+    fn lower_memberwise_call(
+        &mut self,
+        init: HirValue,
+        labels: &[Option<Spanned<String>>],
+        args: Vec<HirValue>,
+        span: Span,
+    ) -> HirValue {
+        let HirValueKind::InitFor(struct_def) = &init.kind else {
+            panic!("internal compiler error: value isn't a memberwise initializer");
+        };
+        let struct_def = struct_def.id;
+
+        let fields = StructDef::fields(struct_def, self.context());
+
+        // Without any defaults, every field is passed in order
+        if !fields.iter().any(|field| self.context().get(*field).has_default) {
+            return self.lower_call(init, args, span);
+        }
+
+        let arg_labels = labels
+            .iter()
+            .map(|label| label.as_ref().map(|label| label.item.as_str()))
+            .collect_vec();
+
+        let Some(arg_indices) = StructDef::memberwise_args(struct_def, &arg_labels, self.context()) else {
+            self.emit(TypeError::MemberwiseArguments { struct_def, span });
+            return HirValue::error(span);
+        };
+
+        // The initializer is called with only the fields that were passed
+        let TyKind::Func(params, return_ty) = &init.ty.kind else {
+            panic!("internal compiler error: memberwise initializer isn't a function");
+        };
+
+        let params = params
+            .iter()
+            .zip(&arg_indices)
+            .filter(|(_, index)| index.is_some())
+            .map(|(param, _)| param.clone())
+            .collect_vec();
+
+        let init_ty = Ty::new(TyKind::Func(params, return_ty.clone()), init.ty.span);
+        let init = HirValue { ty: init_ty, ..init };
+
+        let mut call = self.lower_call(init, args, span);
+
+        // An error has already been reported
+        let HirValueKind::Invoke(function, passed) = &mut call.kind else {
+            return call;
+        };
+        let HirValueKind::InitFor(struct_def) = &function.kind else {
+            return call;
+        };
+        let substitution = struct_def.substitution(&self.context);
+
+        // The rest of the fields are given their defaults
+        let mut passed_args = std::mem::take(passed).into_iter();
+        *passed = fields
+            .into_iter()
+            .zip(arg_indices)
+            .map(|(field, index)| match index {
+                Some(_) => passed_args.next().expect("internal compiler error: missing argument"),
+                None => {
+                    let ty = self.context.get(field).ty.substitute(&substitution).normalize(&self.context);
+                    HirValue::new(HirValueKind::FieldDefault(struct_def.clone(), field), ty, span)
+                }
+            })
+            .collect_vec();
+
+        call
+    }
+
     fn lower_func_value(
         &mut self,
         value: &Spanned<AstValue>,
         parent: Id<Entity>,
         symbol_table: &mut SymbolTable,
         labels: Vec<Option<Spanned<String>>>,
+        args: Vec<HirValue>,
         context: LowerValueContext,
     ) -> HirValue {
         let span = value.span;
 
-        let condition = CallableResolveCondition { labels };
+        let condition = CallableResolveCondition { labels, args };
 
         match &value.item {
             AstValue::Path(path) => {
                 match self.resolve_value_with(path, parent, symbol_table, condition) {
                     Some(value) => return value,
-                    None => {
-                        return HirValue::new(
-                            HirValueKind::Unit,
-                            Ty::new(TyKind::Unit, span),
-                            span,
-                        );
-                    }
+                    None => return HirValue::error(span),
                 }
             }
 
@@ -368,13 +670,11 @@ impl AstLowerer {
                 let parent_val =
                     self.lower_value(parent_val, parent, symbol_table, context.reset());
 
-                if let Some(member) =
-                    self.resolve_instance_member_with(parent_val, member.clone(), parent, condition)
-                {
+                if let Some(member) = self.resolve_member(parent_val, member, parent, symbol_table, condition) {
                     return member;
                 }
 
-                return HirValue::default();
+                return HirValue::error(span);
             }
 
             _ => {
@@ -383,6 +683,154 @@ impl AstLowerer {
         }
     }
 
+    /// Lowers a value that is used as a known type, which
+    /// picks between overloaded functions named by the value
+    pub fn lower_value_as(
+        &mut self,
+        value: &Spanned<AstValue>,
+        expected: &Ty,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+    ) -> HirValue {
+        let condition = TypeResolveCondition { ty: expected.clone() };
+
+        let resolved = match &value.item {
+            AstValue::Path(path) => self.resolve_value_with(path, parent, symbol_table, condition),
+
+            AstValue::Member(parent_val, member) => {
+                let parent_val = self.lower_value(parent_val, parent, symbol_table, Default::default());
+
+                self.resolve_member(parent_val, member, parent, symbol_table, condition)
+            }
+
+            _ => return self.lower_value(value, parent, symbol_table, Default::default()),
+        };
+
+        match resolved {
+            Some(resolved) => {
+                let resolved = self.require_generics(resolved);
+                let resolved = self.require_non_mutating(resolved);
+                self.require_non_throwing(resolved)
+            }
+            None => HirValue::error(value.span),
+        }
+    }
+
+    /// Resolves a member of a value, along with the
+    /// generic arguments written after its name
+    pub fn resolve_member(
+        &mut self,
+        parent_val: HirValue,
+        member: &PathSegment,
+        parent: Id<Entity>,
+        symbol_table: &SymbolTable,
+        condition: impl ResolveCondition,
+    ) -> Option<HirValue> {
+        let (generics, generics_span) =
+            self.lower_generic_args(std::slice::from_ref(member), parent, symbol_table);
+
+        let member = self.resolve_instance_member_with(parent_val, member.clone(), parent, condition)?;
+
+        self.apply_generics(member, generics, generics_span)
+    }
+
+    /// Lowers an assignment, or a compound assignment like `+=`
+    fn lower_assignment(
+        &mut self,
+        mut left: HirValue,
+        op: &InfixOperator,
+        mut right: HirValue,
+        span: Span,
+        parent: Id<Entity>,
+    ) -> HirValue {
+        if let InfixOperator::Assign = op {
+            self.check_mutable(&left);
+
+            self.check_assign(&left.ty, &mut right);
+
+            return HirValue::new(
+                HirValueKind::Assign(Box::new(left), Box::new(right)),
+                Ty::new(TyKind::Unit, span),
+                span,
+            );
+        }
+
+        // An integer literal takes on the type of the other operand
+        self.coerce(&mut left, &right.ty.clone());
+        self.coerce(&mut right, &left.ty.clone());
+
+        let Some(assign_op) = op.assign_operator() else {
+            unreachable!("only assignments are lowered here");
+        };
+
+        // Compound assignments on builtin types are
+        // lowered to an assignment of the operator's result
+        let operator_value = match &left.ty.kind {
+            TyKind::Integer(_) => {
+                self.get_integer_operator(&assign_op, left.clone(), right.clone(), span)
+            }
+            TyKind::Float => {
+                self.get_float_operator(&assign_op, left.clone(), right.clone(), span)
+            }
+            TyKind::Bool => {
+                self.get_boolean_operator(&assign_op, left.clone(), right.clone(), span)
+            }
+            _ => None,
+        };
+
+        if let Some(mut operator_value) = operator_value {
+            self.check_mutable(&left);
+
+            self.check_operator_args(&mut operator_value);
+            self.check_assign(&left.ty, &mut operator_value);
+
+            return HirValue::new(
+                HirValueKind::Assign(Box::new(left), Box::new(operator_value)),
+                Ty::new(TyKind::Unit, span),
+                span,
+            );
+        }
+
+        // Other types can define the compound assignment as a method,
+        // or fall back to the operator followed by an assignment
+        let assign_method =
+            self.get_method_operator(op.get_verb(), left.clone(), vec![right.clone()], span, parent);
+
+        if let Some(mut operator_value) = assign_method {
+            // A `mutating` method already checks its receiver
+            if !self.is_mutating_call(&operator_value) {
+                self.check_mutable(&left);
+            }
+
+            self.check_operator_args(&mut operator_value);
+
+            return operator_value;
+        }
+
+        let Some(mut operator_value) =
+            self.get_method_operator(assign_op.get_verb(), left.clone(), vec![right], span, parent)
+        else {
+            let methods = vec![op.get_verb(), assign_op.get_verb()];
+
+            return self.operator_not_defined(op.symbol(), methods, &left, span);
+        };
+
+        self.check_mutable(&left);
+
+        self.check_operator_args(&mut operator_value);
+        self.check_assign(&left.ty, &mut operator_value);
+
+        HirValue::new(
+            HirValueKind::Assign(Box::new(left), Box::new(operator_value)),
+            Ty::new(TyKind::Unit, span),
+            span,
+        )
+    }
+
+    /// The parser reads every chain of infix operators
+    /// left to right, so `a || b && c` arrives as `(a || b) && c`.
+    /// This rebuilds the chain so tighter operators bind first,
+    /// keeping operators of the same precedence left-associative
     fn reorganize(
         &self,
         left: &Spanned<AstValue>,
@@ -394,32 +842,60 @@ impl AstLowerer {
             return (left.clone(), op.clone(), right.clone());
         }
 
-        match &left.item {
-            AstValue::Infix(inner_lhs, inner_op, inner_rhs) => {
-                let (inner_lhs, inner_op, inner_rhs) =
-                    self.reorganize(&inner_lhs, inner_op, &inner_rhs, is_in_operator);
+        // Flatten the chain into its operands and operators
+        let mut operands = vec![];
+        let mut operators = vec![op.clone()];
+        let mut next = left;
 
-                if inner_op.precedence() >= op.precedence() {
-                    //println!("{inner_lhs:?} {inner_op:?} {inner_rhs:?}");
-                    return (inner_lhs, inner_op, inner_rhs);
-                } else {
-                    let new_left = inner_lhs;
-                    let new_right = Spanned::new(
-                        AstValue::Infix(Box::new(inner_rhs), op.clone(), Box::new(right.clone())),
-                        Span::DUMMY,
-                    );
+        while let AstValue::Infix(inner_lhs, inner_op, inner_rhs) = &next.item {
+            operands.push((**inner_rhs).clone());
+            operators.push(inner_op.clone());
+            next = inner_lhs;
+        }
 
-                    //println!("{new_left:?} {inner_op:?} {new_right:?}");
+        operands.push(next.clone());
+        operands.reverse();
+        operators.reverse();
+        operands.push(right.clone());
 
-                    return (new_left, inner_op, new_right);
-                }
+        let mut operands = operands.into_iter();
+        let first = operands.next().expect("a chain has at least two operands");
+        let mut rest = operators.into_iter().zip(operands).peekable();
+
+        let combined = Self::climb_precedence(first, &mut rest, 0);
+
+        match combined.item {
+            AstValue::Infix(lhs, op, rhs) => (*lhs, op, *rhs),
+            _ => unreachable!("a chain of operators combines into an infix value"),
+        }
+    }
+
+    fn climb_precedence(
+        mut lhs: Spanned<AstValue>,
+        rest: &mut Peekable<impl Iterator<Item = (InfixOperator, Spanned<AstValue>)>>,
+        min_precedence: u32,
+    ) -> Spanned<AstValue> {
+        while let Some((op, _)) = rest.peek() {
+            let precedence = op.precedence();
+            if precedence < min_precedence {
+                break;
             }
 
-            _ => {
-                //println!("{left:?} {op:?} {right:?}");
-                return (left.clone(), op.clone(), right.clone());
+            let (op, mut rhs) = rest.next().unwrap();
+
+            while let Some((next_op, _)) = rest.peek() {
+                if next_op.precedence() <= precedence {
+                    break;
+                }
+
+                rhs = Self::climb_precedence(rhs, rest, precedence + 1);
             }
+
+            let span = lhs.span.to(rhs.span);
+            lhs = Spanned::new(AstValue::Infix(Box::new(lhs), op, Box::new(rhs)), span);
         }
+
+        lhs
     }
 
     fn lower_if_statement(
@@ -431,7 +907,35 @@ impl AstLowerer {
     ) -> IfValue {
         let condition = self.lower_value(&if_stmt.condition, parent, symbol_table, context);
 
-        let positive = self.lower_code_block(&if_stmt.positive, parent, symbol_table);
+        let (binding, positive) = match &if_stmt.binding {
+            // `if let` binds the inside of the optional in the positive block
+            Some(binding) => {
+                let inner_ty = self
+                    .optional_inner(&condition)
+                    .unwrap_or_else(|| Ty::new(TyKind::Never, binding.span));
+                let binding = self.lower_name(binding);
+
+                let local = self.create_local(parent, &binding, &inner_ty, BindingKind::Let);
+                let local_symbol = self
+                    .context()
+                    .cast_id::<Symbol>(local)
+                    .expect("internal compiler error: local doesn't have a symbol");
+
+                // The binding usually has the same name as the optional
+                symbol_table.push_scope();
+                symbol_table.shadow(binding.name.clone(), local_symbol);
+                let positive = self.lower_code_block(&if_stmt.positive, parent, symbol_table);
+                symbol_table.pop_scope();
+
+                (Some(local), positive)
+            }
+            None => {
+                self.check_condition(&condition);
+
+                (None, self.lower_code_block(&if_stmt.positive, parent, symbol_table))
+            }
+        };
+
         let negative = if_stmt.negative.as_ref().map(|negative| match negative {
             ElseStatement::Else(code_block) => {
                 ElseValue::Else(self.lower_code_block(code_block, parent, symbol_table))
@@ -443,11 +947,373 @@ impl AstLowerer {
 
         IfValue {
             condition,
+            binding,
             positive,
             negative,
         }
     }
 
+    /// Finds the type of an if from the values its branches yield.
+    /// Branches that diverge agree with any other branch
+    fn check_if_branch_types(&mut self, if_value: &IfValue, span: Span) -> Ty {
+        let mut branches = vec![if_value.positive];
+        let mut has_else = false;
+
+        let mut current = if_value;
+        loop {
+            match &current.negative {
+                Some(ElseValue::Else(negative)) => {
+                    branches.push(*negative);
+                    has_else = true;
+                    break;
+                }
+                Some(ElseValue::ElseIf(negative)) => {
+                    branches.push(negative.positive);
+                    current = negative;
+                }
+                None => break,
+            }
+        }
+
+        branches.retain(|branch| !self.block_diverges(*branch));
+
+        // Without an else, nothing is yielded if every condition is false
+        if !has_else {
+            let valued = branches
+                .iter()
+                .filter_map(|branch| self.context.get(*branch).yields.as_ref())
+                .find(|yields| !matches!(yields.ty.kind, TyKind::Unit | TyKind::Never));
+
+            if let Some(yields) = valued {
+                self.emit(TypeError::IfWithoutElse { ty: yields.ty.clone(), span: yields.span });
+            }
+
+            return Ty::new(TyKind::Unit, span);
+        }
+
+        let branch_tys = branches
+            .iter()
+            .map(|branch| {
+                let yields = self.context.get(*branch).yields.as_ref();
+
+                yields.map_or(Ty::new(TyKind::Unit, span), |yields| yields.ty.clone())
+            })
+            .collect_vec();
+
+        if branch_tys.is_empty() {
+            return Ty::new(TyKind::Never, span);
+        }
+
+        if branch_tys.iter().any(|ty| matches!(ty.kind, TyKind::Unit)) {
+            return Ty::new(TyKind::Unit, span);
+        }
+
+        let expected = Ty::new(branch_tys[0].kind.clone(), span);
+
+        for branch in branches {
+            let Some(mut yields) = self.context.get_mut(branch).yields.take() else {
+                continue;
+            };
+
+            self.coerce(&mut yields, &expected);
+
+            if !self.is_assignable(&yields.ty, &expected) {
+                self.emit(TypeError::IfBranchType {
+                    expected: expected.clone(),
+                    found: yields.ty.clone(),
+                    span: yields.span,
+                });
+            }
+
+            self.context.get_mut(branch).yields = Some(yields);
+        }
+
+        expected
+    }
+
+    fn lower_break(
+        &mut self,
+        label: Option<&AstName>,
+        break_value: Option<&Spanned<AstValue>>,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+        span: Span,
+    ) -> HirValue {
+        // `break name` breaks out of the loop with that label, if there
+        // is one, and is only a value if the innermost loop can take one
+        let innermost_takes_value = self.label_stack.last().is_some_and(|loop_label| loop_label.takes_value);
+        let (label, break_value) = match (label, break_value) {
+            (None, Some(Spanned { item: AstValue::Path(path), .. }))
+                if path.segments.len() == 1
+                    && (self.label_stack.find(&path.segments[0].name.item).is_some() || !innermost_takes_value) =>
+            {
+                (Some(&path.segments[0].name), None)
+            }
+            _ => (label, break_value),
+        };
+
+        let found_label = if let Some(label) = label {
+            self.label_stack.find(&label.item)
+        } else {
+            self.label_stack.last()
+        };
+        let found_label = found_label.map(|loop_label| (loop_label.code_block, loop_label.takes_value));
+
+        let mut break_value = break_value
+            .map(|break_value| self.lower_value(break_value, parent, symbol_table, Default::default()));
+
+        let Some((code_block, takes_value)) = found_label else {
+            if let Some(label) = label {
+                self.emit(ValueError::UndefinedBreakLabel(label.clone()));
+            } else {
+                self.emit(ValueError::BreakOutsideLoop(span));
+            }
+
+            return HirValue::error(span);
+        };
+
+        if !takes_value {
+            if let Some(break_value) = break_value.take() {
+                self.emit(ValueError::BreakWithValue(break_value.span));
+            }
+        } else {
+            self.unify_break_value(code_block, break_value.as_mut(), span);
+        }
+
+        HirValue::new(
+            HirValueKind::Break(code_block, break_value.map(Box::new)),
+            Ty::new(TyKind::Never, span),
+            span,
+        )
+    }
+
+    /// Checks that a value a loop is broken out with has
+    /// the same type as the other values it is broken out with
+    fn unify_break_value(&mut self, code_block: Id<CodeBlock>, break_value: Option<&mut HirValue>, span: Span) {
+        let mut unit = HirValue::new(HirValueKind::Unit, Ty::new(TyKind::Unit, span), span);
+        let break_value = break_value.unwrap_or(&mut unit);
+
+        let loop_label = self
+            .label_stack
+            .get_mut(code_block)
+            .expect("internal compiler error: loop isn't on the label stack");
+        loop_label.has_break = true;
+
+        if let TyKind::Never = break_value.ty.kind {
+            return;
+        }
+
+        let Some(expected) = loop_label.break_ty.clone() else {
+            loop_label.break_ty = Some(Ty::new(break_value.ty.kind.clone(), break_value.span));
+            return;
+        };
+
+        self.coerce(break_value, &expected);
+
+        if !self.is_assignable(&break_value.ty, &expected) {
+            self.emit(TypeError::BreakType {
+                expected,
+                found: break_value.ty.clone(),
+                span: break_value.span,
+            });
+        }
+    }
+
+    /// Lowers a loop over a range, like `for i in 0..<n { }`,
+    /// or over the values of an iterator, like `for x in xs { }`
+    ///
+    /// Returns `None` if the loop is over anything else
+    fn lower_for_statement(
+        &mut self,
+        for_stmt: &ForStatement,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+    ) -> Option<HirValueKind> {
+        let label = for_stmt.label.as_ref().map(|label| self.lower_name(label));
+        let binding = self.lower_name(&for_stmt.binding);
+
+        let range = match &for_stmt.sequence.item {
+            AstValue::Infix(lhs, op, rhs) => match self.reorganize(lhs, op, rhs, false) {
+                (lhs, InfixOperator::HalfOpenRange, rhs) => Some((lhs, false, rhs)),
+                (lhs, InfixOperator::ClosedRange, rhs) => Some((lhs, true, rhs)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let Some((start, inclusive, end)) = range else {
+            return self.lower_for_each(for_stmt, label, &binding, parent, symbol_table);
+        };
+
+        let mut start = self.lower_value(&start, parent, symbol_table, Default::default());
+        let mut end = self.lower_value(&end, parent, symbol_table, Default::default());
+
+        // An integer literal takes on the type of the other bound
+        self.coerce(&mut start, &end.ty.clone());
+        self.coerce(&mut end, &start.ty.clone());
+
+        let bounds_are_integers = match (&start.ty.kind, &end.ty.kind) {
+            (TyKind::Integer(start_kind), TyKind::Integer(end_kind)) => start_kind == end_kind,
+            (TyKind::Never, _) | (_, TyKind::Never) => true,
+            _ => false,
+        };
+
+        if !bounds_are_integers {
+            self.emit(TypeError::RangeBounds {
+                start: start.ty.clone(),
+                end: end.ty.clone(),
+                span: for_stmt.sequence.span,
+            });
+        }
+
+        let binding_ty = Ty::new(start.ty.kind.clone(), for_stmt.binding.span);
+        let (binding, body) =
+            self.lower_for_body(for_stmt, label.clone(), &binding, &binding_ty, parent, symbol_table);
+
+        Some(HirValueKind::For(Box::new(ForValue {
+            label,
+            binding,
+            start,
+            end,
+            inclusive,
+            body,
+        })))
+    }
+
+    /// Lowers a loop over an iterator, which is any value with a
+    /// `next` method that takes no arguments and returns an optional
+    fn lower_for_each(
+        &mut self,
+        for_stmt: &ForStatement,
+        label: Option<Name>,
+        binding: &Name,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+    ) -> Option<HirValueKind> {
+        let sequence = self.lower_value(&for_stmt.sequence, parent, symbol_table, Default::default());
+
+        let has_next = self.has_next_method(&sequence.ty);
+        let next = match has_next {
+            true => self.lower_next_call(&sequence, parent),
+            false => None,
+        };
+
+        let next_ty = next.as_ref().map(|(_, next)| next.ty.kind.clone());
+
+        let (Some((iterator, next)), Some(TyKind::Optional(item_ty))) = (next, next_ty.clone()) else {
+            // Errors were already reported if the sequence or
+            // `next` are errors, or if `next` couldn't be called
+            let already_reported = matches!(sequence.ty.kind, TyKind::Never)
+                || matches!(next_ty, Some(TyKind::Never))
+                || (has_next && next_ty.is_none());
+
+            if !already_reported {
+                self.emit(TypeError::NotIterable { ty: sequence.ty.clone(), span: sequence.span });
+            }
+
+            // Still check the body, with a binding of any type
+            let binding_ty = Ty::new(TyKind::Never, for_stmt.binding.span);
+            self.lower_for_body(for_stmt, label, binding, &binding_ty, parent, symbol_table);
+
+            return None;
+        };
+
+        let binding_ty = Ty::new(item_ty.kind, for_stmt.binding.span);
+        let (binding, body) =
+            self.lower_for_body(for_stmt, label.clone(), binding, &binding_ty, parent, symbol_table);
+
+        Some(HirValueKind::ForEach(Box::new(ForEachValue {
+            label,
+            binding,
+            iterator,
+            sequence,
+            next,
+            body,
+        })))
+    }
+
+    /// Whether values of a type have a `next` method, without
+    /// reporting an error if they don't
+    fn has_next_method(&mut self, ty: &Ty) -> bool {
+        let Some(owner) = self.member_owner(ty, "next") else {
+            return false;
+        };
+
+        self.context
+            .try_get_computed::<InstanceMemberTable>(owner)
+            .is_some_and(|members| members.lookup("next").is_some())
+    }
+
+    /// Calls `next` on a local holding the sequence,
+    /// returning the local and the call
+    fn lower_next_call(&mut self, sequence: &HirValue, parent: Id<Entity>) -> Option<(Id<Local>, HirValue)> {
+        // The sequence is stored in a local that can't be named
+        let name = Name { name: String::new(), span: sequence.span };
+        let iterator = self.create_local(parent, &name, &sequence.ty, BindingKind::Var);
+        let iterator_value = HirValue::new(HirValueKind::Local(iterator), sequence.ty.clone(), sequence.span);
+
+        let segment = PathSegment::new(Spanned::new("next".to_string(), sequence.span));
+        let condition = CallableResolveCondition { labels: vec![], args: vec![] };
+        let next = self.resolve_instance_member_with(iterator_value, segment, parent, condition)?;
+
+        Some((iterator, self.lower_call(next, vec![], sequence.span)))
+    }
+
+    /// Lowers the body of a for loop, where
+    /// the binding and the loop's label are visible
+    fn lower_for_body(
+        &mut self,
+        for_stmt: &ForStatement,
+        label: Option<Name>,
+        binding: &Name,
+        binding_ty: &Ty,
+        parent: Id<Entity>,
+        symbol_table: &mut SymbolTable,
+    ) -> (Id<Local>, Id<CodeBlock>) {
+        // Changing the binding doesn't change the loop
+        let local = self.create_local(parent, binding, binding_ty, BindingKind::Var);
+        let local_symbol = self
+            .context()
+            .cast_id::<Symbol>(local)
+            .expect("internal compiler error: local doesn't have a symbol");
+
+        symbol_table.push_scope();
+        symbol_table.insert(binding.name.clone(), local_symbol);
+
+        self.label_stack.push(label, for_stmt.body.id, false);
+        let body = self.lower_code_block(&for_stmt.body, parent, symbol_table);
+        self.label_stack.pop();
+
+        symbol_table.pop_scope();
+
+        (local, body)
+    }
+
+    /// Integer literals are an `int`, unless they
+    /// only fit in a `u64`
+    fn lower_integer_literal(&mut self, num: String, span: Span) -> HirValue {
+        let literal = LiteralValue::Integer(num);
+
+        let kind = match literal.integer_value() {
+            Some(n) if IntegerKind::INT.contains(n) => IntegerKind::INT,
+            Some(n) if IntegerKind::U64.contains(n) => IntegerKind::U64,
+            _ => {
+                self.emit(TypeError::LiteralOutOfRange {
+                    ty: Ty::new_unspanned(TyKind::Integer(IntegerKind::U64)),
+                    span,
+                });
+
+                IntegerKind::INT
+            }
+        };
+
+        HirValue::new(
+            HirValueKind::Literal(literal),
+            Ty::new(TyKind::Integer(kind), span),
+            span,
+        )
+    }
+
     fn sanitize_string(&self, s: &str, span: Span) -> String {
         let is_raw = s.starts_with("raw");
         let s = if is_raw { &s[3..] } else { s };
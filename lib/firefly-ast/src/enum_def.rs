@@ -0,0 +1,75 @@
+use firefly_span::Spanned;
+
+use crate::{item::Item, ty::Ty, Name, Visibility};
+
+#[derive(Debug)]
+pub struct EnumDef {
+    pub visibility: Option<Spanned<Visibility>>,
+    pub name: Name,
+    pub id: firefly_hir::Id<firefly_hir::items::EnumDef>,
+    pub cases: Vec<Spanned<EnumCase>>,
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug)]
+pub struct EnumCase {
+    pub name: Name,
+    pub params: Vec<Spanned<EnumCaseParam>>,
+    pub index: usize,
+    pub id: firefly_hir::Id<firefly_hir::items::EnumCase>,
+}
+
+#[derive(Debug)]
+pub struct EnumCaseParam {
+    pub name: Name,
+    pub ty: Spanned<Ty>,
+}
+
+/// Either a case or an item declared in the body of an enum
+#[derive(Debug)]
+pub enum EnumMember {
+    Case(Spanned<EnumCase>),
+    Item(Box<Item>),
+}
+
+impl EnumDef {
+    pub fn new(visibility: Option<Spanned<Visibility>>, name: Name, members: Vec<EnumMember>) -> Self {
+        let mut cases = Vec::new();
+        let mut items = Vec::new();
+
+        for member in members {
+            match member {
+                EnumMember::Case(mut case) => {
+                    case.item.index = cases.len();
+                    cases.push(case);
+                }
+                EnumMember::Item(item) => items.push(*item),
+            }
+        }
+
+        Self {
+            visibility,
+            name,
+            id: firefly_hir::Id::default(),
+            cases,
+            items,
+        }
+    }
+}
+
+impl EnumCase {
+    pub fn new(name: Name, params: Vec<Spanned<EnumCaseParam>>) -> Self {
+        Self {
+            name,
+            params,
+            index: 0,
+            id: firefly_hir::Id::default(),
+        }
+    }
+}
+
+impl EnumCaseParam {
+    pub fn new(name: Name, ty: Spanned<Ty>) -> Self {
+        Self { name, ty }
+    }
+}
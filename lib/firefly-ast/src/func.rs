@@ -1,26 +1,31 @@
 use firefly_hir::Id;
 use firefly_span::{Span, Spanned};
 
-use crate::{stmt::CodeBlock, ty::Ty, Name, Visibility};
+use crate::{generics::GenericParam, stmt::CodeBlock, ty::Ty, Name, Visibility};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FuncParam {
     pub label: Option<Name>,
     pub name: Name,
     pub ty: Spanned<Ty>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct FuncSignature {
     pub params: Vec<Spanned<FuncParam>>,
     pub return_ty: Option<Spanned<Ty>>,
+    /// The type of error the function can throw
+    pub throws: Option<Spanned<Ty>>,
 }
 
 #[derive(Debug)]
 pub struct Func {
     pub visibility: Option<Spanned<Visibility>>,
     pub static_kw: Option<Span>,
+    /// Marks a method that can change `self`
+    pub mutating_kw: Option<Span>,
     pub name: Name,
+    pub generics: Vec<GenericParam>,
     pub signature: FuncSignature,
     pub body: CodeBlock,
     pub id: Id<firefly_hir::func::Func>,
@@ -30,16 +35,19 @@ impl Func {
     pub fn new(
         visibility: Option<Spanned<Visibility>>,
         static_kw: Option<Span>,
+        mutating_kw: Option<Span>,
         name: Name,
-        params: Vec<Spanned<FuncParam>>,
-        return_ty: Option<Spanned<Ty>>,
+        generics: Vec<GenericParam>,
+        signature: FuncSignature,
         body: CodeBlock,
     ) -> Self {
         Self {
             visibility,
             static_kw,
+            mutating_kw,
             name,
-            signature: FuncSignature { params, return_ty },
+            generics,
+            signature,
             body,
             id: Id::default(),
         }
@@ -0,0 +1,21 @@
+use firefly_hir::{generics::GenericParameter, Id};
+
+use crate::{Name, Path};
+
+/// A generic parameter declared by a function or struct
+#[derive(Debug, Clone)]
+pub struct GenericParam {
+    pub name: Name,
+    pub bounds: Vec<Path>,
+    pub id: Id<GenericParameter>,
+}
+
+impl GenericParam {
+    pub fn new(name: Name, bounds: Vec<Path>) -> Self {
+        Self {
+            name,
+            bounds,
+            id: Id::default(),
+        }
+    }
+}
@@ -1,12 +1,16 @@
 use firefly_span::Spanned;
 
-use crate::{func::Func, import::Import, module::Module, struct_def::{Field, StructDef}};
+use crate::{enum_def::EnumDef, func::Func, import::Import, module::Module, protocol_def::ProtocolDef, struct_def::{Field, Init, StructDef}, type_alias::TypeAlias};
 
 #[derive(Debug)]
 pub enum Item {
     Func(Spanned<Func>),
     Field(Spanned<Field>),
+    Init(Spanned<Init>),
     StructDef(Spanned<StructDef>),
+    EnumDef(Spanned<EnumDef>),
+    ProtocolDef(Spanned<ProtocolDef>),
+    TypeAlias(Spanned<TypeAlias>),
     Module(Spanned<Module>),
     Import(Spanned<Import>),
 
@@ -1,13 +1,18 @@
 use firefly_span::{Span, Spanned};
 
+pub mod enum_def;
 pub mod func;
+pub mod generics;
 pub mod import;
 pub mod item;
 pub mod module;
 pub mod operator;
+pub mod pattern;
+pub mod protocol_def;
 pub mod stmt;
 pub mod struct_def;
 pub mod ty;
+pub mod type_alias;
 pub mod value;
 
 pub type Name = Spanned<String>;
@@ -20,14 +25,27 @@ pub enum Visibility {
     Private,
 }
 
+/// Whether a variable is declared with `var`,
+/// and can be assigned to, or with `let`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    Var,
+    Let,
+}
+
 #[derive(Debug, Clone)]
 pub struct PathSegment {
     pub name: Name,
+    pub generics: Vec<Spanned<ty::Ty>>,
 }
 
 impl PathSegment {
     pub fn new(name: Name) -> Self {
-        Self { name }
+        Self { name, generics: Vec::new() }
+    }
+
+    pub fn with_generics(name: Name, generics: Vec<Spanned<ty::Ty>>) -> Self {
+        Self { name, generics }
     }
 }
 
@@ -23,9 +23,16 @@ pub enum InfixOperator {
   CompareGreaterThanOrEqual,
   CompareEqual,
   CompareNotEqual,
+  Identical,
+  NotIdentical,
   LogicalAnd,
   LogicalOr,
 
+  Coalesce,
+
+  HalfOpenRange,
+  ClosedRange,
+
   AddAssign,
   SubtractAssign,
   MultiplyAssign,
@@ -41,6 +48,14 @@ pub enum InfixOperator {
 }
 
 impl PrefixOperator {
+  pub fn symbol(&self) -> &'static str {
+    match self {
+        PrefixOperator::Identity => "+",
+        PrefixOperator::Invert => "!",
+        PrefixOperator::Negate => "-",
+    }
+  }
+
   pub fn get_verb(&self) -> &'static str {
     match self {
         PrefixOperator::Identity => "identity",
@@ -51,6 +66,25 @@ impl PrefixOperator {
 }
 
 impl InfixOperator {
+  /// Returns the operator applied by a compound
+  /// assignment, such as `+` for `+=`
+  pub fn assign_operator(&self) -> Option<InfixOperator> {
+    match self {
+        InfixOperator::AddAssign => Some(InfixOperator::Add),
+        InfixOperator::SubtractAssign => Some(InfixOperator::Subtract),
+        InfixOperator::MultiplyAssign => Some(InfixOperator::Multiply),
+        InfixOperator::DivideAssign => Some(InfixOperator::Divide),
+        InfixOperator::ModuloAssign => Some(InfixOperator::Modulo),
+        InfixOperator::ShiftLeftAssign => Some(InfixOperator::ShiftLeft),
+        InfixOperator::ShiftRightAssign => Some(InfixOperator::ShiftRight),
+        InfixOperator::BitAndAssign => Some(InfixOperator::BitAnd),
+        InfixOperator::BitOrAssign => Some(InfixOperator::BitOr),
+        InfixOperator::BitXorAssign => Some(InfixOperator::BitXor),
+
+        _ => None,
+    }
+  }
+
   pub fn precedence(&self) -> u32 {
     match self {
         // Additive (500)
@@ -76,7 +110,16 @@ impl InfixOperator {
         InfixOperator::CompareGreaterThanOrEqual => 400,
         InfixOperator::CompareEqual => 400,
         InfixOperator::CompareNotEqual => 400,
+        InfixOperator::Identical => 400,
+        InfixOperator::NotIdentical => 400,
         
+        // Coalescing (420)
+        InfixOperator::Coalesce => 420,
+
+        // Range (450)
+        InfixOperator::HalfOpenRange => 450,
+        InfixOperator::ClosedRange => 450,
+
         // Logical (300, 200)
         InfixOperator::LogicalAnd => 300,
         InfixOperator::LogicalOr => 200,
@@ -95,6 +138,45 @@ impl InfixOperator {
         InfixOperator::BitXorAssign => 100,
     }
 }
+  pub fn symbol(&self) -> &'static str {
+    match self {
+        InfixOperator::Add => "+",
+        InfixOperator::Subtract => "-",
+        InfixOperator::Multiply => "*",
+        InfixOperator::Divide => "/",
+        InfixOperator::Modulo => "%",
+        InfixOperator::ShiftLeft => "<<",
+        InfixOperator::ShiftRight => ">>",
+        InfixOperator::BitAnd => "&",
+        InfixOperator::BitXor => "^",
+        InfixOperator::BitOr => "|",
+        InfixOperator::CompareLessThan => "<",
+        InfixOperator::CompareGreaterThan => ">",
+        InfixOperator::CompareLessThanOrEqual => "<=",
+        InfixOperator::CompareGreaterThanOrEqual => ">=",
+        InfixOperator::CompareEqual => "==",
+        InfixOperator::CompareNotEqual => "!=",
+        InfixOperator::Identical => "===",
+        InfixOperator::NotIdentical => "!==",
+        InfixOperator::LogicalAnd => "&&",
+        InfixOperator::LogicalOr => "||",
+        InfixOperator::Coalesce => "??",
+        InfixOperator::HalfOpenRange => "..<",
+        InfixOperator::ClosedRange => "...",
+        InfixOperator::AddAssign => "+=",
+        InfixOperator::SubtractAssign => "-=",
+        InfixOperator::MultiplyAssign => "*=",
+        InfixOperator::DivideAssign => "/=",
+        InfixOperator::ModuloAssign => "%=",
+        InfixOperator::ShiftLeftAssign => "<<=",
+        InfixOperator::ShiftRightAssign => ">>=",
+        InfixOperator::BitAndAssign => "&=",
+        InfixOperator::BitOrAssign => "|=",
+        InfixOperator::BitXorAssign => "^=",
+        InfixOperator::Assign => "=",
+    }
+  }
+
   pub fn get_verb(&self) -> &'static str {
     match self {
         InfixOperator::Add => "add",
@@ -113,8 +195,13 @@ impl InfixOperator {
         InfixOperator::CompareGreaterThanOrEqual => "greaterThanEq",
         InfixOperator::CompareEqual => "equals",
         InfixOperator::CompareNotEqual => "notEquals",
+        InfixOperator::Identical => "identical",
+        InfixOperator::NotIdentical => "notIdentical",
         InfixOperator::LogicalAnd => "logicalAnd",
         InfixOperator::LogicalOr => "logicalOr",
+        InfixOperator::Coalesce => "coalesce",
+        InfixOperator::HalfOpenRange => "halfOpenRange",
+        InfixOperator::ClosedRange => "closedRange",
         InfixOperator::AddAssign => "addAssign",
         InfixOperator::SubtractAssign => "subAssign",
         InfixOperator::MultiplyAssign => "multiplyAssign",
@@ -0,0 +1,70 @@
+use firefly_span::Spanned;
+
+use crate::{stmt::CodeBlock, value::Value, Name, Path};
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// Matches anything, written `_`
+    Wildcard,
+
+    /// Matches anything, and binds it to a name
+    Binding(Name),
+
+    /// Matches a literal value
+    Literal(Spanned<Value>),
+
+    Tuple(Vec<Spanned<Pattern>>),
+
+    /// Matches a case of an enum, and optionally
+    /// the values it carries
+    Case(CasePath, Option<Vec<Spanned<Pattern>>>),
+
+    /// Matches a named value, like an
+    /// enum case or `true`
+    Path(Path),
+}
+
+/// How the case of an enum pattern is named
+#[derive(Debug, Clone)]
+pub enum CasePath {
+    /// `.circle`, a case of the enum being matched
+    Implicit(Name),
+
+    /// `Shape.circle`
+    Path(Path),
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchStatement {
+    pub scrutinee: Spanned<Value>,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Spanned<Pattern>,
+    pub body: CodeBlock,
+}
+
+impl Pattern {
+    pub fn path(path: Path) -> Pattern {
+        match &path.segments[..] {
+            [segment] if segment.name.item == "_" => Pattern::Wildcard,
+            _ => Pattern::Path(path),
+        }
+    }
+}
+
+impl MatchArm {
+    /// Creates an arm whose body is a single value
+    pub fn with_value(pattern: Spanned<Pattern>, value: Spanned<Value>) -> Self {
+        let span = value.span;
+
+        let body = CodeBlock {
+            span,
+            ..CodeBlock::new(vec![], Some(value))
+        };
+
+        Self { pattern, body }
+    }
+}
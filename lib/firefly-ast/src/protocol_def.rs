@@ -0,0 +1,83 @@
+use firefly_hir::Id;
+use firefly_span::{Span, Spanned};
+
+use crate::{func::FuncSignature, ty::Ty, Name, Visibility};
+
+#[derive(Debug)]
+pub struct ProtocolDef {
+    pub visibility: Option<Spanned<Visibility>>,
+    pub name: Name,
+    pub id: Id<firefly_hir::items::ProtocolDef>,
+    pub associated_types: Vec<Spanned<AssociatedType>>,
+    pub requirements: Vec<Spanned<Requirement>>,
+}
+
+/// A type that conforming types choose, which
+/// the requirements of the protocol can use
+#[derive(Debug)]
+pub struct AssociatedType {
+    pub name: Name,
+    pub default: Option<Spanned<Ty>>,
+    pub id: Id<firefly_hir::generics::GenericParameter>,
+}
+
+/// A method that conforming types have to implement
+#[derive(Debug)]
+pub struct Requirement {
+    /// Marks a requirement that can change `self`
+    pub mutating_kw: Option<Span>,
+    pub name: Name,
+    pub signature: FuncSignature,
+    pub id: Id<firefly_hir::func::Func>,
+}
+
+/// Either an associated type or a requirement
+/// declared in the body of a protocol
+#[derive(Debug)]
+pub enum ProtocolMember {
+    AssociatedType(Spanned<AssociatedType>),
+    Requirement(Spanned<Requirement>),
+}
+
+impl ProtocolDef {
+    pub fn new(visibility: Option<Spanned<Visibility>>, name: Name, members: Vec<ProtocolMember>) -> Self {
+        let mut associated_types = Vec::new();
+        let mut requirements = Vec::new();
+
+        for member in members {
+            match member {
+                ProtocolMember::AssociatedType(associated_type) => associated_types.push(associated_type),
+                ProtocolMember::Requirement(requirement) => requirements.push(requirement),
+            }
+        }
+
+        Self {
+            visibility,
+            name,
+            id: Id::default(),
+            associated_types,
+            requirements,
+        }
+    }
+}
+
+impl AssociatedType {
+    pub fn new(name: Name, default: Option<Spanned<Ty>>) -> Self {
+        Self {
+            name,
+            default,
+            id: Id::default(),
+        }
+    }
+}
+
+impl Requirement {
+    pub fn new(mutating_kw: Option<Span>, name: Name, signature: FuncSignature) -> Self {
+        Self {
+            mutating_kw,
+            name,
+            signature,
+            id: Id::default(),
+        }
+    }
+}
@@ -1,12 +1,12 @@
 use firefly_hir::{stmt::CodeBlock as HirCodeBlock, Id};
-use firefly_span::Spanned;
+use firefly_span::{Span, Spanned};
 
-use crate::{ty::Ty, value::Value, Name};
+use crate::{ty::Ty, value::Value, BindingKind, Name};
 
 #[derive(Debug, Clone)]
 pub enum Stmt {
     Value(Spanned<Value>, bool),
-    Bind(Name, Option<Spanned<Ty>>, Spanned<Value>),
+    Bind(BindingKind, Name, Option<Spanned<Ty>>, Spanned<Value>),
     Semicolon,
     Error,
 }
@@ -16,6 +16,7 @@ pub struct CodeBlock {
     pub id: Id<HirCodeBlock>,
     pub stmts: Vec<Spanned<Stmt>>,
     pub yields: Option<Spanned<Value>>,
+    pub span: Span,
 }
 
 impl CodeBlock {
@@ -24,6 +25,7 @@ impl CodeBlock {
             id: Default::default(),
             stmts,
             yields,
+            span: Default::default(),
         }
     }
 }
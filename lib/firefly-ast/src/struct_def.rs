@@ -1,12 +1,23 @@
 use firefly_hir::{Entity, Id};
 use firefly_span::{Span, Spanned};
 
-use crate::{item::Item, value::Value, Name, Visibility};
+use crate::{func::FuncSignature, generics::GenericParam, item::Item, stmt::CodeBlock, value::Value, BindingKind, Name, Path, Visibility};
+
+/// Whether a type is declared with `struct`, and is copied
+/// when it's assigned, or with `class`, and is shared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructKind {
+    Struct,
+    Class,
+}
 
 #[derive(Debug)]
 pub struct StructDef {
     pub visibility: Option<Spanned<Visibility>>,
+    pub kind: StructKind,
     pub name: Name,
+    pub generics: Vec<GenericParam>,
+    pub protocols: Vec<Path>,
     pub id: Id<firefly_hir::items::StructDef>,
     pub items: Vec<Item>,
 }
@@ -15,17 +26,32 @@ pub struct StructDef {
 pub struct Field {
     pub visibility: Option<Spanned<Visibility>>,
     pub static_kw: Option<Span>,
+    pub kind: BindingKind,
     pub name: Name,
     pub ty: Spanned<crate::ty::Ty>,
     pub default: Option<Spanned<Value>>,
     pub id: Id<Entity>,
 }
 
+/// An `init` declared in a struct, which is called
+/// with its parameters to create an instance
+#[derive(Debug)]
+pub struct Init {
+    pub visibility: Option<Spanned<Visibility>>,
+    pub init_kw: Span,
+    pub signature: FuncSignature,
+    pub body: CodeBlock,
+    pub id: Id<firefly_hir::func::Func>,
+}
+
 impl StructDef {
-    pub fn new(visibility: Option<Spanned<Visibility>>, name: Name, items: Vec<Item>) -> Self {
+    pub fn new(visibility: Option<Spanned<Visibility>>, kind: StructKind, name: Name, generics: Vec<GenericParam>, protocols: Vec<Path>, items: Vec<Item>) -> Self {
         Self {
             visibility,
+            kind,
             name,
+            generics,
+            protocols,
             id: Id::default(),
             items,
         }
@@ -33,14 +59,27 @@ impl StructDef {
 }
 
 impl Field {
-    pub fn new(visibility: Option<Spanned<Visibility>>, static_kw: Option<Span>, name: Name, ty: Spanned<crate::ty::Ty>, default: Option<Spanned<Value>>) -> Self {
+    pub fn new(visibility: Option<Spanned<Visibility>>, static_kw: Option<Span>, kind: BindingKind, name: Name, ty: Spanned<crate::ty::Ty>, default: Option<Spanned<Value>>) -> Self {
         Self {
             visibility,
             static_kw,
+            kind,
             name,
             ty,
             default,
             id: Id::default(),
         }
     }
+}
+
+impl Init {
+    pub fn new(visibility: Option<Spanned<Visibility>>, init_kw: Span, signature: FuncSignature, body: CodeBlock) -> Self {
+        Self {
+            visibility,
+            init_kw,
+            signature,
+            body,
+            id: Id::default(),
+        }
+    }
 }
\ No newline at end of file
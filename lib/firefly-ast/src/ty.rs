@@ -6,5 +6,7 @@ use crate::Path;
 pub enum Ty {
     Tuple(Vec<Spanned<Ty>>),
     Path(Path),
+    Func(Vec<Spanned<Ty>>, Box<Spanned<Ty>>),
+    Optional(Box<Spanned<Ty>>),
     Error,
 }
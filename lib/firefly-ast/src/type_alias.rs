@@ -0,0 +1,23 @@
+use firefly_hir::Id;
+use firefly_span::Spanned;
+
+use crate::{ty::Ty, Name, Visibility};
+
+#[derive(Debug)]
+pub struct TypeAlias {
+    pub visibility: Option<Spanned<Visibility>>,
+    pub name: Name,
+    pub ty: Spanned<Ty>,
+    pub id: Id<firefly_hir::items::TypeAlias>,
+}
+
+impl TypeAlias {
+    pub fn new(visibility: Option<Spanned<Visibility>>, name: Name, ty: Spanned<Ty>) -> Self {
+        Self {
+            visibility,
+            name,
+            ty,
+            id: Id::default(),
+        }
+    }
+}
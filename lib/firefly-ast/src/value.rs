@@ -1,7 +1,9 @@
 use firefly_span::Spanned;
 
 use crate::{
+    func::FuncSignature,
     operator::{InfixOperator, PrefixOperator},
+    pattern::MatchStatement,
     stmt::CodeBlock,
     Name, Path, PathSegment,
 };
@@ -12,16 +14,39 @@ pub enum Value {
     IntegerLiteral(Name),
     FloatLiteral(Name),
     StringLiteral(Name),
+    Nil,
     Path(Path),
     Call(Box<Spanned<Value>>, Vec<CallArg>),
     Return(Option<Box<Spanned<Value>>>),
     If(Box<IfStatement>),
     While(Box<WhileStatement>),
-    Break(Option<Name>),
+    For(Box<ForStatement>),
+    Loop(Box<LoopStatement>),
+    Repeat(Box<RepeatStatement>),
+    Match(Box<MatchStatement>),
+    Do(Box<DoStatement>),
+    Closure(Box<Closure>),
+    /// Breaks out of a loop, with an optional label and value
+    ///
+    /// `break name` is parsed as a value, and is
+    /// treated as a label if a loop has that label
+    Break(Option<Name>, Option<Box<Spanned<Value>>>),
     Continue(Option<Name>),
+    /// Throws an error, like `throw ParseError.empty`
+    Throw(Box<Spanned<Value>>),
+    /// Calls functions that can throw, passing
+    /// their errors on, like `try parse(text)`
+    Try(Box<Spanned<Value>>),
+    /// Calls functions that can throw, stopping the
+    /// program if they do, like `try! parse(text)`
+    ForceTry(Box<Spanned<Value>>),
     Assign(Box<Spanned<Value>>, Box<Spanned<Value>>),
     Member(Box<Spanned<Value>>, PathSegment),
     TupleMember(Box<Spanned<Value>>, Name),
+    /// A member of the value inside an optional, like `person?.name`
+    OptionalMember(Box<Spanned<Value>>, PathSegment),
+    /// Unwraps an optional, like `person!`
+    Unwrap(Box<Spanned<Value>>),
     Prefix(PrefixOperator, Box<Spanned<Value>>),
     Infix(Box<Spanned<Value>>, InfixOperator, Box<Spanned<Value>>),
     Error,
@@ -33,8 +58,10 @@ pub struct CallArg {
     pub value: Spanned<Value>,
 }
 
+/// An if statement, or an `if let` if it has a binding
 #[derive(Debug, Clone)]
 pub struct IfStatement {
+    pub binding: Option<Name>,
     pub condition: Spanned<Value>,
     pub positive: CodeBlock,
     pub negative: Option<ElseStatement>,
@@ -53,6 +80,47 @@ pub struct WhileStatement {
     pub body: CodeBlock,
 }
 
+/// An anonymous function, which can use the locals around it
+#[derive(Debug, Clone)]
+pub struct Closure {
+    pub signature: FuncSignature,
+    pub body: CodeBlock,
+}
+
+/// A loop that runs until it is broken out of
+#[derive(Debug, Clone)]
+pub struct LoopStatement {
+    pub label: Option<Name>,
+    pub body: CodeBlock,
+}
+
+/// A loop that checks its condition after each run
+#[derive(Debug, Clone)]
+pub struct RepeatStatement {
+    pub label: Option<Name>,
+    pub body: CodeBlock,
+    pub condition: Spanned<Value>,
+}
+
+/// A loop over each value in a sequence,
+/// such as `for i in 0..<n { }`
+#[derive(Debug, Clone)]
+pub struct ForStatement {
+    pub label: Option<Name>,
+    pub binding: Name,
+    pub sequence: Spanned<Value>,
+    pub body: CodeBlock,
+}
+
+/// A block whose errors are handled by a `catch` block,
+/// which can bind the error to a name
+#[derive(Debug, Clone)]
+pub struct DoStatement {
+    pub body: CodeBlock,
+    pub binding: Option<Name>,
+    pub catch: CodeBlock,
+}
+
 impl Value {
     pub fn member(parent: Box<Spanned<Value>>, member: PathSegment) -> Value {
         match &parent.item {
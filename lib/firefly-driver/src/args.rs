@@ -10,5 +10,9 @@ pub struct Args {
     pub print_hir: bool,
 
     #[arg(long)]
-    pub print_mir: bool
+    pub print_mir: bool,
+
+    /// Arguments to pass to `main`, after `--`
+    #[arg(last = true)]
+    pub program_args: Vec<String>,
 }
\ No newline at end of file
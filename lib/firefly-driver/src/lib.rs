@@ -3,8 +3,12 @@ use std::{path::Path, sync::Arc};
 use clap::Parser;
 use context::Context;
 use firefly_ast_lower::AstLowerer;
-use firefly_errors::emitter::{Destination, Emitter};
-use firefly_interpret::ExecutionEngine;
+use firefly_error_messages::DiagnosticMessage;
+use firefly_errors::{
+    diagnostic::{Diagnostic, DiagnosticId, Level},
+    emitter::{Destination, Emitter},
+};
+use firefly_interpret::{error::RuntimeError, value::InnerValue, ExecutionEngine};
 use firefly_mir::MirContext;
 use firefly_span::{SourceFile, SourceMap};
 use pipeline::Pipeline;
@@ -14,6 +18,15 @@ mod pipeline;
 mod context;
 mod args;
 
+/// The exit code of a program stopped by a runtime error
+const RUNTIME_ERROR_EXIT_CODE: i32 = 101;
+
+/// The exit code when the program can't be started with its arguments
+const USAGE_EXIT_CODE: i32 = 2;
+
+/// The exit code of a program that failed to compile
+const COMPILE_ERROR_EXIT_CODE: i32 = 1;
+
 pub struct Driver {
     source_map: Arc<SourceMap>,
     emitter: Arc<Emitter>,
@@ -22,6 +35,9 @@ pub struct Driver {
 
     print_hir: bool,
     print_mir: bool,
+
+    /// The arguments passed to `main`
+    program_args: Vec<String>,
 }
 
 impl Driver {
@@ -31,7 +47,7 @@ impl Driver {
         let ast_lowerer = AstLowerer::new(emitter.clone());
         let mir_context = MirContext::new();
 
-        Driver { source_map, emitter, ast_lowerer, mir_context, print_hir: false, print_mir: false }
+        Driver { source_map, emitter, ast_lowerer, mir_context, print_hir: false, print_mir: false, program_args: Vec::new() }
     }
 
     pub fn parse_args(&mut self) {
@@ -43,6 +59,7 @@ impl Driver {
 
         self.print_hir = args.print_hir;
         self.print_mir = args.print_mir;
+        self.program_args = args.program_args;
     }
 
     pub fn load_file(&self, path: &str) {
@@ -62,7 +79,8 @@ impl Driver {
         pipeline.run(self.source_map.files(), &mut context);
     }
 
-    pub fn output(&self) {
+    /// Runs the program, returning the code to exit with
+    pub fn output(&self) -> i32 {
         if self.print_hir {
             println!("{}", self.ast_lowerer.context().display())
         }
@@ -71,8 +89,58 @@ impl Driver {
             println!("{}", self.mir_context)
         }
 
+        // A program with errors is never run
+        if self.emitter.has_triggered() {
+            return COMPILE_ERROR_EXIT_CODE;
+        }
+
+        if let Some(entry_point) = self.mir_context.entry_point() {
+            let expected = self.mir_context.get_function(entry_point).signature().parameters().len();
+
+            if expected != self.program_args.len() {
+                self.report_argument_count(expected);
+
+                return USAGE_EXIT_CODE;
+            }
+        }
+
         let mut execution_engine = ExecutionEngine::new(&self.mir_context);
 
-        execution_engine.execute();
+        match execution_engine.execute(self.program_args.clone()) {
+            Ok(value) => match *value {
+                InnerValue::Int(code) => code as i32,
+                InnerValue::UInt(code) => code as i32,
+                _ => 0,
+            },
+            Err(error) => {
+                self.report_runtime_error(error);
+
+                RUNTIME_ERROR_EXIT_CODE
+            }
+        }
+    }
+
+    fn report_argument_count(&self, expected: usize) {
+        let diagnostic = Diagnostic::new(
+            Level::Error,
+            DiagnosticMessage::Str(format!(
+                "`main` expects {expected} arguments, but {} were given",
+                self.program_args.len()
+            )),
+        )
+        .with_error_code(DiagnosticId::new("E0903"));
+
+        let _ = self.emitter.emit(diagnostic);
+    }
+
+    fn report_runtime_error(&self, error: RuntimeError) {
+        let diagnostic = Diagnostic::new(
+            Level::Error,
+            DiagnosticMessage::Str(format!("runtime error: {}", error.kind)),
+        )
+        .with_error_code(DiagnosticId::new(error.code()))
+        .with_source(error.span);
+
+        let _ = self.emitter.emit(diagnostic);
     }
 }
\ No newline at end of file
@@ -2,7 +2,7 @@ use firefly_ast::item::Item;
 
 use crate::context::Context;
 
-use super::ParallelPass;
+use super::{ParallelPass, Pass};
 
 pub struct LinkPass;
 
@@ -17,6 +17,19 @@ impl ParallelPass for LinkPass {
     }
 }
 
+pub struct LowerImportsPass;
+
+impl ParallelPass for LowerImportsPass {
+    type Input = Vec<Item>;
+    type Output = Vec<Item>;
+
+    fn process(&self, input: Self::Input, context: &mut Context) -> Self::Output {
+        context.ast_lowerer.lower_imports(&input);
+
+        input
+    }
+}
+
 pub struct LowerDefsPass;
 
 impl ParallelPass for LowerDefsPass {
@@ -41,4 +54,16 @@ impl ParallelPass for LowerCodePass {
 
         return ();
     }
+}
+
+pub struct CheckDeclarationsPass;
+
+impl Pass for CheckDeclarationsPass {
+    type Input = Vec<()>;
+    type Output = ();
+
+    fn process(&self, _: Self::Input, context: &mut Context) -> Self::Output {
+        context.ast_lowerer.check_global_cycles();
+        context.ast_lowerer.check_entry_point();
+    }
 }
\ No newline at end of file
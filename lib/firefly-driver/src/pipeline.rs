@@ -109,4 +109,31 @@ impl<T1, T2, T3, T4, T5, T6> Pipeline for (T1, T2, T3, T4, T5, T6)
         if context.emitter.has_triggered() { return; }
         self.5.process(output5, context);
     }
-}
\ No newline at end of file
+}
+impl<T1, T2, T3, T4, T5, T6, T7> Pipeline for (T1, T2, T3, T4, T5, T6, T7)
+    where T1: Pass,
+          T2: Pass<Input = T1::Output>,
+          T3: Pass<Input = T2::Output>,
+          T4: Pass<Input = T3::Output>,
+          T5: Pass<Input = T4::Output>,
+          T6: Pass<Input = T5::Output>,
+          T7: Pass<Input = T6::Output>
+{
+    type Input = T1::Input;
+
+    fn run(&self, input: Self::Input, context: &mut Context) {
+        let output1 = self.0.process(input, context);
+        if context.emitter.has_triggered() { return; }
+        let output2 = self.1.process(output1, context);
+        if context.emitter.has_triggered() { return; }
+        let output3 = self.2.process(output2, context);
+        if context.emitter.has_triggered() { return; }
+        let output4 = self.3.process(output3, context);
+        if context.emitter.has_triggered() { return; }
+        let output5 = self.4.process(output4, context);
+        if context.emitter.has_triggered() { return; }
+        let output6 = self.5.process(output5, context);
+        if context.emitter.has_triggered() { return; }
+        self.6.process(output6, context);
+    }
+}
@@ -1,5 +1,5 @@
 use firefly_hir::{stmt::{CodeBlock, Stmt, StmtKind}, Id};
-use firefly_mir::{code::Terminator, value::{Immediate, ImmediateKind}};
+use firefly_mir::value::{Immediate, ImmediateKind};
 
 use crate::HirLowerer;
 
@@ -11,12 +11,7 @@ impl HirLowerer<'_> {
             return;
         }
 
-        if let Some(imm) = imm {
-            self.mir.build_terminator(Terminator::returns(imm));
-        }
-        else {
-            self.mir.build_terminator(Terminator::returns_void());
-        }
+        self.build_return(imm);
     }
 
     pub fn lower_code_block(&mut self, code_block: Id<CodeBlock>) {
@@ -26,7 +21,7 @@ impl HirLowerer<'_> {
             self.mir.build_eval(imm);
         }
     }
-    fn lower_code_block_inner(&mut self, code_block: Id<CodeBlock>) -> Option<Immediate> {
+    pub(crate) fn lower_code_block_inner(&mut self, code_block: Id<CodeBlock>) -> Option<Immediate> {
         let code_block = self.hir.get(code_block).clone();
 
         for stmt in &code_block.stmts {
@@ -0,0 +1,32 @@
+use firefly_error_messages::DiagnosticMessage;
+use firefly_errors::diagnostic::{Diagnostic, DiagnosticId, Level};
+use firefly_hir::{func::Func, resolve::Symbol, HirContext, Id, IntoDiagnostic};
+
+pub enum LowerError {
+    InstantiationDepth {
+        func: Id<Func>,
+        limit: usize,
+    },
+}
+
+impl IntoDiagnostic for LowerError {
+    fn into_diagnostic(&self, context: &HirContext) -> Diagnostic {
+        match self {
+            LowerError::InstantiationDepth { func, limit } => {
+                let symbol = context.try_get::<Symbol>(*func);
+                let name = symbol.map(|symbol| symbol.name.name.clone()).unwrap_or_default();
+
+                let diag = Diagnostic::new(Level::Error,
+                    DiagnosticMessage::Str(format!(
+                        "Generic function `{name}` is instantiated more than {limit} levels deep, which happens when it calls itself with ever larger generic arguments"
+                    ))
+                ).with_error_code(DiagnosticId::new("E0524"));
+
+                match symbol {
+                    Some(symbol) => diag.with_source(symbol.name.span),
+                    None => diag,
+                }
+            }
+        }
+    }
+}
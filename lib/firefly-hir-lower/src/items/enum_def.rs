@@ -0,0 +1,33 @@
+use firefly_hir::{items::{mangle::MangledName, EnumCase}, Id};
+
+use itertools::Itertools;
+
+use crate::HirLowerer;
+use firefly_hir::items::EnumDef as HirEnumDef;
+
+impl HirLowerer<'_> {
+    pub fn create_enum(&mut self, enum_def: Id<HirEnumDef>) {
+        let MangledName { symbol } = self.hir.try_get_computed(enum_def)
+            .expect("internal compiler error: enum doesn't have a mangled name");
+
+        let mir_id = self.mir.context_mut().create_enum(symbol);
+
+        self.enum_map.insert(enum_def, mir_id);
+    }
+
+    pub fn lower_enum(&mut self, enum_def: Id<HirEnumDef>) {
+        let mir_id = *self.enum_map.get(&enum_def).unwrap();
+
+        let cases = self.hir.children(enum_def.as_base())
+            .iter()
+            .filter_map(|case| self.hir.cast_id::<EnumCase>(*case))
+            .collect_vec();
+
+        for case_id in cases {
+            let case = self.hir.get(case_id).clone();
+            let fields = case.fields.iter().map(|(_, ty)| self.lower_ty(ty)).collect();
+
+            self.mir.context_mut().create_case(mir_id, fields);
+        }
+    }
+}
@@ -1,36 +1,128 @@
-use firefly_hir::{func::Callable, items::mangle::MangledName, stmt::CodeBlock, value::HasSelf, Id};
+use firefly_hir::{func::{Callable, Initializer}, generics::{GenericParameterList, Monomorph}, items::mangle::MangledName, stmt::CodeBlock, value::HasSelf, Id};
+use firefly_mangle::SymbolName;
+use firefly_mir::{code::Function as MirFunc, Id as MirId};
 use itertools::Itertools;
 
-use crate::HirLowerer;
+use crate::{errors::LowerError, HirLowerer};
 use firefly_hir::func::Func as HirFunc;
 
+/// How deeply generic functions can create instances of each other
+const INSTANCE_DEPTH_LIMIT: usize = 16;
+
 impl HirLowerer<'_> {
     pub fn create_func(&mut self, func: Id<HirFunc>) {
+        // Generic functions are created when they're used
+        if !GenericParameterList::in_scope(func.as_base(), self.hir).is_empty() {
+            return;
+        }
+
+        // Requirements are only called through the types that implement them
+        if self.requirement_of(func).is_some() {
+            return;
+        }
+
         let MangledName { symbol } = self.hir.try_get_computed(func).cloned()
             .expect("internal compiler error: function doesn't have a mangled name");
 
-        let Callable { params, return_ty, .. } = self.hir.try_get(func)
-            .expect("internal compiler error: function doesn't have a signature");
+        let mir_id = self.create_signature(func, &symbol);
 
+        self.func_map.insert(func, mir_id);
+    }
 
+    pub fn lower_func(&mut self, func: Id<HirFunc>) {
+        let Some(mir_id) = self.func_map.get(&func).cloned() else {
+            return;
+        };
+
+        self.lower_func_body(func, mir_id);
+    }
+
+    /// Gets the function for a list of generic arguments,
+    /// creating it the first time it's used
+    pub fn func_instance(&mut self, func: &Monomorph<HirFunc>) -> MirId<MirFunc> {
+        if func.generics.is_empty() {
+            return self.func_map[&func.id];
+        }
+
+        let func = func.substitute(&self.generics).normalize(self.hir);
+        let symbol = MangledName::of_monomorph(&func, self.hir);
+
+        // Overloads share a name, so instances are told apart by function too
+        let key = (func.id, symbol.clone());
+
+        if let Some(mir_id) = self.func_instances.get(&key) {
+            return *mir_id;
+        }
+
+        let generics = func.substitution(self.hir);
+        let outer_generics = std::mem::replace(&mut self.generics, generics);
+
+        let mir_id = self.create_signature(func.id, &symbol);
+
+        self.generics = outer_generics;
+
+        self.func_instances.insert(key, mir_id);
+
+        // The instance is left without a body, since the
+        // program won't be run
+        if self.instance_depth >= INSTANCE_DEPTH_LIMIT {
+            self.hir.emit(LowerError::InstantiationDepth { func: func.id, limit: INSTANCE_DEPTH_LIMIT });
+            self.pending_funcs.clear();
+
+            return mir_id;
+        }
+
+        // The body is lowered once the current function is finished
+        self.pending_funcs.push((func, mir_id, self.instance_depth + 1));
+
+        mir_id
+    }
+
+    /// Lowers the bodies of the generic functions that were used,
+    /// which can use more generic functions in turn
+    pub fn lower_func_instances(&mut self) {
+        while let Some((func, mir_id, depth)) = self.pending_funcs.pop() {
+            self.generics = func.substitution(self.hir);
+            self.instance_depth = depth;
+
+            self.lower_func_body(func.id, mir_id);
+        }
+
+        self.generics = Default::default();
+        self.instance_depth = 0;
+    }
+
+    fn create_signature(&mut self, func: Id<HirFunc>, symbol: &SymbolName) -> MirId<MirFunc> {
+        let Callable { params, return_ty, throws, .. } = self.hir.try_get(func).cloned()
+            .expect("internal compiler error: function doesn't have a signature");
 
         // create the function
         let mut mir_params = params.iter().map(|p| self.lower_ty(&p.ty)).collect_vec();
-        let return_ty = self.lower_ty(return_ty);
+        let return_ty = self.lower_return_ty(&return_ty, throws.as_ref());
 
-        if let Some(HasSelf { ty, .. }) = self.hir.try_get::<HasSelf>(func) {
-            let ty = self.lower_ty(ty);
+        if let Some(HasSelf { ty, .. }) = self.hir.try_get::<HasSelf>(func).cloned() {
+            let ty = self.lower_ty(&ty);
 
             mir_params.insert(0, ty);
         }
 
-        let mir_id = self.mir.context_mut().create_function(&symbol, mir_params, return_ty);
+        self.mir.context_mut().create_function(symbol, mir_params, return_ty)
+    }
+
+    fn lower_func_body(&mut self, func: Id<HirFunc>, mir_id: MirId<MirFunc>) {
+        self.mir.select_func(mir_id);
+
+        let Callable { params, return_ty, throws, .. } = self.hir.try_get(func).cloned()
+            .expect("internal compiler error: function doesn't have a signature");
+
+        // errors that aren't caught are returned
+        self.result_ty = throws.map(|throws| self.lower_return_ty(&return_ty, Some(&throws)));
 
         // add the self parameter to the function
-        if let Some(HasSelf { local, ty }) = self.hir.try_get::<HasSelf>(func) {
-            let ty = self.lower_ty(ty);
+        if let Some(HasSelf { local, ty }) = self.hir.try_get::<HasSelf>(func).cloned() {
+            let ty = self.lower_ty(&ty);
             let mir_local = self.mir.context_mut().create_local(mir_id, ty);
-            self.local_map.insert(*local, mir_local.id());
+            self.local_map.insert(local, mir_local.id());
         }
 
         // add locals to the function
@@ -40,14 +132,6 @@ impl HirLowerer<'_> {
             self.local_map.insert(param.id, mir_local.id());
         }
 
-        self.func_map.insert(func, mir_id);
-    }
-
-    pub fn lower_func(&mut self, func: Id<HirFunc>) {
-        let mir_id = *self.func_map.get(&func).unwrap();
-
-        self.mir.select_func(mir_id);
-
         let Some(code_block) = self.hir.children(func.as_base()).iter().find_map(|child| self.hir.cast_id::<CodeBlock>(*child)) else {
             return;
         };
@@ -55,6 +139,9 @@ impl HirLowerer<'_> {
         // Lower the code
         let bb0 = self.mir.append_basic_block();
         self.mir.select_basic_block(bb0);
+
+        self.init_self = self.hir.try_get::<Initializer>(func).cloned().map(|init| self.build_init_self(&init));
+
         self.lower_code_block_func(code_block);
     }
-}
\ No newline at end of file
+}
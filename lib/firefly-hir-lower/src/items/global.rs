@@ -1,4 +1,6 @@
-use firefly_hir::{items::{mangle::MangledName, Global as HirGlobal}, Id};
+use firefly_hir::{items::{init_order::GlobalInitOrder, mangle::MangledName, Global as HirGlobal}, Id};
+use firefly_mangle::SymbolName;
+use firefly_mir::{code::Terminator, ty::{Ty, TyKind}, value::{Place, PlaceKind}};
 
 use crate::HirLowerer;
 
@@ -7,10 +9,46 @@ impl HirLowerer<'_> {
         let MangledName { symbol } = self.hir.try_get_computed(id).cloned()
             .expect("internal compiler error: function doesn't have a mangled name");
 
-        let global = self.hir.get(id);
+        let global_ty = self.hir.get(id).ty.clone();
+        let global_ty = self.lower_ty(&global_ty);
 
-        let global_ty = self.lower_ty(&global.ty);
+        let mir_id = self.mir.context_mut().create_global(&symbol, global_ty);
 
-        self.mir.context_mut().create_global(&symbol, global_ty);
+        self.global_map.insert(id, mir_id);
     }
-}
\ No newline at end of file
+
+    /// Creates a function that assigns each global its
+    /// default value, after the globals it depends on
+    pub fn lower_global_initializer(&mut self) {
+        let init_order = GlobalInitOrder::new(self.hir);
+
+        if init_order.order.is_empty() {
+            return;
+        }
+
+        let name = SymbolName::Custom("_init_globals".to_string());
+        let void = Ty::new(TyKind::Void);
+
+        let initializer = self.mir.context_mut().create_function(&name, Vec::new(), void);
+        self.mir.context_mut().set_global_initializer(initializer);
+
+        self.mir.select_func(initializer);
+        let bb0 = self.mir.append_basic_block();
+        self.mir.select_basic_block(bb0);
+
+        for global in init_order.order {
+            let default_value = self.hir.get(global).default_value.clone();
+            let value = self.lower_immediate(&default_value);
+
+            let place = Place {
+                kind: Box::new(PlaceKind::Global(self.global_map[&global])),
+                ty: value.ty.clone(),
+                span: default_value.span,
+            };
+
+            self.mir.build_assign(place, value);
+        }
+
+        self.mir.build_terminator(Terminator::returns_void());
+    }
+}
@@ -0,0 +1,49 @@
+use firefly_hir::{func::Initializer, items::Field};
+use firefly_mir::value::{Immediate, ImmediateKind, Place};
+use firefly_span::Span;
+use itertools::Itertools;
+
+use crate::HirLowerer;
+
+impl HirLowerer<'_> {
+    /// Creates the `self` local of an init, starting
+    /// each field with its default value if it has one
+    pub(super) fn build_init_self(&mut self, init: &Initializer) -> Place {
+        let ty = self.hir.get(init.local).ty.clone();
+        let ty = self.lower_ty(&ty);
+
+        let local = self.mir.build_local(ty.clone());
+        self.local_map.insert(init.local, local.id());
+        let place = local.place_unspanned();
+
+        let fields = self.hir.children(init.struct_def.as_base())
+            .iter()
+            .filter_map(|field| self.hir.cast_id::<Field>(*field))
+            .collect_vec();
+
+        let mut field_values = Vec::new();
+
+        for field in fields {
+            let Field { ty, default, .. } = self.hir.get(field).clone();
+
+            let value = match default {
+                Some(default) => self.lower_immediate(&default),
+
+                // The init is checked to assign the field before it's read
+                None => Immediate {
+                    kind: Box::new(ImmediateKind::Void),
+                    ty: self.lower_ty(&ty),
+                    span: Span::default(),
+                },
+            };
+
+            field_values.push(value);
+        }
+
+        let value = Self::build_instance(field_values, ty, Span::default());
+
+        self.mir.build_assign(place.clone(), value);
+
+        place
+    }
+}
@@ -1,3 +1,6 @@
 mod struct_def;
+mod enum_def;
 mod func;
-mod global;
\ No newline at end of file
+mod init;
+mod global;
+mod protocol_def;
\ No newline at end of file
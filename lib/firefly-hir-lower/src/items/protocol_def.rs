@@ -0,0 +1,116 @@
+use firefly_hir::{
+    func::Func as HirFunc,
+    generics::Monomorph,
+    items::{conformance::Conformance, mangle::MangledName, ProtocolDef},
+    ty::{Ty as HirTy, TyKind as HirTyKind},
+    value::Value,
+    Id,
+};
+use firefly_mangle::SymbolName;
+use firefly_mir::{
+    ty::witness_table::WitnessTable,
+    value::{Immediate, ImmediateKind},
+    Id as MirId,
+};
+use itertools::Itertools;
+
+use crate::HirLowerer;
+
+impl HirLowerer<'_> {
+    /// Gets the protocol a method is a requirement of,
+    /// if it was declared in a protocol
+    pub fn requirement_of(&self, func: Id<HirFunc>) -> Option<Id<ProtocolDef>> {
+        let parent = self.hir.parent(func.as_base())?;
+
+        self.hir.cast_id::<ProtocolDef>(parent)
+    }
+
+    /// Finds the method a concrete type implements a requirement with
+    pub fn witness_of(&self, ty: &HirTy, requirement: Id<HirFunc>) -> Monomorph<HirFunc> {
+        let HirTyKind::StructDef(struct_def, generics) = &ty.kind else {
+            panic!("internal compiler error: only structs can conform to protocols");
+        };
+
+        let witness = self.hir.try_get::<Conformance>(*struct_def)
+            .and_then(|conformance| conformance.witnesses.get(&requirement))
+            .expect("internal compiler error: requirement doesn't have a witness");
+
+        Monomorph::with_generics(*witness, generics.clone())
+    }
+
+    /// Wraps a value in an existential with the
+    /// witness table of its type for a protocol
+    pub fn lower_existential(&mut self, value: &Value, protocol_ty: &HirTy) -> Immediate {
+        let HirTyKind::Protocol(protocol) = &protocol_ty.kind else {
+            panic!("internal compiler error: existential doesn't have a protocol type");
+        };
+
+        let inner_ty = value.ty.substitute(&self.generics);
+        let witness_table = self.witness_table(&inner_ty, *protocol);
+
+        let inner = self.lower_immediate(value);
+
+        Immediate {
+            kind: Box::new(ImmediateKind::Existential(inner, witness_table)),
+            ty: self.lower_ty(protocol_ty),
+            span: value.span,
+        }
+    }
+
+    /// Calls a requirement on a value of a protocol type, which
+    /// finds the method to call in the value's witness table
+    pub fn lower_witness(&mut self, receiver: &Value, requirement: Id<HirFunc>, func_ty: &HirTy) -> Immediate {
+        let index = self.requirement_index(requirement);
+        let existential = self.lower_immediate(receiver);
+
+        Immediate {
+            kind: Box::new(ImmediateKind::Witness(existential, index)),
+            ty: self.lower_ty(func_ty),
+            span: receiver.span,
+        }
+    }
+
+    /// The index of a requirement's function in witness tables
+    pub fn requirement_index(&self, requirement: Id<HirFunc>) -> usize {
+        let protocol = self.requirement_of(requirement)
+            .expect("internal compiler error: method isn't a requirement");
+
+        ProtocolDef::requirements(protocol, self.hir)
+            .iter()
+            .position(|other| *other == requirement)
+            .expect("internal compiler error: requirement isn't in its protocol")
+    }
+
+    /// Gets the witness table of a type for a protocol,
+    /// creating it the first time it's used
+    fn witness_table(&mut self, ty: &HirTy, protocol: Id<ProtocolDef>) -> MirId<WitnessTable> {
+        let HirTyKind::StructDef(struct_def, generics) = &ty.kind else {
+            panic!("internal compiler error: only structs can conform to protocols");
+        };
+
+        let struct_def = Monomorph::with_generics(*struct_def, generics.clone());
+        let struct_symbol = MangledName::of_monomorph(&struct_def, self.hir);
+
+        let MangledName { symbol: protocol_symbol } = self.hir.try_get_computed(protocol).cloned()
+            .expect("internal compiler error: protocol doesn't have a mangled name");
+
+        let symbol = SymbolName::Witness(Box::new(struct_symbol), Box::new(protocol_symbol));
+
+        if let Some(mir_id) = self.witness_tables.get(&symbol) {
+            return *mir_id;
+        }
+
+        let functions = ProtocolDef::requirements(protocol, self.hir)
+            .into_iter()
+            .map(|requirement| self.witness_of(ty, requirement))
+            .collect_vec()
+            .iter()
+            .map(|witness| self.func_instance(witness))
+            .collect_vec();
+
+        let mir_id = self.mir.context_mut().create_witness_table(&symbol, functions);
+        self.witness_tables.insert(symbol, mir_id);
+
+        mir_id
+    }
+}
@@ -1,10 +1,17 @@
-use firefly_hir::{items::{mangle::MangledName, Field}, Id};
+use firefly_hir::{generics::{GenericParameterList, Monomorph}, items::{mangle::MangledName, Field}, Id};
+use firefly_mir::{ty::struct_def::StructDef as MirStructDef, value::Immediate, Id as MirId};
+use itertools::Itertools;
 
 use crate::HirLowerer;
 use firefly_hir::items::StructDef as HirStructDef;
 
 impl HirLowerer<'_> {
     pub fn create_struct(&mut self, struct_def: Id<HirStructDef>) {
+        // Generic structs are created when they're used
+        if !GenericParameterList::in_scope(struct_def.as_base(), self.hir).is_empty() {
+            return;
+        }
+
         let MangledName { symbol } = self.hir.try_get_computed(struct_def)
             .expect("internal compiler error: function doesn't have a mangled name");
 
@@ -14,19 +21,70 @@ impl HirLowerer<'_> {
     }
 
     pub fn lower_struct(&mut self, struct_def: Id<HirStructDef>) {
-        let mir_id = *self.struct_map.get(&struct_def).unwrap();
+        let Some(mir_id) = self.struct_map.get(&struct_def).cloned() else {
+            return;
+        };
+
+        self.lower_fields(struct_def, mir_id);
+    }
+
+    /// Gets the struct for a list of generic arguments,
+    /// creating it the first time it's used
+    pub fn struct_instance(&mut self, struct_def: Monomorph<HirStructDef>) -> MirId<MirStructDef> {
+        let struct_def = struct_def.substitute(&self.generics).normalize(self.hir);
+        let symbol = MangledName::of_monomorph(&struct_def, self.hir);
+
+        if let Some(mir_id) = self.struct_instances.get(&symbol) {
+            return *mir_id;
+        }
+
+        let mir_id = self.mir.context_mut().create_struct(&symbol);
+        self.struct_instances.insert(symbol, mir_id);
+
+        let generics = struct_def.substitution(self.hir);
+        let outer_generics = std::mem::replace(&mut self.generics, generics);
+
+        self.lower_fields(struct_def.id, mir_id);
 
+        self.generics = outer_generics;
+
+        mir_id
+    }
+
+    fn lower_fields(&mut self, struct_def: Id<HirStructDef>, mir_id: MirId<MirStructDef>) {
         let fields = self.hir.children(struct_def.as_base())
             .iter()
-            .filter_map(|field| self.hir.cast_id::<Field>(*field));
+            .filter_map(|field| self.hir.cast_id::<Field>(*field))
+            .collect_vec();
 
         for field_id in fields {
-            let field = self.hir.get(field_id);
-            let field_ty = self.lower_ty(&field.ty);
+            let field_ty = self.hir.get(field_id).ty.clone();
+            let field_ty = self.lower_ty(&field_ty);
 
             let field_idx = self.mir.context_mut().create_field(mir_id, field_ty);
 
+            // Every instance of a struct has its fields in the same order
             self.field_map.insert(field_id, field_idx);
         }
     }
-}
\ No newline at end of file
+}
+
+impl HirLowerer<'_> {
+    /// Evaluates the default value of a field left out of a call
+    /// to the memberwise initializer, with the struct's generic
+    /// arguments filled in
+    pub(crate) fn lower_field_default(&mut self, struct_def: &Monomorph<HirStructDef>, field: Id<Field>) -> Immediate {
+        let default = self.hir.get(field).default.clone()
+            .expect("internal compiler error: field left out of an initializer doesn't have a default");
+
+        let struct_def = struct_def.substitute(&self.generics);
+        let generics = struct_def.substitution(self.hir);
+        let outer_generics = std::mem::replace(&mut self.generics, generics);
+
+        let value = self.lower_immediate(&default);
+
+        self.generics = outer_generics;
+
+        value
+    }
+}
@@ -2,24 +2,50 @@ mod items;
 mod ty;
 mod value;
 mod code;
+mod errors;
 
 use std::collections::HashMap;
 
-use firefly_hir::{func::Func as HirFunc, items::{Field, Global as HirGlobal, StructDef as HirStruct}, stmt::{CodeBlock, Local as HirLocal}, HirContext, Id as HirId};
-use firefly_mir::{builder::Builder, code::{Function as MirFunc, Global as MirGlobal, Local as MirLocal}, ty::struct_def::StructDef as MirStruct, MirContext, Id as MirId};
+use firefly_hir::{func::{EntryPoint, Func as HirFunc}, generics::{Monomorph, Substitution}, items::{EnumDef as HirEnum, Field, Global as HirGlobal, StructDef as HirStruct}, stmt::{CodeBlock, Local as HirLocal}, HirContext, Id as HirId};
+use firefly_mir::{builder::Builder, code::{Function as MirFunc, Global as MirGlobal, Local as MirLocal}, ty::{enum_def::EnumDef as MirEnum, struct_def::StructDef as MirStruct, witness_table::WitnessTable, Ty as MirTy}, value::Place, MirContext, Id as MirId};
+use firefly_mangle::SymbolName;
 use itertools::Itertools;
-use value::loops::LoopMarker;
+use value::{loops::LoopMarker, throws::CatchMarker};
 
 pub struct HirLowerer<'a> {
     mir: Builder<'a>,
     hir: &'a mut HirContext,
 
     struct_map: HashMap<HirId<HirStruct>, MirId<MirStruct>>,
+    enum_map: HashMap<HirId<HirEnum>, MirId<MirEnum>>,
     func_map: HashMap<HirId<HirFunc>, MirId<MirFunc>>,
     local_map: HashMap<HirId<HirLocal>, MirId<MirLocal>>,
     global_map: HashMap<HirId<HirGlobal>, MirId<MirGlobal>>,
     field_map: HashMap<HirId<Field>, usize>,
-    loop_map: HashMap<HirId<CodeBlock>, LoopMarker>
+    loop_map: HashMap<HirId<CodeBlock>, LoopMarker>,
+
+    // Errors are thrown to the innermost `catch`, or
+    // returned if the current function can throw
+    catch_stack: Vec<CatchMarker>,
+    result_ty: Option<MirTy>,
+
+    // An init builds `self` in a local, and returns it
+    init_self: Option<Place>,
+
+    // Generic structs and functions are lowered once
+    // for each list of generic arguments they are used with
+    struct_instances: HashMap<SymbolName, MirId<MirStruct>>,
+    func_instances: HashMap<(HirId<HirFunc>, SymbolName), MirId<MirFunc>>,
+    pending_funcs: Vec<(Monomorph<HirFunc>, MirId<MirFunc>, usize)>,
+    generics: Substitution,
+
+    // How many generic functions led to the one being lowered,
+    // which stops a function calling itself with ever larger types
+    instance_depth: usize,
+
+    // Each type has a witness table for each protocol
+    // it is used as
+    witness_tables: HashMap<SymbolName, MirId<WitnessTable>>,
 }
 
 pub fn lower<'a>(hir: &'a mut HirContext, mir: &'a mut MirContext) {
@@ -30,11 +56,23 @@ pub fn lower<'a>(hir: &'a mut HirContext, mir: &'a mut MirContext) {
         hir,
 
         loop_map:   HashMap::new(),
+        catch_stack: Vec::new(),
+        result_ty:   None,
+        init_self:   None,
         func_map:   HashMap::new(),
         local_map:  HashMap::new(),
         field_map:  HashMap::new(),
         global_map: HashMap::new(),
         struct_map: HashMap::new(),
+        enum_map:   HashMap::new(),
+
+        struct_instances: HashMap::new(),
+        func_instances:   HashMap::new(),
+        pending_funcs:    Vec::new(),
+        generics:         Substitution::default(),
+        instance_depth:   0,
+
+        witness_tables:   HashMap::new(),
     };
 
 
@@ -43,6 +81,11 @@ pub fn lower<'a>(hir: &'a mut HirContext, mir: &'a mut MirContext) {
                .into_iter()
                .for_each(|item| lowerer.create_struct(item));
 
+    lowerer.hir.entities_with()
+               .collect_vec()
+               .into_iter()
+               .for_each(|item| lowerer.create_enum(item));
+
     lowerer.hir.entities_with()
                .collect_vec()
                .into_iter()
@@ -53,15 +96,28 @@ pub fn lower<'a>(hir: &'a mut HirContext, mir: &'a mut MirContext) {
                .into_iter()
                .for_each(|item| lowerer.create_global(item));
 
-    // todo: initialize the globals
+    if let Some(entry_point) = EntryPoint::new(lowerer.hir).func(lowerer.hir) {
+        let mir_id = lowerer.func_map[&entry_point];
+
+        lowerer.mir.context_mut().set_entry_point(mir_id);
+    }
 
     lowerer.hir.entities_with()
                .collect_vec()
                .into_iter()
                .for_each(|item| lowerer.lower_struct(item));
 
+    lowerer.hir.entities_with()
+               .collect_vec()
+               .into_iter()
+               .for_each(|item| lowerer.lower_enum(item));
+
     lowerer.hir.entities_with()
                .collect_vec()
                .into_iter()
                .for_each(|item| lowerer.lower_func(item));
+
+    lowerer.lower_func_instances();
+
+    lowerer.lower_global_initializer();
 }
\ No newline at end of file
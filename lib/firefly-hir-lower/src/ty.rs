@@ -1,16 +1,16 @@
-use firefly_hir::ty::{Ty as HirTy, TyKind as HirTyKind};
-use firefly_mir::ty::{Ty as MirTy, TyKind as MirTyKind};
+use firefly_hir::{generics::Monomorph, items::mangle::MangledName, ty::{IntegerKind as HirIntegerKind, Ty as HirTy, TyKind as HirTyKind}};
+use firefly_mir::ty::{IntegerKind as MirIntegerKind, Ty as MirTy, TyKind as MirTyKind};
 
 use crate::HirLowerer;
 
 impl HirLowerer<'_> {
-    pub fn lower_ty(&self, hir_ty: &HirTy) -> MirTy {
+    pub fn lower_ty(&mut self, hir_ty: &HirTy) -> MirTy {
         let kind =
         match &hir_ty.kind {
             HirTyKind::Unit => MirTyKind::Void,
             HirTyKind::Never => MirTyKind::Void,
 
-            HirTyKind::Integer => MirTyKind::Integer,
+            HirTyKind::Integer(kind) => MirTyKind::Integer(self.lower_integer_kind(*kind)),
             HirTyKind::String => MirTyKind::String,
             HirTyKind::Bool => MirTyKind::Bool,
             HirTyKind::Float => MirTyKind::Float,
@@ -27,13 +27,67 @@ impl HirLowerer<'_> {
 
                 MirTyKind::Tuple(items)
             }
-            HirTyKind::StructDef(id) => {
-                let mir_id = self.struct_map.get(id).unwrap();
+            HirTyKind::StructDef(id, generics) => {
+                let mir_id = match generics.is_empty() {
+                    true => *self.struct_map.get(id).unwrap(),
+                    false => self.struct_instance(Monomorph::with_generics(*id, generics.clone())),
+                };
+                let struct_ty = MirTyKind::Struct(mir_id);
 
-                MirTyKind::Struct(*mir_id)
+                // Instances of classes live on the heap
+                if self.hir.get(*id).is_class {
+                    return MirTy::new(MirTyKind::Reference(MirTy::new(struct_ty)));
+                }
+
+                struct_ty
+            }
+            HirTyKind::EnumDef(id) => {
+                let mir_id = self.enum_map.get(id).unwrap();
+
+                MirTyKind::Enum(*mir_id)
+            }
+
+            HirTyKind::Optional(inner) => MirTyKind::Optional(self.lower_ty(inner)),
+
+            HirTyKind::Protocol(id) => {
+                let MangledName { symbol } = self.hir.try_get_computed(*id).cloned()
+                    .expect("internal compiler error: protocol doesn't have a mangled name");
+
+                MirTyKind::Existential(symbol)
+            }
+
+            HirTyKind::Associated(..) => {
+                let ty = hir_ty.substitute(&self.generics).normalize(self.hir);
+
+                if let HirTyKind::Associated(..) = ty.kind {
+                    panic!("internal compiler error: associated type wasn't chosen");
+                }
+
+                return self.lower_ty(&ty);
+            }
+
+            HirTyKind::Generic(parameter) => {
+                let Some(ty) = self.generics.get(*parameter).cloned() else {
+                    panic!("internal compiler error: generic parameter wasn't substituted");
+                };
+
+                return self.lower_ty(&ty);
             }
         };
 
         MirTy::new(kind)
     }
-}
\ No newline at end of file
+
+    pub fn lower_integer_kind(&self, kind: HirIntegerKind) -> MirIntegerKind {
+        match kind {
+            HirIntegerKind::I8 => MirIntegerKind::I8,
+            HirIntegerKind::I16 => MirIntegerKind::I16,
+            HirIntegerKind::I32 => MirIntegerKind::I32,
+            HirIntegerKind::I64 => MirIntegerKind::I64,
+            HirIntegerKind::U8 => MirIntegerKind::U8,
+            HirIntegerKind::U16 => MirIntegerKind::U16,
+            HirIntegerKind::U32 => MirIntegerKind::U32,
+            HirIntegerKind::U64 => MirIntegerKind::U64,
+        }
+    }
+}
@@ -1,8 +1,8 @@
 use firefly_mir::{
-    ty::{Ty as MirTy, TyKind as MirTyKind},
+    ty::Ty as MirTy,
     value::{
         BinaryIntrinsic, BooleanBinaryOp, Comparison, FloatBinaryOp, Immediate, ImmediateKind,
-        IntegerBinaryOp, StringBinaryOp, UnaryIntrinsic,
+        IntegerBinaryOp, Overflow, StringBinaryOp, UnaryIntrinsic,
     },
 };
 use firefly_span::Span;
@@ -14,60 +14,79 @@ impl HirLowerer<'_> {
         &self,
         builtin_name: &str,
         args: Vec<Immediate>,
+        ty: MirTy,
         span: Span,
     ) -> Immediate {
-        // Check for binary
-        let binary_kind = match builtin_name {
-            "eq_int" => BinaryIntrinsic::Compare(Comparison::Equal),
-            "neq_int" => BinaryIntrinsic::Compare(Comparison::NotEqual),
-            "lt_int" => BinaryIntrinsic::Compare(Comparison::LessThan),
-            "leq_int" => BinaryIntrinsic::Compare(Comparison::LessThanOrEqual),
-            "gt_int" => BinaryIntrinsic::Compare(Comparison::GreaterThan),
-            "geq_int" => BinaryIntrinsic::Compare(Comparison::GreaterThanOrEqual),
-
-            "add" => BinaryIntrinsic::Integer(IntegerBinaryOp::Add),
-            "sub" => BinaryIntrinsic::Integer(IntegerBinaryOp::Sub),
-            "mul" => BinaryIntrinsic::Integer(IntegerBinaryOp::Mul),
-            "div" => BinaryIntrinsic::Integer(IntegerBinaryOp::Div),
-            "rem" => BinaryIntrinsic::Integer(IntegerBinaryOp::Rem),
-            "left_shift" => BinaryIntrinsic::Integer(IntegerBinaryOp::ShiftLeft),
-            "right_shift" => BinaryIntrinsic::Integer(IntegerBinaryOp::ShiftRight),
-            "bitand" => BinaryIntrinsic::Integer(IntegerBinaryOp::BitAnd),
-            "bitor" => BinaryIntrinsic::Integer(IntegerBinaryOp::BitOr),
-            "bitxor" => BinaryIntrinsic::Integer(IntegerBinaryOp::BitXor),
-
-            "eq_float" => BinaryIntrinsic::Compare(Comparison::Equal),
-            "neq_float" => BinaryIntrinsic::Compare(Comparison::NotEqual),
-            "lt_float" => BinaryIntrinsic::Compare(Comparison::LessThan),
-            "leq_float" => BinaryIntrinsic::Compare(Comparison::LessThanOrEqual),
-            "gt_float" => BinaryIntrinsic::Compare(Comparison::GreaterThan),
-            "geq_float" => BinaryIntrinsic::Compare(Comparison::GreaterThanOrEqual),
-
-            "fadd" => BinaryIntrinsic::Float(FloatBinaryOp::Add),
-            "fsub" => BinaryIntrinsic::Float(FloatBinaryOp::Sub),
-            "fmul" => BinaryIntrinsic::Float(FloatBinaryOp::Mul),
-            "fdiv" => BinaryIntrinsic::Float(FloatBinaryOp::Div),
-            "frem" => BinaryIntrinsic::Float(FloatBinaryOp::Rem),
-            "fpow" => BinaryIntrinsic::Float(FloatBinaryOp::Pow),
-
-            "and" => BinaryIntrinsic::Boolean(BooleanBinaryOp::And),
-            "or" => BinaryIntrinsic::Boolean(BooleanBinaryOp::Or),
-            "xor" => BinaryIntrinsic::Boolean(BooleanBinaryOp::Xor),
-            "eq_bool" => BinaryIntrinsic::Compare(Comparison::Equal),
-            "neq_bool" => BinaryIntrinsic::Compare(Comparison::NotEqual),
-            "eq_str" => BinaryIntrinsic::Compare(Comparison::Equal),
-            "neq_str" => BinaryIntrinsic::Compare(Comparison::NotEqual),
-            "concat" => BinaryIntrinsic::String(StringBinaryOp::Concat),
-
-            _ => return self.lower_unary_builtin(builtin_name, args, span),
+        // Integer arithmetic can opt out of trapping on overflow
+        let (overflow, op_name) = if let Some(name) = builtin_name.strip_prefix("wrapping_") {
+            (Overflow::Wrap, name)
+        } else if let Some(name) = builtin_name.strip_prefix("saturating_") {
+            (Overflow::Saturate, name)
+        } else if let Some(name) = builtin_name.strip_prefix("checked_") {
+            (Overflow::Check, name)
+        } else {
+            (Overflow::Trap, builtin_name)
+        };
+
+        let integer_op = match op_name {
+            "add" => Some(IntegerBinaryOp::Add),
+            "sub" => Some(IntegerBinaryOp::Sub),
+            "mul" => Some(IntegerBinaryOp::Mul),
+            "div" => Some(IntegerBinaryOp::Div),
+            "rem" => Some(IntegerBinaryOp::Rem),
+            "left_shift" => Some(IntegerBinaryOp::ShiftLeft),
+            "right_shift" => Some(IntegerBinaryOp::ShiftRight),
+            "bitand" => Some(IntegerBinaryOp::BitAnd),
+            "bitor" => Some(IntegerBinaryOp::BitOr),
+            "bitxor" => Some(IntegerBinaryOp::BitXor),
+            _ => None,
         };
 
-        let ty = match binary_kind {
-            BinaryIntrinsic::Compare(_) => MirTy::new(MirTyKind::Bool),
-            BinaryIntrinsic::Float(_) => MirTy::new(MirTyKind::Float),
-            BinaryIntrinsic::Integer(_) => MirTy::new(MirTyKind::Integer),
-            BinaryIntrinsic::Boolean(_) => MirTy::new(MirTyKind::Bool),
-            BinaryIntrinsic::String(_) => MirTy::new(MirTyKind::String),
+        // Check for binary
+        let binary_kind = if let Some(integer_op) = integer_op {
+            BinaryIntrinsic::Integer(integer_op, overflow)
+        } else {
+            match builtin_name {
+                "eq_int" => BinaryIntrinsic::Compare(Comparison::Equal),
+                "neq_int" => BinaryIntrinsic::Compare(Comparison::NotEqual),
+                "lt_int" => BinaryIntrinsic::Compare(Comparison::LessThan),
+                "leq_int" => BinaryIntrinsic::Compare(Comparison::LessThanOrEqual),
+                "gt_int" => BinaryIntrinsic::Compare(Comparison::GreaterThan),
+                "geq_int" => BinaryIntrinsic::Compare(Comparison::GreaterThanOrEqual),
+
+                "eq_float" => BinaryIntrinsic::Compare(Comparison::Equal),
+                "neq_float" => BinaryIntrinsic::Compare(Comparison::NotEqual),
+                "lt_float" => BinaryIntrinsic::Compare(Comparison::LessThan),
+                "leq_float" => BinaryIntrinsic::Compare(Comparison::LessThanOrEqual),
+                "gt_float" => BinaryIntrinsic::Compare(Comparison::GreaterThan),
+                "geq_float" => BinaryIntrinsic::Compare(Comparison::GreaterThanOrEqual),
+
+                "fadd" => BinaryIntrinsic::Float(FloatBinaryOp::Add),
+                "fsub" => BinaryIntrinsic::Float(FloatBinaryOp::Sub),
+                "fmul" => BinaryIntrinsic::Float(FloatBinaryOp::Mul),
+                "fdiv" => BinaryIntrinsic::Float(FloatBinaryOp::Div),
+                "frem" => BinaryIntrinsic::Float(FloatBinaryOp::Rem),
+                "fpow" => BinaryIntrinsic::Float(FloatBinaryOp::Pow),
+
+                "and" => BinaryIntrinsic::Boolean(BooleanBinaryOp::And),
+                "or" => BinaryIntrinsic::Boolean(BooleanBinaryOp::Or),
+                "xor" => BinaryIntrinsic::Boolean(BooleanBinaryOp::Xor),
+                "eq_bool" => BinaryIntrinsic::Compare(Comparison::Equal),
+                "neq_bool" => BinaryIntrinsic::Compare(Comparison::NotEqual),
+                "eq_enum" => BinaryIntrinsic::Compare(Comparison::Equal),
+                "neq_enum" => BinaryIntrinsic::Compare(Comparison::NotEqual),
+                "eq_str" => BinaryIntrinsic::Compare(Comparison::Equal),
+                "neq_str" => BinaryIntrinsic::Compare(Comparison::NotEqual),
+                "eq_ref" => BinaryIntrinsic::Compare(Comparison::Equal),
+                "neq_ref" => BinaryIntrinsic::Compare(Comparison::NotEqual),
+                "concat" => BinaryIntrinsic::String(StringBinaryOp::Concat),
+
+                "live_objects" => {
+                    return Immediate { kind: Box::new(ImmediateKind::LiveObjects), ty, span };
+                }
+
+                _ => return self.lower_unary_builtin(builtin_name, args, ty, span),
+            }
         };
 
         let [lhs, rhs] = &args[..] else { panic!() };
@@ -83,39 +102,33 @@ impl HirLowerer<'_> {
         &self,
         builtin_name: &str,
         args: Vec<Immediate>,
+        ty: MirTy,
         span: Span,
     ) -> Immediate {
-        let (imm, ty) = match builtin_name {
-            "not" => (UnaryIntrinsic::Not, MirTyKind::Bool),
-            "bitnot" => (UnaryIntrinsic::BitNot, MirTyKind::Integer),
-
-            "len" => (UnaryIntrinsic::Len, MirTyKind::Integer),
+        let imm = match builtin_name {
+            "not" => UnaryIntrinsic::Not,
+            "bitnot" => UnaryIntrinsic::BitNot,
 
-            "print" => (UnaryIntrinsic::Print, MirTyKind::Void),
+            "len" => UnaryIntrinsic::Len,
 
-            "parse_int" => (UnaryIntrinsic::Parse, MirTyKind::Integer),
-            "format_int" => (UnaryIntrinsic::Format, MirTyKind::String),
+            "print" => UnaryIntrinsic::Print,
 
-            "parse_bool" => (UnaryIntrinsic::Parse, MirTyKind::Bool),
-            "format_bool" => (UnaryIntrinsic::Format, MirTyKind::String),
+            "parse_int" | "parse_bool" | "parse_float" => UnaryIntrinsic::Parse,
+            "format_int" | "format_bool" | "format_float" => UnaryIntrinsic::Format,
 
-            "parse_float" => (UnaryIntrinsic::Parse, MirTyKind::Float),
-            "format_float" => (UnaryIntrinsic::Format, MirTyKind::String),
+            "floor" => UnaryIntrinsic::Floor,
+            "ceil" => UnaryIntrinsic::Ceil,
+            "to_float" => UnaryIntrinsic::ToFloat,
 
-            "floor" => (UnaryIntrinsic::Floor, MirTyKind::Integer),
-            "ceil" => (UnaryIntrinsic::Ceil, MirTyKind::Integer),
-            "to_float" => (UnaryIntrinsic::ToFloat, MirTyKind::Float),
+            "to_i8" | "to_i16" | "to_i32" | "to_i64" | "to_u8" | "to_u16" | "to_u32" | "to_u64"
+            | "to_int" => UnaryIntrinsic::Convert,
 
-            "identity" => (UnaryIntrinsic::Identity, MirTyKind::Integer),
-            "identity_float" => (UnaryIntrinsic::Identity, MirTyKind::Float),
-            "negate" => (UnaryIntrinsic::Negate, MirTyKind::Integer),
-            "negate_float" => (UnaryIntrinsic::Negate, MirTyKind::Float),
+            "identity" | "identity_float" => UnaryIntrinsic::Identity,
+            "negate" | "negate_float" => UnaryIntrinsic::Negate,
 
             _ => panic!(),
         };
 
-        let ty = MirTy::new(ty);
-
         Immediate {
             kind: Box::new(ImmediateKind::Unary(imm, args[0].clone())),
             ty,
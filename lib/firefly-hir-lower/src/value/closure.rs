@@ -0,0 +1,236 @@
+use firefly_hir::{
+    ty::{Ty as HirTy, TyKind as HirTyKind},
+    value::{ClosureValue, Value, ValueKind},
+};
+use firefly_mangle::SymbolName;
+use firefly_mir::{
+    code::{Local as MirLocal, Terminator},
+    ty::{Ty as MirTy, TyKind as MirTyKind},
+    value::{Immediate, ImmediateKind, Place, PlaceKind},
+    Id as MirId,
+};
+use firefly_span::Span;
+use itertools::Itertools;
+
+use crate::HirLowerer;
+
+impl HirLowerer<'_> {
+    /// Lowers a closure to a function, which takes the values
+    /// it captured in an environment struct before its parameters
+    pub(super) fn lower_closure(&mut self, closure: &ClosureValue, ty: &HirTy, span: Span) -> Immediate {
+        let HirTyKind::Func(_, return_ty) = &ty.kind else {
+            panic!("internal compiler error: closure doesn't have a function type");
+        };
+        let return_ty = self.lower_ty(return_ty);
+
+        let name = self.closure_name("closure");
+
+        // The captured values are copied from the locals they're in
+        let capture_tys = closure
+            .captures
+            .iter()
+            .map(|capture| self.hir.get(*capture).ty.clone())
+            .collect_vec();
+        let capture_tys = capture_tys.iter().map(|ty| self.lower_ty(ty)).collect_vec();
+        let captured_values = closure
+            .captures
+            .iter()
+            .zip(&capture_tys)
+            .map(|(capture, ty)| self.local_place(self.local_map[capture], ty.clone()).move_out())
+            .collect_vec();
+
+        let environment = match closure.captures.is_empty() {
+            true => None,
+            false => {
+                let environment_name = SymbolName::Custom(format!("{name}.env"));
+                let environment_struct = self.mir.context_mut().create_struct(&environment_name);
+
+                for ty in &capture_tys {
+                    self.mir.context_mut().create_field(environment_struct, ty.clone());
+                }
+
+                let ty = MirTy::new(MirTyKind::Struct(environment_struct));
+                let kind = ImmediateKind::Struct(captured_values);
+
+                Some(Immediate { kind: Box::new(kind), ty, span })
+            }
+        };
+
+        let param_tys = closure
+            .params
+            .iter()
+            .map(|param| self.hir.get(*param).ty.clone())
+            .collect_vec();
+        let mut mir_params = param_tys.iter().map(|ty| self.lower_ty(ty)).collect_vec();
+
+        if let Some(environment) = &environment {
+            mir_params.insert(0, environment.ty.clone());
+        }
+
+        let outer_block = self.mir.current_basic_block_id();
+        let function = self.mir.context_mut().create_function(&name, mir_params, return_ty);
+        self.mir.select_func(function);
+
+        let environment_place = environment
+            .as_ref()
+            .map(|environment| self.mir.build_local(environment.ty.clone()).place_unspanned());
+
+        for param in &closure.params {
+            let ty = self.hir.get(*param).ty.clone();
+            let ty = self.lower_ty(&ty);
+            let mir_local = self.mir.build_local(ty).id();
+
+            self.local_map.insert(*param, mir_local);
+        }
+
+        let bb0 = self.mir.append_basic_block();
+        self.mir.select_basic_block(bb0);
+
+        // Inside the closure, captured locals are copies of
+        // the values in the environment
+        let mut outer_locals = Vec::new();
+
+        if let Some(environment_place) = environment_place {
+            for (index, (capture, ty)) in closure.captures.iter().zip(capture_tys).enumerate() {
+                let field = Place {
+                    kind: Box::new(PlaceKind::Field(environment_place.clone(), index)),
+                    ty: ty.clone(),
+                    span: Span::default(),
+                };
+
+                let mir_local = self.mir.build_local(ty).id();
+                let local = self.local_place(mir_local, field.ty.clone());
+                self.mir.build_assign(local, field.move_out());
+
+                outer_locals.push((*capture, self.local_map.insert(*capture, mir_local)));
+            }
+        }
+
+        // Closures can't throw, and return their own value
+        let outer_catch_stack = std::mem::take(&mut self.catch_stack);
+        let outer_result_ty = self.result_ty.take();
+        let outer_init_self = self.init_self.take();
+
+        self.lower_code_block_func(closure.body);
+
+        self.catch_stack = outer_catch_stack;
+        self.result_ty = outer_result_ty;
+        self.init_self = outer_init_self;
+
+        for (capture, outer_local) in outer_locals {
+            match outer_local {
+                Some(outer_local) => self.local_map.insert(capture, outer_local),
+                None => self.local_map.remove(&capture),
+            };
+        }
+
+        self.mir.select_basic_block(outer_block);
+
+        Immediate {
+            kind: Box::new(ImmediateKind::Closure(function, environment)),
+            ty: self.lower_ty(ty),
+            span,
+        }
+    }
+
+    /// Lowers a function that isn't called to a function value.
+    /// Builtins, initializers and enum cases don't have a function,
+    /// so they are wrapped in one
+    pub(super) fn lower_func_value(&mut self, func: &Value) -> Immediate {
+        match &func.kind {
+            ValueKind::StaticFunc(static_func) => {
+                let function = self.func_instance(static_func);
+
+                return Immediate {
+                    kind: Box::new(ImmediateKind::Closure(function, None)),
+                    ty: self.lower_ty(&func.ty),
+                    span: func.span,
+                };
+            }
+
+            // A method is called with its receiver before its arguments
+            ValueKind::InstanceFunc(receiver, instance_func) => {
+                let mut instance_func = instance_func.clone();
+
+                if self.requirement_of(instance_func.id).is_some() {
+                    let receiver_ty = receiver.ty.substitute(&self.generics);
+
+                    if let HirTyKind::Protocol(_) = receiver_ty.kind {
+                        return self.lower_witness(receiver, instance_func.id, &func.ty);
+                    }
+
+                    instance_func = self.witness_of(&receiver_ty, instance_func.id);
+                }
+
+                let function = self.func_instance(&instance_func);
+                let receiver = self.lower_immediate(receiver);
+
+                return Immediate {
+                    kind: Box::new(ImmediateKind::Closure(function, Some(receiver))),
+                    ty: self.lower_ty(&func.ty),
+                    span: func.span,
+                };
+            }
+
+            _ => {}
+        }
+
+        let HirTyKind::Func(params, return_ty) = &func.ty.kind else {
+            panic!("internal compiler error: function doesn't have a function type");
+        };
+
+        let params = params.iter().map(|param| self.lower_ty(param)).collect_vec();
+        let return_ty = self.lower_ty(return_ty);
+
+        let name = self.closure_name("wrapper");
+
+        let outer_block = self.mir.current_basic_block_id();
+        let function = self.mir.context_mut().create_function(&name, params.clone(), return_ty);
+        self.mir.select_func(function);
+
+        let args = params
+            .into_iter()
+            .map(|ty| self.mir.build_local(ty).place_unspanned().move_out())
+            .collect_vec();
+
+        let bb0 = self.mir.append_basic_block();
+        self.mir.select_basic_block(bb0);
+
+        let result = self.lower_call_with(func, args);
+
+        match result.ty.kind() {
+            MirTyKind::Void => {
+                self.mir.build_eval(result);
+                self.mir.build_terminator(Terminator::returns_void());
+            }
+            _ => self.mir.build_terminator(Terminator::returns(result)),
+        }
+
+        self.mir.select_basic_block(outer_block);
+
+        Immediate {
+            kind: Box::new(ImmediateKind::Closure(function, None)),
+            ty: self.lower_ty(&func.ty),
+            span: func.span,
+        }
+    }
+
+    /// Names a function created inside the current function
+    fn closure_name(&mut self, kind: &str) -> SymbolName {
+        let outer_function = self.mir.current_func_id();
+        let context = self.mir.context_mut();
+
+        let index = context.functions().len();
+        let outer_name = context.get_function(outer_function).name();
+
+        SymbolName::Custom(format!("{outer_name}.{kind}{index}"))
+    }
+
+    fn local_place(&self, local: MirId<MirLocal>, ty: MirTy) -> Place {
+        Place {
+            kind: Box::new(PlaceKind::Local(local)),
+            ty,
+            span: Span::default(),
+        }
+    }
+}
@@ -1,37 +1,55 @@
 
 use std::collections::VecDeque;
 
-use firefly_hir::value::{ElseValue, IfValue};
-use firefly_mir::{code::Terminator, value::Immediate};
+use firefly_hir::{stmt::CodeBlock, value::{ElseValue, IfValue, Value}, Id};
+use firefly_mir::{code::Terminator, ty::{Ty as MirTy, TyKind as MirTyKind}, value::{Immediate, Place}};
+use firefly_span::Span;
 
 use crate::HirLowerer;
 
 impl HirLowerer<'_> {
-    pub(super) fn lower_if(&mut self, if_value: &IfValue) -> Immediate {
+    /// Lowers an if by branching to each block in turn, and storing
+    /// the value each block yields in a shared local
+    pub(super) fn lower_if(&mut self, if_value: &IfValue, ty: MirTy, span: Span) -> Immediate {
+        let result = match ty.kind() {
+            MirTyKind::Void => None,
+            _ => Some(self.mir.build_local(ty).place_unspanned()),
+        };
+
         let mut if_value_queue = VecDeque::new();
         if_value_queue.push_back(if_value);
 
         let mut blocks_to_link = Vec::new();
 
         while let Some(if_value) = if_value_queue.pop_front() {
-            let condition = self.lower_immediate(&if_value.condition);
-
             let then_block = self.mir.append_basic_block();
             let else_block = self.mir.append_basic_block();
 
             // Branch to the correct block
-            self.mir.build_terminator(Terminator::branch_if(condition, then_block, else_block));
+            match if_value.binding {
+                Some(binding) => {
+                    let optional = self.build_optional_branch(&if_value.condition, then_block, else_block);
+
+                    self.mir.select_basic_block(then_block);
+                    self.bind_optional_payload(optional, binding);
+                }
+                None => {
+                    let condition = self.lower_immediate(&if_value.condition);
+                    self.mir.build_terminator(Terminator::branch_if(condition, then_block, else_block));
+
+                    self.mir.select_basic_block(then_block);
+                }
+            }
 
             // Lower the positive block
-            self.mir.select_basic_block(then_block);
-            self.lower_code_block(if_value.positive);
+            self.lower_if_branch(if_value.positive, result.as_ref());
             blocks_to_link.push(self.mir.current_basic_block_id());
 
             // Lower the negative block, if any
             self.mir.select_basic_block(else_block);
             match &if_value.negative {
                 Some(ElseValue::Else(code_block)) => {
-                    self.lower_code_block(*code_block);
+                    self.lower_if_branch(*code_block, result.as_ref());
                     blocks_to_link.push(self.mir.current_basic_block_id());
                 },
                 Some(ElseValue::ElseIf(if_value)) => { if_value_queue.push_back(if_value); },
@@ -53,6 +71,66 @@ impl HirLowerer<'_> {
 
         self.mir.select_basic_block(after_block);
 
-        Immediate::void()
+        match result {
+            Some(result) => {
+                let mut result = result.move_out();
+                result.span = span;
+
+                result
+            }
+            None => Immediate::void(),
+        }
+    }
+
+    /// Lowers one branch of an if, storing its value in the result
+    fn lower_if_branch(&mut self, code_block: Id<CodeBlock>, result: Option<&Place>) {
+        let yields = self.lower_code_block_inner(code_block);
+
+        if self.mir.is_terminated() {
+            return;
+        }
+
+        match (result, yields) {
+            (Some(result), Some(yields)) => self.mir.build_assign(result.clone(), yields),
+            (None, Some(yields)) => self.mir.build_eval(yields),
+            _ => {}
+        }
+    }
+
+    /// Lowers `&&` and `||`, only evaluating the right
+    /// operand if the left one doesn't decide the result
+    pub(super) fn lower_short_circuit(&mut self, op: &str, left: &Value, right: &Value, span: Span) -> Immediate {
+        let result = self.mir.build_local(MirTy::new(MirTyKind::Bool)).place_unspanned();
+
+        let left = self.lower_immediate(left);
+        self.mir.build_assign(result.clone(), left);
+
+        let right_block = self.mir.append_basic_block();
+        let after_block = self.mir.append_basic_block();
+
+        // `&&` only needs the right operand if the left one is true,
+        // and `||` only needs it if the left one is false
+        let condition = result.clone().move_out();
+        let terminator = match op {
+            "logical_and" => Terminator::branch_if(condition, right_block, after_block),
+            _ => Terminator::branch_if(condition, after_block, right_block),
+        };
+        self.mir.build_terminator(terminator);
+
+        // Otherwise, the right operand is the result
+        self.mir.select_basic_block(right_block);
+        let right = self.lower_immediate(right);
+        self.mir.build_assign(result.clone(), right);
+
+        if !self.mir.is_terminated() {
+            self.mir.build_terminator(Terminator::branch(after_block));
+        }
+
+        self.mir.select_basic_block(after_block);
+
+        let mut result = result.move_out();
+        result.span = span;
+
+        result
     }
 }
\ No newline at end of file
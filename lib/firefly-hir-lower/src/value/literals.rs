@@ -1,5 +1,5 @@
 
-use firefly_hir::value::Value;
+use firefly_hir::{ty::Ty as HirTy, value::{LiteralValue, Value}};
 use firefly_mir::{ty::{Ty as MirTy, TyKind as MirTyKind}, value::{ConstantValue, Immediate, ImmediateKind}};
 use firefly_span::Span;
 use itertools::Itertools;
@@ -7,20 +7,14 @@ use itertools::Itertools;
 use crate::HirLowerer;
 
 impl HirLowerer<'_> {
-    pub(super) fn lower_integer(&self, value: &str, span: Span) -> Immediate {
-        let value = if value.starts_with("0b") {
-            u64::from_str_radix(&value[2..], 2).unwrap_or(u64::MAX)
-        } else if value.starts_with("0x") {
-            u64::from_str_radix(&value[2..], 16).unwrap_or(u64::MAX)
-        } else if value.starts_with("0o") {
-            u64::from_str_radix(&value[2..], 8).unwrap_or(u64::MAX)
-        } else {
-            u64::from_str_radix(value, 10).unwrap_or(u64::MAX)
-        };
+    pub(super) fn lower_integer(&mut self, literal: &LiteralValue, ty: &HirTy, span: Span) -> Immediate {
+        let value = literal
+            .integer_value()
+            .expect("internal compiler error: integer literal is out of range");
 
         Immediate {
             kind: Box::new(ImmediateKind::Constant(ConstantValue::Integer(value))),
-            ty: MirTy::new(MirTyKind::Integer),
+            ty: self.lower_ty(ty),
             span,
         }
     }
@@ -1,11 +1,18 @@
-use firefly_hir::{stmt::CodeBlock, value::WhileValue, Id};
-use firefly_mir::{code::{BasicBlockId, Terminator}, value::Immediate};
+use firefly_hir::{stmt::CodeBlock, value::{ForEachValue, ForValue, LoopValue, RepeatValue, Value, WhileValue}, Id};
+use firefly_mir::{
+    code::{BasicBlockId, Terminator},
+    ty::{Ty as MirTy, TyKind as MirTyKind},
+    value::{BinaryIntrinsic, Comparison, ConstantValue, Immediate, ImmediateKind, IntegerBinaryOp, Overflow, Place},
+};
 
 use crate::HirLowerer;
 
 pub struct LoopMarker {
     pub start: BasicBlockId,
-    pub end: BasicBlockId
+    pub end: BasicBlockId,
+
+    /// Where the value a loop is broken out with is stored
+    pub result: Option<Place>,
 }
 
 impl HirLowerer<'_> {
@@ -24,7 +31,7 @@ impl HirLowerer<'_> {
         self.mir.build_terminator(Terminator::branch_if(condition, body, while_end));
 
         // track our loop for continue and breaks
-        self.loop_map.insert(while_value.body, LoopMarker { start: while_start, end: while_end });
+        self.loop_map.insert(while_value.body, LoopMarker { start: while_start, end: while_end, result: None });
 
         // now lower the body
         self.mir.select_basic_block(body);
@@ -39,12 +46,201 @@ impl HirLowerer<'_> {
         Immediate::void()
     }
 
-    pub(super) fn lower_break(&mut self, code_block: Id<CodeBlock>) -> Immediate {
-        let Some(LoopMarker { end, .. }) = self.loop_map.get(&code_block) else {
+    pub(super) fn lower_for(&mut self, for_value: &ForValue) -> Immediate {
+        let ty = self.lower_ty(&for_value.start.ty);
+
+        // Evaluate the bounds once, before the loop starts
+        let counter = self.mir.build_local(ty.clone()).place_unspanned();
+        let start = self.lower_immediate(&for_value.start);
+        self.mir.build_assign(counter.clone(), start);
+
+        let end = self.mir.build_local(ty.clone()).place_unspanned();
+        let end_value = self.lower_immediate(&for_value.end);
+        self.mir.build_assign(end.clone(), end_value);
+
+        let for_start = self.mir.append_basic_block();
+        let body = self.mir.append_basic_block();
+        let for_step = self.mir.append_basic_block();
+        let for_end = self.mir.append_basic_block();
+
+        self.mir.build_terminator(Terminator::branch(for_start));
+
+        // check whether the counter is still in the range
+        self.mir.select_basic_block(for_start);
+        let comparison = if for_value.inclusive { Comparison::LessThanOrEqual } else { Comparison::LessThan };
+        let in_range = Self::build_binary(
+            BinaryIntrinsic::Compare(comparison),
+            counter.clone().move_out(),
+            end.clone().move_out(),
+            MirTy::new(MirTyKind::Bool),
+        );
+
+        self.mir.build_terminator(Terminator::branch_if(in_range, body, for_end));
+
+        // continue steps to the next value, rather than rechecking the range
+        self.loop_map.insert(for_value.body, LoopMarker { start: for_step, end: for_end, result: None });
+
+        // the body gets its own copy of the counter
+        self.mir.select_basic_block(body);
+        let binding = self.mir.build_local(ty.clone());
+        self.local_map.insert(for_value.binding, binding.id());
+        let binding = binding.place_unspanned();
+        self.mir.build_assign(binding, counter.clone().move_out());
+
+        self.lower_code_block(for_value.body);
+
+        self.mir.build_terminator(Terminator::branch(for_step));
+
+        // step to the next value. An inclusive range stops at its
+        // end, so stepping past it can't overflow
+        self.mir.select_basic_block(for_step);
+        if for_value.inclusive {
+            let increment = self.mir.append_basic_block();
+            let at_end = Self::build_binary(
+                BinaryIntrinsic::Compare(Comparison::Equal),
+                counter.clone().move_out(),
+                end.move_out(),
+                MirTy::new(MirTyKind::Bool),
+            );
+
+            self.mir.build_terminator(Terminator::branch_if(at_end, for_end, increment));
+            self.mir.select_basic_block(increment);
+        }
+
+        let one = Immediate {
+            kind: Box::new(ImmediateKind::Constant(ConstantValue::Integer(1))),
+            ty: ty.clone(),
+            span: Default::default(),
+        };
+        let next = Self::build_binary(
+            BinaryIntrinsic::Integer(IntegerBinaryOp::Add, Overflow::Trap),
+            counter.clone().move_out(),
+            one,
+            ty,
+        );
+        self.mir.build_assign(counter, next);
+
+        self.mir.build_terminator(Terminator::branch(for_start));
+
+        // and finally, continue after the loop
+        self.mir.select_basic_block(for_end);
+
+        Immediate::void()
+    }
+
+    pub(super) fn lower_for_each(&mut self, for_each: &ForEachValue) -> Immediate {
+        // The sequence is moved into a local, so
+        // `next` can change it as the loop goes
+        let ty = self.lower_ty(&for_each.sequence.ty);
+        let sequence = self.lower_immediate(&for_each.sequence);
+
+        let iterator = self.mir.build_local(ty);
+        self.local_map.insert(for_each.iterator, iterator.id());
+        let iterator = iterator.place_unspanned();
+        self.mir.build_assign(iterator, sequence);
+
+        let for_start = self.mir.append_basic_block();
+        let body = self.mir.append_basic_block();
+        let for_end = self.mir.append_basic_block();
+
+        self.mir.build_terminator(Terminator::branch(for_start));
+
+        // ask for the next value, and stop once there isn't one
+        self.mir.select_basic_block(for_start);
+        let next = self.build_optional_branch(&for_each.next, body, for_end);
+
+        self.loop_map.insert(for_each.body, LoopMarker { start: for_start, end: for_end, result: None });
+
+        self.mir.select_basic_block(body);
+        self.bind_optional_payload(next, for_each.binding);
+
+        self.lower_code_block(for_each.body);
+
+        self.mir.build_terminator(Terminator::branch(for_start));
+
+        // and finally, continue after the loop
+        self.mir.select_basic_block(for_end);
+
+        Immediate::void()
+    }
+
+    fn build_binary(intrinsic: BinaryIntrinsic, left: Immediate, right: Immediate, ty: MirTy) -> Immediate {
+        Immediate {
+            kind: Box::new(ImmediateKind::Binary(intrinsic, left, right)),
+            ty,
+            span: Default::default(),
+        }
+    }
+
+    pub(super) fn lower_loop(&mut self, loop_value: &LoopValue, ty: MirTy) -> Immediate {
+        let body = self.mir.append_basic_block();
+        let loop_end = self.mir.append_basic_block();
+
+        // a loop broken out of with a value stores it in a local
+        let result = match ty.kind() {
+            MirTyKind::Void => None,
+            _ => Some(self.mir.build_local(ty).place_unspanned()),
+        };
+
+        self.mir.build_terminator(Terminator::branch(body));
+
+        self.loop_map.insert(loop_value.body, LoopMarker { start: body, end: loop_end, result: result.clone() });
+
+        self.mir.select_basic_block(body);
+        self.lower_code_block(loop_value.body);
+
+        self.mir.build_terminator(Terminator::branch(body));
+
+        self.mir.select_basic_block(loop_end);
+
+        match result {
+            Some(result) => result.move_out(),
+            None => Immediate::void(),
+        }
+    }
+
+    pub(super) fn lower_repeat(&mut self, repeat_value: &RepeatValue) -> Immediate {
+        let body = self.mir.append_basic_block();
+        let repeat_condition = self.mir.append_basic_block();
+        let repeat_end = self.mir.append_basic_block();
+
+        // the body always runs once
+        self.mir.build_terminator(Terminator::branch(body));
+
+        // continue checks the condition before running again
+        self.loop_map.insert(repeat_value.body, LoopMarker { start: repeat_condition, end: repeat_end, result: None });
+
+        self.mir.select_basic_block(body);
+        self.lower_code_block(repeat_value.body);
+
+        self.mir.build_terminator(Terminator::branch(repeat_condition));
+
+        self.mir.select_basic_block(repeat_condition);
+        let condition = self.lower_immediate(&repeat_value.condition);
+
+        self.mir.build_terminator(Terminator::branch_if(condition, body, repeat_end));
+
+        self.mir.select_basic_block(repeat_end);
+
+        Immediate::void()
+    }
+
+    pub(super) fn lower_break(&mut self, code_block: Id<CodeBlock>, value: Option<&Value>) -> Immediate {
+        let Some(LoopMarker { end, result, .. }) = self.loop_map.get(&code_block) else {
             panic!("internal compiler error: expected code block to be tracked");
         };
+        let (end, result) = (*end, result.clone());
+
+        if let Some(value) = value {
+            let value = self.lower_immediate(value);
+
+            match result {
+                Some(result) => self.mir.build_assign(result, value),
+                None => self.mir.build_eval(value),
+            }
+        }
 
-        self.mir.build_terminator(Terminator::branch(*end));
+        self.mir.build_terminator(Terminator::branch(end));
 
         Immediate::void()
     }
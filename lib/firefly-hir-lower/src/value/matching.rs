@@ -0,0 +1,154 @@
+use firefly_hir::value::{LiteralValue, MatchValue, Pattern, PatternKind};
+use firefly_mir::{
+    code::{BasicBlockId, Terminator},
+    ty::{IntegerKind, Ty as MirTy, TyKind as MirTyKind},
+    value::{ConstantValue, Immediate, ImmediateKind, Place, PlaceKind, UnaryIntrinsic},
+};
+use firefly_span::Span;
+
+use crate::HirLowerer;
+
+impl HirLowerer<'_> {
+    /// Lowers a match by testing each arm's pattern in order,
+    /// falling through to the next arm when a test fails
+    pub(super) fn lower_match(&mut self, match_value: &MatchValue, ty: MirTy, span: Span) -> Immediate {
+        let scrutinee = self.lower_immediate(&match_value.scrutinee);
+        let scrutinee_place = self.mir.build_local(scrutinee.ty.clone()).place_unspanned();
+        self.mir.build_assign(scrutinee_place.clone(), scrutinee);
+
+        let result = match ty.kind() {
+            MirTyKind::Void => None,
+            _ => Some(self.mir.build_local(ty).place_unspanned()),
+        };
+
+        let after_block = self.mir.append_basic_block();
+
+        for arm in &match_value.arms {
+            let next_block = self.mir.append_basic_block();
+
+            self.lower_pattern_test(&arm.pattern, scrutinee_place.clone(), next_block);
+            self.lower_pattern_bindings(&arm.pattern, scrutinee_place.clone());
+
+            let yields = self.lower_code_block_inner(arm.body);
+
+            if !self.mir.is_terminated() {
+                match (&result, yields) {
+                    (Some(result), Some(yields)) => self.mir.build_assign(result.clone(), yields),
+                    (None, Some(yields)) => self.mir.build_eval(yields),
+                    _ => {}
+                }
+
+                self.mir.build_terminator(Terminator::branch(after_block));
+            }
+
+            self.mir.select_basic_block(next_block);
+        }
+
+        // Exhaustiveness checking guarantees an arm matched
+        self.mir.build_terminator(Terminator::unreachable());
+
+        self.mir.select_basic_block(after_block);
+
+        match result {
+            Some(result) => {
+                let mut result = result.move_out();
+                result.span = span;
+
+                result
+            }
+            None => Immediate::void(),
+        }
+    }
+
+    /// Branches to `fail` unless the value in `place` matches the pattern
+    fn lower_pattern_test(&mut self, pattern: &Pattern, place: Place, fail: BasicBlockId) {
+        match &pattern.kind {
+            PatternKind::Wildcard | PatternKind::Binding(_) => {}
+
+            PatternKind::Literal(literal) => {
+                let constant = Self::lower_constant(literal);
+
+                self.build_switch(place.move_out(), constant, fail);
+            }
+
+            PatternKind::Tuple(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let item_place = self.sub_place(&place, index, item);
+
+                    self.lower_pattern_test(item, item_place, fail);
+                }
+            }
+
+            PatternKind::Case(case, values) => {
+                let index = self.hir.get(*case).index;
+                let discriminant = Immediate {
+                    kind: Box::new(ImmediateKind::Unary(UnaryIntrinsic::Discriminant, place.clone().move_out())),
+                    ty: MirTy::new(MirTyKind::Integer(IntegerKind::I64)),
+                    span: pattern.span,
+                };
+
+                self.build_switch(discriminant, ConstantValue::Integer(index as i128), fail);
+
+                for (index, value) in values.iter().enumerate() {
+                    let value_place = self.sub_place(&place, index, value);
+
+                    self.lower_pattern_test(value, value_place, fail);
+                }
+            }
+        }
+    }
+
+    /// Binds the locals in a pattern to the parts of the value they match
+    fn lower_pattern_bindings(&mut self, pattern: &Pattern, place: Place) {
+        match &pattern.kind {
+            PatternKind::Wildcard | PatternKind::Literal(_) => {}
+
+            PatternKind::Binding(local_id) => {
+                let ty = self.lower_ty(&pattern.ty);
+                let local = self.mir.build_local(ty);
+                self.local_map.insert(*local_id, local.id());
+
+                let local_place = local.place_unspanned();
+                self.mir.build_assign(local_place, place.move_out());
+            }
+
+            PatternKind::Tuple(items) | PatternKind::Case(_, items) => {
+                for (index, item) in items.iter().enumerate() {
+                    let item_place = self.sub_place(&place, index, item);
+
+                    self.lower_pattern_bindings(item, item_place);
+                }
+            }
+        }
+    }
+
+    /// Continues in a new block if `value` equals `constant`,
+    /// and branches to `fail` otherwise
+    fn build_switch(&mut self, value: Immediate, constant: ConstantValue, fail: BasicBlockId) {
+        let matched_block = self.mir.append_basic_block();
+
+        self.mir.build_terminator(Terminator::switch(value, vec![(constant, matched_block)], fail));
+        self.mir.select_basic_block(matched_block);
+    }
+
+    fn sub_place(&mut self, place: &Place, index: usize, pattern: &Pattern) -> Place {
+        Place {
+            kind: Box::new(PlaceKind::Field(place.clone(), index)),
+            ty: self.lower_ty(&pattern.ty),
+            span: pattern.span,
+        }
+    }
+
+    fn lower_constant(literal: &LiteralValue) -> ConstantValue {
+        match literal {
+            LiteralValue::Integer(_) => ConstantValue::Integer(
+                literal
+                    .integer_value()
+                    .expect("internal compiler error: integer literal is out of range"),
+            ),
+            LiteralValue::Boolean(boolean) => ConstantValue::Bool(*boolean),
+            LiteralValue::Float(float) => ConstantValue::Float(float.parse().unwrap_or(f64::NAN)),
+            LiteralValue::String(string) => ConstantValue::String(string.clone()),
+        }
+    }
+}
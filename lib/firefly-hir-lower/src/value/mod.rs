@@ -2,9 +2,15 @@ pub mod loops;
 mod literals;
 mod builtins;
 mod conditional;
-
-use firefly_hir::{ty::TyKind, value::{LiteralValue, Value, ValueKind}};
-use firefly_mir::{ty::{Ty as MirTy, TyKind as MirTyKind}, code::Terminator, value::{Immediate, ImmediateKind, Place, PlaceKind}};
+mod closure;
+mod matching;
+mod optional;
+mod reference;
+pub mod throws;
+
+use firefly_hir::{func::{Callable, Func as HirFunc}, ty::TyKind, value::{LiteralValue, Value, ValueKind}, Id};
+use firefly_mir::{code::Function as MirFunc, ty::Ty as MirTy, value::{Immediate, ImmediateKind, Place, PlaceKind}, Id as MirId};
+use firefly_span::Span;
 use itertools::Itertools;
 
 use crate::HirLowerer;
@@ -12,7 +18,7 @@ use crate::HirLowerer;
 impl HirLowerer<'_> {
     pub fn lower_immediate(&mut self, value: &Value) -> Immediate {
         match &value.kind {
-            ValueKind::Literal(LiteralValue::Integer(num)) => self.lower_integer(num, value.span),
+            ValueKind::Literal(literal @ LiteralValue::Integer(_)) => self.lower_integer(literal, &value.ty, value.span),
             ValueKind::Literal(LiteralValue::String(string)) => self.lower_string(string, value.span),
             ValueKind::Literal(LiteralValue::Boolean(boolean)) => self.lower_bool(*boolean, value.span),
             ValueKind::Literal(LiteralValue::Float(float)) => self.lower_float(float, value.span),
@@ -21,19 +27,69 @@ impl HirLowerer<'_> {
 
             ValueKind::Unit => Immediate::void(),
 
-            ValueKind::Invoke(function, args) => self.lower_call(function, args),
+            ValueKind::Invoke(function, args) => match (&function.kind, &args[..]) {
+                (ValueKind::BuiltinFunc(op @ ("logical_and" | "logical_or")), [left, right]) => {
+                    self.lower_short_circuit(op, left, right, value.span)
+                }
+                _ => self.lower_call(function, args),
+            },
             ValueKind::Assign(place, value) => self.lower_assign(place, value),
 
             ValueKind::Return(value) => self.lower_return(value),
-            ValueKind::Break(code_block) => self.lower_break(*code_block),
+            ValueKind::Throw(error) => self.lower_throw(error),
+            ValueKind::Try(try_value) => self.lower_try(try_value, value.span),
+            ValueKind::Do(do_value) => self.lower_do(do_value),
+            ValueKind::Break(code_block, value) => self.lower_break(*code_block, value.as_deref()),
             ValueKind::Continue(code_block) => self.lower_continue(*code_block),
 
-            ValueKind::If(if_value) => self.lower_if(if_value),
+            ValueKind::If(if_value) => {
+                let ty = self.lower_ty(&value.ty);
+                self.lower_if(if_value, ty, value.span)
+            }
 
             ValueKind::While(while_value) => self.lower_while(while_value),
 
-            ValueKind::StaticFunc(_) | ValueKind::InitFor(_) | ValueKind::InstanceFunc(..) | ValueKind::BuiltinFunc(_) => {
-                panic!("internal compiler error: first-class functions are not supported yet");
+            ValueKind::For(for_value) => self.lower_for(for_value),
+            ValueKind::ForEach(for_each) => self.lower_for_each(for_each),
+
+            ValueKind::Loop(loop_value) => {
+                let ty = self.lower_ty(&value.ty);
+                self.lower_loop(loop_value, ty)
+            }
+
+            ValueKind::Repeat(repeat_value) => self.lower_repeat(repeat_value),
+
+            ValueKind::Match(match_value) => {
+                let ty = self.lower_ty(&value.ty);
+                self.lower_match(match_value, ty, value.span)
+            }
+
+            ValueKind::Closure(closure) => self.lower_closure(closure, &value.ty, value.span),
+
+            ValueKind::Existential(inner) => self.lower_existential(inner, &value.ty),
+
+            ValueKind::Nil => {
+                let ty = self.lower_ty(&value.ty);
+                self.lower_optional(None, ty, value.span)
+            }
+            ValueKind::Wrap(inner) => {
+                let ty = self.lower_ty(&value.ty);
+                self.lower_optional(Some(inner), ty, value.span)
+            }
+            ValueKind::Unwrap(optional) => self.lower_unwrap(optional, value.span),
+            ValueKind::Coalesce(optional, default) => {
+                let ty = self.lower_ty(&value.ty);
+                self.lower_coalesce(optional, default, ty, value.span)
+            }
+            ValueKind::OptionalChain(chain) => {
+                let ty = self.lower_ty(&value.ty);
+                self.lower_optional_chain(chain, ty, value.span)
+            }
+
+            ValueKind::FieldDefault(struct_def, field) => self.lower_field_default(struct_def, *field),
+
+            ValueKind::StaticFunc(_) | ValueKind::InitFor(_) | ValueKind::InitCase(_) | ValueKind::InstanceFunc(..) | ValueKind::BuiltinFunc(_) => {
+                self.lower_func_value(value)
             }
 
             _ => self.lower_place(value).move_out(),
@@ -44,29 +100,28 @@ impl HirLowerer<'_> {
         match &value.kind {
             ValueKind::Local(id) => {
                 let mir_local = self.local_map[id];
-                let local = self.hir.get(*id);
+                let local_ty = self.hir.get(*id).ty.clone();
 
                 Place {
                     kind: Box::new(PlaceKind::Local(mir_local)),
-                    ty: self.lower_ty(&local.ty),
+                    ty: self.lower_ty(&local_ty),
                     span: value.span,
                 }
             }
 
             ValueKind::Global(id) => {
-                // todo: globals dont work
                 let mir_global = self.global_map[id];
-                let global = self.hir.get(*id);
+                let global_ty = self.hir.get(*id).ty.clone();
 
                 Place {
                     kind: Box::new(PlaceKind::Global(mir_global)),
-                    ty: self.lower_ty(&global.ty),
+                    ty: self.lower_ty(&global_ty),
                     span: value.span
                 }
             }
 
             ValueKind::FieldOf(place, field) => {
-                let place = self.lower_place(place);
+                let place = Self::deref(self.lower_place(place));
                 let field = self.field_map[field];
 
                 Place {
@@ -86,13 +141,26 @@ impl HirLowerer<'_> {
                 }
             }
 
-            other => todo!("internal compiler error: can't lower {other:?}"),
+            // Other values, like the result of a call or an
+            // unwrapped optional, are stored in a temporary
+            _ => {
+                let immediate = self.lower_immediate(value);
+                let temporary = self.mir.build_local(immediate.ty.clone()).place_unspanned();
+                self.mir.build_assign(temporary.clone(), immediate);
+
+                Place { span: value.span, ..temporary }
+            }
         }
     }
 
     pub fn lower_call(&mut self, func: &Value, args: &Vec<Value>) -> Immediate {
-        let mut args = args.iter().map(|arg| self.lower_immediate(arg)).collect_vec();
+        let args = args.iter().map(|arg| self.lower_immediate(arg)).collect_vec();
+
+        self.lower_call_with(func, args)
+    }
 
+    /// Calls a function with arguments that are already lowered
+    fn lower_call_with(&mut self, func: &Value, mut args: Vec<Immediate>) -> Immediate {
         let TyKind::Func(_, return_ty) = &func.ty.kind else {
             panic!();
         };
@@ -101,7 +169,7 @@ impl HirLowerer<'_> {
 
         match &func.kind {
             ValueKind::StaticFunc(static_func) => {
-                let static_func = self.func_map[static_func];
+                let static_func = self.func_instance(static_func);
 
                 Immediate {
                     kind: Box::new(ImmediateKind::Call(static_func, args)),
@@ -109,8 +177,56 @@ impl HirLowerer<'_> {
                     span: func.span,
                 }
             }
+            ValueKind::InstanceFunc(receiver, requirement) if self.requirement_of(requirement.id).is_some() => {
+                let receiver_ty = receiver.ty.substitute(&self.generics);
+                let mutating = self.is_mutating(requirement.id);
+
+                // A value of a protocol type calls the method from its witness table
+                if let (TyKind::Protocol(_), true) = (&receiver_ty.kind, mutating) {
+                    let index = self.requirement_index(requirement.id);
+                    let receiver = self.lower_place(receiver);
+
+                    return Immediate {
+                        kind: Box::new(ImmediateKind::WitnessMutating(receiver, index, args)),
+                        ty: return_ty,
+                        span: func.span,
+                    };
+                }
+
+                if let TyKind::Protocol(_) = receiver_ty.kind {
+                    let func_value = self.lower_witness(receiver, requirement.id, &func.ty);
+
+                    return Immediate {
+                        kind: Box::new(ImmediateKind::CallValue(func_value, args)),
+                        ty: return_ty,
+                        span: func.span,
+                    };
+                }
+
+                let witness = self.witness_of(&receiver_ty, requirement.id);
+                let witness = self.func_instance(&witness);
+
+                if mutating {
+                    return self.lower_mutating_call(witness, receiver, args, return_ty, func.span);
+                }
+
+                let receiver = self.lower_immediate(receiver);
+
+                args.insert(0, receiver);
+
+                Immediate {
+                    kind: Box::new(ImmediateKind::Call(witness, args)),
+                    ty: return_ty,
+                    span: func.span,
+                }
+            }
             ValueKind::InstanceFunc(receiver, instance_func) => {
-                let instance_func = self.func_map[instance_func];
+                let mutating = self.is_mutating(instance_func.id);
+                let instance_func = self.func_instance(instance_func);
+
+                if mutating {
+                    return self.lower_mutating_call(instance_func, receiver, args, return_ty, func.span);
+                }
 
                 let receiver = self.lower_immediate(&receiver);
 
@@ -122,22 +238,48 @@ impl HirLowerer<'_> {
                     span: func.span,
                 }
             }
-            ValueKind::InitFor(struct_def) => {
-                let struct_def = self.struct_map[struct_def];
-                let ty = MirTy::new(MirTyKind::Struct(struct_def));
+            ValueKind::InitFor(_) => Self::build_instance(args, return_ty, func.span),
+            ValueKind::InitCase(case) => {
+                let index = self.hir.get(*case).index;
+
+                Immediate {
+                    kind: Box::new(ImmediateKind::Enum(index, args)),
+                    ty: return_ty,
+                    span: func.span,
+                }
+            }
+            ValueKind::BuiltinFunc(builtin_name) => self.lower_builtin(builtin_name, args, return_ty, func.span),
+
+            // Any other value is a function value
+            _ => {
+                let func_value = self.lower_immediate(func);
 
                 Immediate {
-                    kind: Box::new(ImmediateKind::Struct(args)),
-                    ty,
+                    kind: Box::new(ImmediateKind::CallValue(func_value, args)),
+                    ty: return_ty,
                     span: func.span,
                 }
             }
-            ValueKind::BuiltinFunc(builtin_name) => self.lower_builtin(builtin_name, args, func.span),
+        }
+    }
+
+    /// Calls a `mutating` method, which stores
+    /// the changes it makes back into its receiver
+    fn lower_mutating_call(&mut self, func: MirId<MirFunc>, receiver: &Value, args: Vec<Immediate>, ty: MirTy, span: Span) -> Immediate {
+        let receiver = self.lower_place(receiver);
 
-            _ => unreachable!(),
+        Immediate {
+            kind: Box::new(ImmediateKind::CallMutating(func, receiver, args)),
+            ty,
+            span,
         }
     }
 
+    /// Whether a method can change the value it's called on
+    fn is_mutating(&self, func: Id<HirFunc>) -> bool {
+        self.hir.try_get::<Callable>(func).is_some_and(|callable| callable.mutating)
+    }
+
     fn lower_assign(&mut self, place: &Value, value: &Value) -> Immediate {
         let place = self.lower_place(place);
         let value = self.lower_immediate(value);
@@ -151,13 +293,12 @@ impl HirLowerer<'_> {
         let imm = self.lower_immediate(value);
 
         if let ImmediateKind::Void = imm.kind.as_ref() {
-            self.mir.build_terminator(Terminator::returns_void());
+            self.build_return(None);
         }
         else {
-            self.mir.build_terminator(Terminator::returns(imm));
+            self.build_return(Some(imm));
         }
 
-
         Immediate::void()
     }
 }
\ No newline at end of file
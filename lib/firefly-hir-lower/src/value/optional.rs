@@ -0,0 +1,183 @@
+use firefly_hir::{stmt::Local, value::{OptionalChainValue, Value, ValueKind}, Id};
+use firefly_mir::{
+    code::{BasicBlockId, PanicKind, Terminator},
+    ty::{IntegerKind, Ty as MirTy, TyKind as MirTyKind},
+    value::{ConstantValue, Immediate, ImmediateKind, Place, PlaceKind, UnaryIntrinsic},
+};
+use firefly_span::Span;
+
+use crate::HirLowerer;
+
+/// The tag of an optional that holds a value
+const SOME_TAG: usize = 1;
+
+/// The tag of an optional that is `nil`
+const NIL_TAG: usize = 0;
+
+impl HirLowerer<'_> {
+    /// Lowers `nil`, or a value wrapped in an optional
+    pub(super) fn lower_optional(&mut self, inner: Option<&Value>, ty: MirTy, span: Span) -> Immediate {
+        let (tag, fields) = match inner {
+            Some(inner) => (SOME_TAG, vec![self.lower_immediate(inner)]),
+            None => (NIL_TAG, vec![]),
+        };
+
+        Immediate {
+            kind: Box::new(ImmediateKind::Enum(tag, fields)),
+            ty,
+            span,
+        }
+    }
+
+    /// Lowers `optional!`, which stops the
+    /// program if the optional is `nil`
+    pub(super) fn lower_unwrap(&mut self, optional: &Value, span: Span) -> Immediate {
+        let some_block = self.mir.append_basic_block();
+        let nil_block = self.mir.append_basic_block();
+
+        let optional = self.build_optional_branch(optional, some_block, nil_block);
+
+        self.mir.select_basic_block(nil_block);
+        self.mir.build_terminator(Terminator::panic(PanicKind::UnwrapNil, span));
+
+        self.mir.select_basic_block(some_block);
+
+        let mut inner = Self::optional_payload(optional).move_out();
+        inner.span = span;
+
+        inner
+    }
+
+    /// Lowers `optional ?? default`, only evaluating
+    /// the default if the optional is `nil`
+    pub(super) fn lower_coalesce(&mut self, optional: &Value, default: &Value, ty: MirTy, span: Span) -> Immediate {
+        // With an optional default, the optional itself is the result
+        let unwraps = optional.ty != default.ty;
+
+        let result = self.mir.build_local(ty).place_unspanned();
+
+        let some_block = self.mir.append_basic_block();
+        let nil_block = self.mir.append_basic_block();
+        let after_block = self.mir.append_basic_block();
+
+        let optional = self.build_optional_branch(optional, some_block, nil_block);
+
+        self.mir.select_basic_block(some_block);
+        let some = if unwraps { Self::optional_payload(optional) } else { optional };
+        self.mir.build_assign(result.clone(), some.move_out());
+        self.mir.build_terminator(Terminator::branch(after_block));
+
+        self.mir.select_basic_block(nil_block);
+        let default = self.lower_immediate(default);
+        if !self.mir.is_terminated() {
+            self.mir.build_assign(result.clone(), default);
+            self.mir.build_terminator(Terminator::branch(after_block));
+        }
+
+        self.mir.select_basic_block(after_block);
+
+        let mut result = result.move_out();
+        result.span = span;
+
+        result
+    }
+
+    /// Lowers a value chained to an optional, like `person?.name`,
+    /// which is only evaluated if the optional isn't `nil`
+    pub(super) fn lower_optional_chain(&mut self, chain: &OptionalChainValue, ty: MirTy, span: Span) -> Immediate {
+        let result = self.mir.build_local(ty.clone()).place_unspanned();
+
+        let some_block = self.mir.append_basic_block();
+        let nil_block = self.mir.append_basic_block();
+        let after_block = self.mir.append_basic_block();
+
+        let optional = self.build_optional_branch(&chain.optional, some_block, nil_block);
+
+        self.mir.select_basic_block(some_block);
+        self.bind_optional_payload(optional, chain.binding);
+        let value = self.lower_immediate(&chain.value);
+        if !self.mir.is_terminated() {
+            self.mir.build_assign(result.clone(), value);
+
+            // Store the changed binding back into the optional
+            if chain.writes_back {
+                let binding_ty = self.hir.get(chain.binding).ty.clone();
+                let binding = self.lower_place(&Value::new(ValueKind::Local(chain.binding), binding_ty, chain.optional.span));
+                let optional = self.lower_place(&chain.optional);
+
+                let some = Immediate {
+                    kind: Box::new(ImmediateKind::Enum(SOME_TAG, vec![binding.move_out()])),
+                    ty: optional.ty.clone(),
+                    span: chain.optional.span,
+                };
+
+                self.mir.build_assign(optional, some);
+            }
+
+            self.mir.build_terminator(Terminator::branch(after_block));
+        }
+
+        self.mir.select_basic_block(nil_block);
+        let nil = self.lower_optional(None, ty, span);
+        self.mir.build_assign(result.clone(), nil);
+        self.mir.build_terminator(Terminator::branch(after_block));
+
+        self.mir.select_basic_block(after_block);
+
+        let mut result = result.move_out();
+        result.span = span;
+
+        result
+    }
+
+    /// Stores an optional in a new local, and branches to `some_block`
+    /// if it holds a value, or to `nil_block` if it doesn't
+    ///
+    /// Returns the place the optional is stored in
+    pub(super) fn build_optional_branch(
+        &mut self,
+        optional: &Value,
+        some_block: BasicBlockId,
+        nil_block: BasicBlockId,
+    ) -> Place {
+        let optional = self.lower_immediate(optional);
+        let optional_place = self.mir.build_local(optional.ty.clone()).place_unspanned();
+        self.mir.build_assign(optional_place.clone(), optional);
+
+        let tag = Immediate {
+            kind: Box::new(ImmediateKind::Unary(UnaryIntrinsic::Discriminant, optional_place.clone().move_out())),
+            ty: MirTy::new(MirTyKind::Integer(IntegerKind::I64)),
+            span: optional_place.span,
+        };
+
+        let targets = vec![(ConstantValue::Integer(SOME_TAG as i128), some_block)];
+        self.mir.build_terminator(Terminator::switch(tag, targets, nil_block));
+
+        optional_place
+    }
+
+    /// Binds a local to the value inside an optional
+    /// that is known to hold a value
+    pub(super) fn bind_optional_payload(&mut self, optional: Place, binding: Id<Local>) {
+        let payload = Self::optional_payload(optional);
+
+        let local = self.mir.build_local(payload.ty.clone());
+        self.local_map.insert(binding, local.id());
+
+        let local_place = local.place_unspanned();
+        self.mir.build_assign(local_place, payload.move_out());
+    }
+
+    /// The place of the value inside an optional
+    fn optional_payload(optional: Place) -> Place {
+        let MirTyKind::Optional(inner_ty) = optional.ty.kind() else {
+            panic!("internal compiler error: unwrapped a value that isn't an optional");
+        };
+
+        Place {
+            ty: inner_ty.clone(),
+            span: optional.span,
+            kind: Box::new(PlaceKind::Field(optional, 0)),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use firefly_mir::{
+    ty::{Ty as MirTy, TyKind as MirTyKind},
+    value::{Immediate, ImmediateKind, Place, PlaceKind},
+};
+use firefly_span::Span;
+
+use crate::HirLowerer;
+
+impl HirLowerer<'_> {
+    /// Builds an instance of a struct from its fields. Instances
+    /// of classes are moved onto the heap, and are referenced by
+    /// every value they're assigned to
+    pub(crate) fn build_instance(fields: Vec<Immediate>, ty: MirTy, span: Span) -> Immediate {
+        let MirTyKind::Reference(struct_ty) = ty.kind() else {
+            return Immediate {
+                kind: Box::new(ImmediateKind::Struct(fields)),
+                ty,
+                span,
+            };
+        };
+
+        let instance = Immediate {
+            kind: Box::new(ImmediateKind::Struct(fields)),
+            ty: struct_ty.clone(),
+            span,
+        };
+
+        Immediate {
+            kind: Box::new(ImmediateKind::Alloc(instance)),
+            ty,
+            span,
+        }
+    }
+
+    /// Follows a reference to the instance it points to,
+    /// so its fields are changed for every value sharing it
+    pub(crate) fn deref(place: Place) -> Place {
+        let MirTyKind::Reference(struct_ty) = place.ty.kind() else {
+            return place;
+        };
+
+        Place {
+            ty: struct_ty.clone(),
+            span: place.span,
+            kind: Box::new(PlaceKind::Deref(place)),
+        }
+    }
+}
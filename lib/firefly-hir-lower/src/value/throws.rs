@@ -0,0 +1,189 @@
+use firefly_hir::{
+    ty::Ty as HirTy,
+    value::{DoValue, TryValue, Value},
+};
+use firefly_mir::{
+    code::{BasicBlockId, PanicKind, Terminator},
+    ty::{IntegerKind, Ty as MirTy, TyKind as MirTyKind},
+    value::{ConstantValue, Immediate, ImmediateKind, Place, PlaceKind, UnaryIntrinsic},
+};
+use firefly_span::Span;
+
+use crate::HirLowerer;
+
+/// The tag of a result that holds a returned value
+const VALUE_TAG: usize = 0;
+
+/// The tag of a result that holds a thrown error
+const ERROR_TAG: usize = 1;
+
+/// Where errors thrown inside a `do` block go
+pub struct CatchMarker {
+    /// The block that handles the error
+    pub block: BasicBlockId,
+
+    /// Where the error is stored
+    pub error: Place,
+}
+
+impl HirLowerer<'_> {
+    /// The type a function returns, which holds either
+    /// its return value or an error if it can throw
+    pub(crate) fn lower_return_ty(&mut self, return_ty: &HirTy, throws: Option<&HirTy>) -> MirTy {
+        let return_ty = self.lower_ty(return_ty);
+
+        let Some(throws) = throws else {
+            return return_ty;
+        };
+
+        let error_ty = self.lower_ty(throws);
+
+        MirTy::new(MirTyKind::Result(return_ty, error_ty))
+    }
+
+    /// Returns from the current function, marking the
+    /// value as returned if the function can throw
+    pub(crate) fn build_return(&mut self, value: Option<Immediate>) {
+        // An init returns the struct it built
+        let value = match &self.init_self {
+            Some(init_self) => Some(init_self.clone().move_out()),
+            None => value,
+        };
+
+        let Some(result_ty) = self.result_ty.clone() else {
+            let terminator = match value {
+                Some(value) => Terminator::returns(value),
+                None => Terminator::returns_void(),
+            };
+
+            self.mir.build_terminator(terminator);
+            return;
+        };
+
+        let span = value.as_ref().map(|value| value.span).unwrap_or_default();
+        let result = Immediate {
+            kind: Box::new(ImmediateKind::Enum(VALUE_TAG, value.into_iter().collect())),
+            ty: result_ty,
+            span,
+        };
+
+        self.mir.build_terminator(Terminator::returns(result));
+    }
+
+    /// Lowers `throw error`
+    pub(super) fn lower_throw(&mut self, error: &Value) -> Immediate {
+        let error = self.lower_immediate(error);
+
+        if !self.mir.is_terminated() {
+            self.build_throw(error);
+        }
+
+        Immediate::void()
+    }
+
+    /// Lowers a call to a function that can throw, passing the error
+    /// on if it throws, or stopping the program if it's called with `try!`
+    pub(super) fn lower_try(&mut self, try_value: &TryValue, span: Span) -> Immediate {
+        let mut result = self.lower_immediate(&try_value.call);
+        let value_ty = result.ty.clone();
+        let error_ty = self.lower_ty(&try_value.error_ty);
+        result.ty = MirTy::new(MirTyKind::Result(value_ty.clone(), error_ty.clone()));
+
+        let result_place = self.mir.build_local(result.ty.clone()).place_unspanned();
+        self.mir.build_assign(result_place.clone(), result);
+
+        let value_block = self.mir.append_basic_block();
+        let error_block = self.mir.append_basic_block();
+
+        let tag = Immediate {
+            kind: Box::new(ImmediateKind::Unary(UnaryIntrinsic::Discriminant, result_place.clone().move_out())),
+            ty: MirTy::new(MirTyKind::Integer(IntegerKind::I64)),
+            span,
+        };
+
+        let targets = vec![(ConstantValue::Integer(VALUE_TAG as i128), value_block)];
+        self.mir.build_terminator(Terminator::switch(tag, targets, error_block));
+
+        self.mir.select_basic_block(error_block);
+        if try_value.force {
+            self.mir.build_terminator(Terminator::panic(PanicKind::ForceTry, span));
+        } else {
+            let error = Self::result_payload(result_place.clone(), error_ty, span);
+            self.build_throw(error.move_out());
+        }
+
+        self.mir.select_basic_block(value_block);
+
+        if let MirTyKind::Void = value_ty.kind() {
+            return Immediate::void();
+        }
+
+        Self::result_payload(result_place, value_ty, span).move_out()
+    }
+
+    /// Lowers a `do` statement, which runs the `catch`
+    /// block if anything in its body throws
+    pub(super) fn lower_do(&mut self, do_value: &DoValue) -> Immediate {
+        let catch_block = self.mir.append_basic_block();
+        let end_block = self.mir.append_basic_block();
+
+        let error_ty = self.hir.get(do_value.binding).ty.clone();
+        let error_ty = self.lower_ty(&error_ty);
+        let error = self.mir.build_local(error_ty);
+        self.local_map.insert(do_value.binding, error.id());
+        let error = error.place_unspanned();
+
+        self.catch_stack.push(CatchMarker { block: catch_block, error });
+        self.lower_code_block(do_value.body);
+        self.catch_stack.pop();
+
+        if !self.mir.is_terminated() {
+            self.mir.build_terminator(Terminator::branch(end_block));
+        }
+
+        self.mir.select_basic_block(catch_block);
+        self.lower_code_block(do_value.catch);
+
+        if !self.mir.is_terminated() {
+            self.mir.build_terminator(Terminator::branch(end_block));
+        }
+
+        self.mir.select_basic_block(end_block);
+
+        Immediate::void()
+    }
+
+    /// Passes an error to the innermost `catch`,
+    /// or returns it from the current function
+    fn build_throw(&mut self, error: Immediate) {
+        if let Some(catch) = self.catch_stack.last() {
+            let (block, error_place) = (catch.block, catch.error.clone());
+
+            self.mir.build_assign(error_place, error);
+            self.mir.build_terminator(Terminator::branch(block));
+            return;
+        }
+
+        let Some(result_ty) = self.result_ty.clone() else {
+            panic!("internal compiler error: error thrown in a function that can't throw");
+        };
+
+        let span = error.span;
+        let result = Immediate {
+            kind: Box::new(ImmediateKind::Enum(ERROR_TAG, vec![error])),
+            ty: result_ty,
+            span,
+        };
+
+        self.mir.build_terminator(Terminator::returns(result));
+    }
+
+    /// The place of the value or error inside a result
+    fn result_payload(result: Place, ty: MirTy, span: Span) -> Place {
+        Place {
+            ty,
+            span,
+            kind: Box::new(PlaceKind::Field(result, 0)),
+        }
+    }
+}
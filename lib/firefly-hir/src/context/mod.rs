@@ -6,7 +6,7 @@ use std::{collections::HashMap, fmt::Debug};
 use display::DisplayContext;
 
 use crate::{
-    component::{BaseComponent, Component}, entity::Id, func::{Callable, Func}, items::{mangle::MangledName, Constant, Field, Global, Module, SourceFile, StructDef, TypeAlias}, resolve::{Import, InstanceMemberTable, Namespace, Passthrough, StaticMemberTable, Symbol, SymbolTable, VisibleWithin}, stmt::{CodeBlock, Local}, ty::{HasType, Ty}, util::Root, value::{HasSelf, HasValue, HasValueIn}, AccessComponent, ComponentConstructor, ComputedComponent, Entity, EntityKind
+    component::{BaseComponent, Component}, entity::Id, func::{Callable, Func, Initializer}, generics::{GenericParameter, GenericParameterList}, items::{conformance::{AssociatedType, Conformance}, mangle::MangledName, Constant, EnumCase, EnumDef, Field, Global, Module, ProtocolDef, SourceFile, StructDef, TypeAlias}, resolve::{Import, InstanceMemberTable, Namespace, Passthrough, StaticMemberTable, Symbol, SymbolTable, VisibleWithin}, stmt::{CodeBlock, Local}, ty::{HasType, Ty}, util::Root, value::{HasSelf, HasValue, HasValueIn, Mutable, Parameter}, AccessComponent, ComponentConstructor, ComputedComponent, Entity, EntityKind
 };
 
 // The HirContext keeps track of every entity in the system,
@@ -23,8 +23,12 @@ ecs! {
         globals: Global,
         modules: Module,
         structs: StructDef,
+        enums: EnumDef,
+        enum_cases: EnumCase,
+        protocols: ProtocolDef,
         typealiases: TypeAlias,
         source_files: SourceFile,
+        generic_parameters: GenericParameter,
 
         // Code items
         types: Ty,
@@ -36,7 +40,13 @@ ecs! {
         has_values: HasValue,
         has_values_in: HasValueIn,
         has_self: HasSelf,
+        mutables: Mutable,
+        parameters: Parameter,
         callables: Callable,
+        initializers: Initializer,
+        generic_parameter_lists: GenericParameterList,
+        conformances: Conformance,
+        associated_types: AssociatedType,
         locals: Local,
         mangled_names: MangledName,
 
@@ -158,6 +168,20 @@ impl HirContext {
             .expect("internal compiler error: component doesn't exist");
     }
 
+    /// Returns a mutable reference to the component for this entity
+    pub fn get_mut<C: Component>(&mut self, id: Id<C>) -> &mut C
+    where
+        Self: AccessComponent<C>,
+    {
+        let entity_id = id.as_base();
+
+        let component_map = <Self as AccessComponent<C>>::get_components_mut(self);
+
+        component_map
+            .get_mut(&entity_id)
+            .expect("internal compiler error: component doesn't exist")
+    }
+
     /// Returns the specified component for an entity
     /// if it exists.
     ///
@@ -12,7 +12,11 @@ pub enum EntityKind {
     File,
     StructDef,
     Field,
+    EnumDef,
+    EnumCase,
+    ProtocolDef,
     Func,
+    GenericParameter,
     TypeAlias,
     Import,
     Global,
@@ -0,0 +1,82 @@
+use itertools::Itertools;
+
+use crate::{
+    items::{EnumDef, StructDef},
+    resolve::Symbol,
+    ty::{IntegerKind, Ty, TyKind},
+    HirContext, Id,
+};
+
+use super::{Callable, Func};
+
+/// The function that runs when the program starts
+///
+/// The entry point is the only function named `main` that
+/// isn't a member of a struct. Each of its parameters takes
+/// one of the program's arguments, and if it returns an `int`,
+/// that becomes the exit code of the program
+pub struct EntryPoint {
+    /// Every function that could be the entry point
+    pub candidates: Vec<Id<Func>>,
+}
+
+impl EntryPoint {
+    pub const NAME: &'static str = "main";
+
+    pub fn new(context: &HirContext) -> Self {
+        let candidates = context
+            .entities_with::<Func>()
+            .filter(|func| Self::is_candidate(*func, context))
+            .collect_vec();
+
+        EntryPoint { candidates }
+    }
+
+    /// Gets the entry point, if there is exactly one
+    /// and it has a signature the program can start with
+    pub fn func(&self, context: &HirContext) -> Option<Id<Func>> {
+        let [func] = self.candidates[..] else {
+            return None;
+        };
+
+        let Callable { params, return_ty, .. } = context.try_get(func)?;
+
+        if !params.iter().all(|param| Self::is_valid_param(&param.ty))
+            || !Self::is_valid_return(return_ty)
+        {
+            return None;
+        }
+
+        Some(func)
+    }
+
+    /// Program arguments are passed as strings
+    pub fn is_valid_param(ty: &Ty) -> bool {
+        matches!(ty.kind, TyKind::String | TyKind::Never)
+    }
+
+    /// The entry point returns nothing, or an exit code
+    pub fn is_valid_return(ty: &Ty) -> bool {
+        matches!(
+            ty.kind,
+            TyKind::Unit | TyKind::Integer(IntegerKind::INT) | TyKind::Never
+        )
+    }
+
+    fn is_candidate(func: Id<Func>, context: &HirContext) -> bool {
+        let Some(Symbol { name, .. }) = context.try_get(func) else {
+            return false;
+        };
+
+        if name.name != Self::NAME {
+            return false;
+        }
+
+        // Static functions of structs and enums named main don't count
+        let parent = context.parent(func.as_base());
+
+        parent.is_none_or(|parent| {
+            context.cast_id::<StructDef>(parent).is_none() && context.cast_id::<EnumDef>(parent).is_none()
+        })
+    }
+}
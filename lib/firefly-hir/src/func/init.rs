@@ -0,0 +1,17 @@
+use crate::{items::StructDef, stmt::Local, Id};
+
+/// Marks a function as an `init` declared in a struct
+///
+/// An init builds the struct in its `self` local,
+/// and returns it when it is finished
+#[derive(Debug, Clone)]
+pub struct Initializer {
+    pub struct_def: Id<StructDef>,
+    pub local: Id<Local>,
+}
+
+impl Initializer {
+    pub const NAME: &'static str = "init";
+}
+
+component!(initializers: Initializer);
@@ -3,8 +3,12 @@
 use crate::entity::{EntityKind, Id};
 
 mod signature;
+mod entry;
+mod init;
 
 pub use signature::*;
+pub use entry::*;
+pub use init::*;
 
 /// Represents a function in the HIR.
 #[derive(Debug, Clone)]
@@ -13,7 +13,11 @@ pub struct Callable {
     pub labels: Vec<Option<Name>>,
     pub params: Vec<FuncParam>,
     pub return_ty: Ty,
+    /// The type of error the function can throw
+    pub throws: Option<Ty>,
     pub receiver: Option<Ty>,
+    /// Whether the function can change its receiver
+    pub mutating: bool,
 }
 
 #[derive(Debug, Clone)]
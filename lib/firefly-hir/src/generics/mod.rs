@@ -1,2 +1,7 @@
+mod parameter;
 mod polymorph;
-mod parameter;
\ No newline at end of file
+mod substitution;
+
+pub use parameter::*;
+pub use polymorph::*;
+pub use substitution::*;
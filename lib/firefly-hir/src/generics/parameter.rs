@@ -1,25 +1,51 @@
-use crate::entity::{Entity, Id};
+use crate::{
+    entity::{EntityKind, Id},
+    items::ProtocolDef,
+    Entity, HirContext,
+};
 
 ///
 /// A generic parameter represents a type parameter
 /// in a generic type or function.
-/// 
-/// The generic parameter has a list of constraints
-/// that must be satisfied by the type that is passed
-/// to the generic.
-/// 
-/// The generic parameter can be used like a type.
-/// 
+///
+/// The generic parameter can be used like a type, and
+/// is replaced by a concrete type in each monomorph
+/// of the item that declares it.
+///
+/// Its bounds are the protocols the types that
+/// replace it have to conform to.
+///
+#[derive(Debug, Clone)]
 pub struct GenericParameter {
-    id: Id<GenericParameter>,
+    pub id: Id<GenericParameter>,
+    pub bounds: Vec<Id<ProtocolDef>>,
 }
 
-impl Entity for GenericParameter {
-    fn id(&self) -> Id<GenericParameter> {
-        self.id
-    }
-}
+component!(base(EntityKind::GenericParameter) generic_parameters: GenericParameter);
 
+/// The generic parameters declared by a function or struct
+#[derive(Debug, Clone)]
 pub struct GenericParameterList {
-    parameters: Vec<Id<GenericParameter>>
-}
\ No newline at end of file
+    pub parameters: Vec<Id<GenericParameter>>,
+}
+
+component!(generic_parameter_lists: GenericParameterList);
+
+impl GenericParameterList {
+    /// Gets every generic parameter that can be used in an entity,
+    /// starting with the ones declared by the items it is inside of
+    pub fn in_scope(entity: Id<Entity>, context: &HirContext) -> Vec<Id<GenericParameter>> {
+        let mut lists = Vec::new();
+        let mut current = Some(entity);
+
+        while let Some(id) = current {
+            if let Some(list) = context.try_get::<GenericParameterList>(id) {
+                lists.push(list.parameters.clone());
+            }
+
+            current = context.parent(id);
+        }
+
+        lists.into_iter().rev().flatten().collect()
+    }
+}
@@ -1,17 +1,77 @@
-use crate::entity::{Entity, Id};
+use crate::{func::Func, items::StructDef, ty::Ty, BaseComponent, HirContext, Id};
 
+use super::{GenericParameter, GenericParameterList, Substitution};
 
 /// Represents a polymorphic object
-/// 
-/// Anything that is polymorphic must implement this trait.
-/// 
-/// Polymorphic objects can be monomorphized, and track
-/// their monomorphizations.
-pub trait Polymorphic: Entity {
+///
+/// Functions and structs can declare generic parameters, and
+/// are monomorphized for each list of generic arguments they
+/// are used with.
+pub trait Polymorphic: BaseComponent {}
 
+impl Polymorphic for Func {}
+impl Polymorphic for StructDef {}
+
+/// A use of a polymorphic object, along with the arguments for
+/// each generic parameter it has in scope
+///
+/// A monomorph is incomplete until an argument is known for
+/// every parameter. The missing arguments are inferred
+/// when the object is called.
+#[derive(Debug, Clone)]
+pub struct Monomorph<T: Polymorphic> {
+    pub id: Id<T>,
+    pub generics: Vec<Ty>,
 }
 
-pub struct Monomorph<T> where T: Polymorphic
-{
-    id: Id<T>,
-}
\ No newline at end of file
+impl<T: Polymorphic> Monomorph<T> {
+    pub fn new(id: Id<T>) -> Self {
+        Self { id, generics: Vec::new() }
+    }
+
+    pub fn with_generics(id: Id<T>, generics: Vec<Ty>) -> Self {
+        Self { id, generics }
+    }
+
+    /// Whether there is an argument for every generic parameter
+    pub fn is_complete(&self, context: &HirContext) -> bool {
+        GenericParameterList::in_scope(self.id.as_base(), context).len() == self.generics.len()
+    }
+
+    /// The generic parameters that don't have an argument yet
+    pub fn missing(&self, context: &HirContext) -> Vec<Id<GenericParameter>> {
+        let parameters = GenericParameterList::in_scope(self.id.as_base(), context);
+
+        parameters.into_iter().skip(self.generics.len()).collect()
+    }
+
+    /// Replaces the generic parameters in scope with the arguments
+    pub fn substitution(&self, context: &HirContext) -> Substitution {
+        let parameters = GenericParameterList::in_scope(self.id.as_base(), context);
+
+        Substitution::new(&parameters, &self.generics)
+    }
+
+    /// Replaces the generic parameters used in the arguments
+    pub fn substitute(&self, substitution: &Substitution) -> Self {
+        let generics = self
+            .generics
+            .iter()
+            .map(|generic| generic.substitute(substitution))
+            .collect();
+
+        Self { id: self.id, generics }
+    }
+
+    /// Replaces the associated types used in the arguments
+    /// with the types chosen for them
+    pub fn normalize(&self, context: &HirContext) -> Self {
+        let generics = self
+            .generics
+            .iter()
+            .map(|generic| generic.normalize(context))
+            .collect();
+
+        Self { id: self.id, generics }
+    }
+}
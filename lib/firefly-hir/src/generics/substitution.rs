@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+
+use crate::{
+    ty::{Ty, TyKind},
+    Id,
+};
+
+use super::GenericParameter;
+
+/// Maps generic parameters to the types that replace them
+#[derive(Debug, Clone, Default)]
+pub struct Substitution {
+    types: HashMap<Id<GenericParameter>, Ty>,
+}
+
+impl Substitution {
+    pub fn new(parameters: &[Id<GenericParameter>], generics: &[Ty]) -> Self {
+        let types = parameters.iter().cloned().zip(generics.iter().cloned()).collect();
+
+        Self { types }
+    }
+
+    pub fn get(&self, parameter: Id<GenericParameter>) -> Option<&Ty> {
+        self.types.get(&parameter)
+    }
+
+    pub fn insert(&mut self, parameter: Id<GenericParameter>, ty: Ty) {
+        self.types.insert(parameter, ty);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Infers the arguments for generic parameters by matching
+    /// a type that uses them against the type of a value
+    ///
+    /// Returns whether the types can match
+    pub fn unify(&mut self, expected: &Ty, found: &Ty, parameters: &[Id<GenericParameter>]) -> bool {
+        if let TyKind::Never = found.kind {
+            return true;
+        }
+
+        match (&expected.kind, &found.kind) {
+            (TyKind::Generic(parameter), _) if parameters.contains(parameter) => {
+                match self.types.get(parameter) {
+                    Some(bound) => bound == found,
+                    None => {
+                        self.types.insert(*parameter, found.clone());
+                        true
+                    }
+                }
+            }
+
+            (TyKind::Tuple(expected), TyKind::Tuple(found)) if expected.len() == found.len() => expected
+                .iter()
+                .zip(found)
+                .all(|(expected, found)| self.unify(expected, found, parameters)),
+
+            (TyKind::Func(expected_params, expected_return), TyKind::Func(found_params, found_return))
+                if expected_params.len() == found_params.len() =>
+            {
+                expected_params
+                    .iter()
+                    .zip(found_params)
+                    .all(|(expected, found)| self.unify(expected, found, parameters))
+                    && self.unify(expected_return, found_return, parameters)
+            }
+
+            (TyKind::Optional(expected), TyKind::Optional(found)) => self.unify(expected, found, parameters),
+
+            // A value is wrapped when it's passed as an optional
+            (TyKind::Optional(expected), _) => self.unify(expected, found, parameters),
+
+            (TyKind::StructDef(expected_id, expected), TyKind::StructDef(found_id, found))
+                if expected_id == found_id && expected.len() == found.len() =>
+            {
+                expected
+                    .iter()
+                    .zip(found)
+                    .all(|(expected, found)| self.unify(expected, found, parameters))
+            }
+
+            _ => expected == found,
+        }
+    }
+}
+
+impl Ty {
+    /// Replaces the generic parameters used in a type
+    pub fn substitute(&self, substitution: &Substitution) -> Ty {
+        if substitution.is_empty() {
+            return self.clone();
+        }
+
+        let kind = match &self.kind {
+            TyKind::Generic(parameter) => match substitution.get(*parameter) {
+                Some(ty) => ty.kind.clone(),
+                None => self.kind.clone(),
+            },
+
+            TyKind::Tuple(items) => {
+                TyKind::Tuple(items.iter().map(|item| item.substitute(substitution)).collect())
+            }
+            TyKind::Func(params, return_ty) => TyKind::Func(
+                params.iter().map(|param| param.substitute(substitution)).collect(),
+                Box::new(return_ty.substitute(substitution)),
+            ),
+            TyKind::StructDef(id, generics) => TyKind::StructDef(
+                *id,
+                generics.iter().map(|generic| generic.substitute(substitution)).collect(),
+            ),
+            TyKind::Optional(inner) => TyKind::Optional(Box::new(inner.substitute(substitution))),
+            TyKind::Associated(base, associated) => {
+                TyKind::Associated(Box::new(base.substitute(substitution)), *associated)
+            }
+
+            kind => kind.clone(),
+        };
+
+        // An alias still names the type if nothing was substituted
+        if kind == self.kind {
+            return self.clone();
+        }
+
+        Ty::new(kind, self.span)
+    }
+}
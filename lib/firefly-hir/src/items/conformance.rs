@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use crate::{
+    func::{Callable, Func},
+    generics::{GenericParameter, GenericParameterList, Substitution},
+    ty::{Ty, TyKind},
+    HirContext, Id,
+};
+
+use super::ProtocolDef;
+
+/// The protocols a struct conforms to, the method it
+/// implements each of their requirements with, and the
+/// type it chose for each of their associated types
+#[derive(Debug, Clone, Default)]
+pub struct Conformance {
+    pub protocols: Vec<Id<ProtocolDef>>,
+    pub witnesses: HashMap<Id<Func>, Id<Func>>,
+    pub associated: HashMap<Id<GenericParameter>, Ty>,
+}
+
+component!(conformances: Conformance);
+
+/// An associated type declared by a protocol. Inside the
+/// protocol, it is used like a generic parameter
+#[derive(Debug, Clone)]
+pub struct AssociatedType {
+    pub id: Id<GenericParameter>,
+
+    /// The type chosen by conforming types that don't choose one
+    pub default: Option<Ty>,
+}
+
+component!(associated_types: AssociatedType);
+
+impl ProtocolDef {
+    /// Gets the methods a type has to implement to
+    /// conform to the protocol, in the order they're declared
+    pub fn requirements(protocol: Id<ProtocolDef>, context: &HirContext) -> Vec<Id<Func>> {
+        context
+            .children(protocol.as_base())
+            .iter()
+            .filter_map(|child| context.cast_id::<Func>(*child))
+            .collect()
+    }
+
+    /// Gets the associated types a conforming
+    /// type has to choose, in the order they're declared
+    pub fn associated_types(protocol: Id<ProtocolDef>, context: &HirContext) -> Vec<Id<GenericParameter>> {
+        context
+            .children(protocol.as_base())
+            .iter()
+            .filter(|child| context.has::<AssociatedType>(**child))
+            .filter_map(|child| context.cast_id::<GenericParameter>(*child))
+            .collect()
+    }
+}
+
+impl Callable {
+    /// Checks whether a method can implement a requirement,
+    /// which needs the same labels, parameter types, return
+    /// type, and thrown error type. Only a `mutating`
+    /// requirement can be implemented by a `mutating` method
+    pub fn implements(&self, requirement: &Callable) -> bool {
+        let labels_match = self.labels.len() == requirement.labels.len()
+            && self
+                .labels
+                .iter()
+                .zip(&requirement.labels)
+                .all(|(label, required)| label.as_ref().map(|l| &l.name) == required.as_ref().map(|l| &l.name));
+
+        let params_match = self.params.len() == requirement.params.len()
+            && self
+                .params
+                .iter()
+                .zip(&requirement.params)
+                .all(|(param, required)| param.ty == required.ty);
+
+        labels_match
+            && params_match
+            && self.return_ty == requirement.return_ty
+            && self.throws == requirement.throws
+            && (!self.mutating || requirement.mutating)
+    }
+
+    /// Replaces the generic parameters used in the signature
+    pub fn substitute(&self, substitution: &Substitution) -> Callable {
+        let mut callable = self.clone();
+
+        for param in &mut callable.params {
+            param.ty = param.ty.substitute(substitution);
+        }
+        callable.return_ty = callable.return_ty.substitute(substitution);
+        callable.throws = callable.throws.map(|throws| throws.substitute(substitution));
+
+        callable
+    }
+}
+
+impl Substitution {
+    /// Infers generic parameters used in a requirement from
+    /// the signature of the method implementing it
+    ///
+    /// Returns whether the signatures can match
+    pub fn unify_callable(&mut self, requirement: &Callable, method: &Callable, parameters: &[Id<GenericParameter>]) -> bool {
+        let params_match = requirement.params.len() == method.params.len()
+            && requirement
+                .params
+                .iter()
+                .zip(&method.params)
+                .all(|(required, param)| self.unify(&required.ty, &param.ty, parameters));
+
+        let throws_match = match (&requirement.throws, &method.throws) {
+            (Some(required), Some(throws)) => self.unify(required, throws, parameters),
+            _ => true,
+        };
+
+        params_match && throws_match && self.unify(&requirement.return_ty, &method.return_ty, parameters)
+    }
+}
+
+impl Ty {
+    /// Replaces the associated types of concrete types with
+    /// the types they chose when conforming to the protocol
+    pub fn normalize(&self, context: &HirContext) -> Ty {
+        let kind = match &self.kind {
+            TyKind::Associated(base, associated) => {
+                let base = base.normalize(context);
+
+                let TyKind::StructDef(struct_def, generics) = &base.kind else {
+                    return Ty::new(TyKind::Associated(Box::new(base), *associated), self.span);
+                };
+
+                let chosen = context
+                    .try_get::<Conformance>(*struct_def)
+                    .and_then(|conformance| conformance.associated.get(associated));
+
+                let Some(chosen) = chosen else {
+                    return Ty::new(TyKind::Associated(Box::new(base.clone()), *associated), self.span);
+                };
+
+                // The chosen type can use the struct's generic parameters
+                let parameters = GenericParameterList::in_scope(struct_def.as_base(), context);
+                let chosen = chosen.substitute(&Substitution::new(&parameters, generics)).normalize(context);
+
+                return Ty { span: self.span, ..chosen };
+            }
+
+            TyKind::Tuple(items) => TyKind::Tuple(items.iter().map(|item| item.normalize(context)).collect()),
+            TyKind::Func(params, return_ty) => TyKind::Func(
+                params.iter().map(|param| param.normalize(context)).collect(),
+                Box::new(return_ty.normalize(context)),
+            ),
+            TyKind::StructDef(id, generics) => {
+                TyKind::StructDef(*id, generics.iter().map(|generic| generic.normalize(context)).collect())
+            }
+            TyKind::Optional(inner) => TyKind::Optional(Box::new(inner.normalize(context))),
+
+            _ => return self.clone(),
+        };
+
+        // An alias still names the type if nothing was replaced
+        if kind == self.kind {
+            return self.clone();
+        }
+
+        Ty::new(kind, self.span)
+    }
+}
+
+impl Conformance {
+    /// Checks whether values of a type can be
+    /// used as values of a protocol
+    pub fn conforms_to(ty: &Ty, protocol: Id<ProtocolDef>, context: &HirContext) -> bool {
+        match &ty.kind {
+            TyKind::StructDef(struct_def, _) => context
+                .try_get::<Conformance>(*struct_def)
+                .is_some_and(|conformance| conformance.protocols.contains(&protocol)),
+
+            TyKind::Generic(parameter) => context
+                .try_get::<GenericParameter>(*parameter)
+                .is_some_and(|parameter| parameter.bounds.contains(&protocol)),
+
+            _ => false,
+        }
+    }
+}
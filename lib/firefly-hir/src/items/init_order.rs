@@ -0,0 +1,257 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::{
+    func::{Func, Initializer},
+    stmt::{CodeBlock, StmtKind},
+    value::{ElseValue, IfValue, MatchValue, Value, ValueKind},
+    HirContext, Id,
+};
+
+use super::{Field, Global, StructDef};
+
+/// The order that globals are initialized in
+/// when the program starts
+///
+/// Each global is initialized after the globals its
+/// default value reads, including any globals read
+/// by the functions it calls
+pub struct GlobalInitOrder {
+    pub order: Vec<Id<Global>>,
+
+    /// Groups of globals whose default values depend on each other
+    pub cycles: Vec<Vec<Id<Global>>>,
+}
+
+enum VisitState {
+    Visiting,
+    Done,
+}
+
+impl GlobalInitOrder {
+    pub fn new(context: &HirContext) -> Self {
+        let globals = context.entities_with::<Global>().collect_vec();
+
+        let dependencies = globals
+            .iter()
+            .map(|global| (*global, Self::dependencies(*global, context)))
+            .collect::<HashMap<_, _>>();
+
+        let mut init_order = GlobalInitOrder {
+            order: Vec::new(),
+            cycles: Vec::new(),
+        };
+
+        let mut states = HashMap::new();
+        let mut stack = Vec::new();
+
+        for global in globals {
+            init_order.visit(global, &dependencies, &mut states, &mut stack);
+        }
+
+        init_order
+    }
+
+    /// Visits a global after the globals it depends on
+    fn visit(
+        &mut self,
+        global: Id<Global>,
+        dependencies: &HashMap<Id<Global>, Vec<Id<Global>>>,
+        states: &mut HashMap<Id<Global>, VisitState>,
+        stack: &mut Vec<Id<Global>>,
+    ) {
+        match states.get(&global) {
+            Some(VisitState::Done) => return,
+            Some(VisitState::Visiting) => {
+                // Every global on the stack after this
+                // one depends on the one before it
+                let start = stack
+                    .iter()
+                    .position(|item| *item == global)
+                    .expect("internal compiler error: global isn't being visited");
+
+                self.cycles.push(stack[start..].to_vec());
+                return;
+            }
+            None => {}
+        }
+
+        states.insert(global, VisitState::Visiting);
+        stack.push(global);
+
+        for dependency in dependencies.get(&global).into_iter().flatten() {
+            self.visit(*dependency, dependencies, states, stack);
+        }
+
+        stack.pop();
+        states.insert(global, VisitState::Done);
+        self.order.push(global);
+    }
+
+    /// Finds the globals that are read when
+    /// evaluating the default value of a global
+    fn dependencies(global: Id<Global>, context: &HirContext) -> Vec<Id<Global>> {
+        let mut collector = DependencyCollector {
+            context,
+            globals: Vec::new(),
+            visited_funcs: HashSet::new(),
+        };
+
+        collector.value(&context.get(global).default_value);
+
+        collector.globals
+    }
+}
+
+struct DependencyCollector<'a> {
+    context: &'a HirContext,
+    globals: Vec<Id<Global>>,
+    visited_funcs: HashSet<Id<Func>>,
+}
+
+impl DependencyCollector<'_> {
+    fn value(&mut self, value: &Value) {
+        match &value.kind {
+            ValueKind::Global(global) => {
+                if !self.globals.contains(global) {
+                    self.globals.push(*global);
+                }
+            }
+
+            ValueKind::StaticFunc(func) => self.func(func.id),
+            ValueKind::InstanceFunc(receiver, func) => {
+                self.value(receiver);
+                self.func(func.id);
+            }
+
+            ValueKind::Tuple(items) => items.iter().for_each(|item| self.value(item)),
+            ValueKind::TupleMember(parent, _) => self.value(parent),
+            ValueKind::FieldOf(parent, _) => self.value(parent),
+            ValueKind::FieldDefault(_, field) => self.field_default(*field),
+            ValueKind::Return(value) => self.value(value),
+            ValueKind::Throw(value) => self.value(value),
+            ValueKind::Try(try_value) => self.value(&try_value.call),
+            ValueKind::Do(do_value) => {
+                self.code_block(do_value.body);
+                self.code_block(do_value.catch);
+            }
+            ValueKind::Existential(value) => self.value(value),
+            ValueKind::Wrap(value) | ValueKind::Unwrap(value) => self.value(value),
+            ValueKind::Coalesce(optional, default) => {
+                self.value(optional);
+                self.value(default);
+            }
+            ValueKind::OptionalChain(chain) => {
+                self.value(&chain.optional);
+                self.value(&chain.value);
+            }
+            ValueKind::Assign(place, value) => {
+                self.value(place);
+                self.value(value);
+            }
+            ValueKind::Invoke(function, args) => {
+                self.value(function);
+                args.iter().for_each(|arg| self.value(arg));
+            }
+
+            ValueKind::If(if_value) => self.if_value(if_value),
+            ValueKind::While(while_value) => {
+                self.value(&while_value.condition);
+                self.code_block(while_value.body);
+            }
+            ValueKind::For(for_value) => {
+                self.value(&for_value.start);
+                self.value(&for_value.end);
+                self.code_block(for_value.body);
+            }
+            ValueKind::ForEach(for_each) => {
+                self.value(&for_each.sequence);
+                self.value(&for_each.next);
+                self.code_block(for_each.body);
+            }
+            ValueKind::Loop(loop_value) => self.code_block(loop_value.body),
+            ValueKind::Repeat(repeat_value) => {
+                self.code_block(repeat_value.body);
+                self.value(&repeat_value.condition);
+            }
+            ValueKind::Match(match_value) => self.match_value(match_value),
+            ValueKind::Closure(closure) => self.code_block(closure.body),
+            ValueKind::Break(_, Some(value)) => self.value(value),
+
+            ValueKind::Unit
+            | ValueKind::Nil
+            | ValueKind::Literal(_)
+            | ValueKind::InitFor(_)
+            | ValueKind::InitCase(_)
+            | ValueKind::BuiltinFunc(_)
+            | ValueKind::Break(_, None)
+            | ValueKind::Continue(_)
+            | ValueKind::Local(_) => {}
+        }
+    }
+
+    fn if_value(&mut self, if_value: &IfValue) {
+        self.value(&if_value.condition);
+        self.code_block(if_value.positive);
+
+        match &if_value.negative {
+            Some(ElseValue::Else(negative)) => self.code_block(*negative),
+            Some(ElseValue::ElseIf(negative)) => self.if_value(negative),
+            None => {}
+        }
+    }
+
+    fn match_value(&mut self, match_value: &MatchValue) {
+        self.value(&match_value.scrutinee);
+
+        for arm in &match_value.arms {
+            self.code_block(arm.body);
+        }
+    }
+
+    fn code_block(&mut self, code_block: Id<CodeBlock>) {
+        let code_block = self.context.get(code_block);
+
+        for stmt in &code_block.stmts {
+            match &stmt.kind {
+                StmtKind::Value(value) => self.value(value),
+                StmtKind::Bind(_, _, _, value) => self.value(value),
+            }
+        }
+
+        if let Some(yields) = &code_block.yields {
+            self.value(yields);
+        }
+    }
+
+    /// Functions read globals when they're called
+    fn func(&mut self, func: Id<Func>) {
+        if !self.visited_funcs.insert(func) {
+            return;
+        }
+
+        let body = self
+            .context
+            .children(func.as_base())
+            .iter()
+            .find_map(|child| self.context.cast_id::<CodeBlock>(*child));
+
+        if let Some(body) = body {
+            self.code_block(body);
+        }
+
+        // An init evaluates the defaults of the struct's fields
+        if let Some(initializer) = self.context.try_get::<Initializer>(func) {
+            for field in StructDef::fields(initializer.struct_def, self.context) {
+                self.field_default(field);
+            }
+        }
+    }
+
+    fn field_default(&mut self, field: Id<Field>) {
+        if let Some(default) = &self.context.get(field).default {
+            self.value(default);
+        }
+    }
+}
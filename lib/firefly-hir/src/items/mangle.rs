@@ -1,6 +1,11 @@
-use firefly_mangle::SymbolName;
+use firefly_mangle::{SymbolName, TyName};
 
-use crate::{resolve::Symbol, ComputedComponent, Entity, EntityKind, HirContext, Id};
+use crate::{
+    generics::{Monomorph, Polymorphic},
+    resolve::Symbol,
+    ty::{Ty, TyKind},
+    ComputedComponent, Entity, EntityKind, HirContext, Id,
+};
 
 #[derive(Debug, Clone)]
 pub struct MangledName {
@@ -31,6 +36,8 @@ impl ComputedComponent for MangledName {
         
         let symbol = match entity.kind {
             EntityKind::StructDef => SymbolName::Struct(path),
+            EntityKind::EnumDef => SymbolName::Enum(path),
+            EntityKind::ProtocolDef => SymbolName::Protocol(path),
             EntityKind::Func => SymbolName::Func(path),
             EntityKind::Global => SymbolName::Var(path),
 
@@ -39,4 +46,81 @@ impl ComputedComponent for MangledName {
 
         return Some(MangledName { symbol })
     }
-}
\ No newline at end of file
+}
+
+impl MangledName {
+    /// Gets the name of a monomorph, which includes the
+    /// generic arguments if there are any
+    pub fn of_monomorph<T: Polymorphic>(monomorph: &Monomorph<T>, context: &mut HirContext) -> SymbolName {
+        let MangledName { symbol } = context
+            .try_get_computed(monomorph.id)
+            .cloned()
+            .expect("internal compiler error: item doesn't have a mangled name");
+
+        if monomorph.generics.is_empty() {
+            return symbol;
+        }
+
+        let generics = monomorph
+            .generics
+            .iter()
+            .map(|generic| Self::ty_name(generic, context))
+            .collect();
+
+        SymbolName::Instance(Box::new(symbol), generics)
+    }
+
+    fn ty_name(ty: &Ty, context: &mut HirContext) -> TyName {
+        match &ty.kind {
+            TyKind::Unit => TyName::Unit,
+            TyKind::Tuple(items) => {
+                TyName::Tuple(items.iter().map(|item| Self::ty_name(item, context)).collect())
+            }
+            TyKind::Func(params, return_ty) => TyName::Func(
+                params.iter().map(|param| Self::ty_name(param, context)).collect(),
+                Box::new(Self::ty_name(return_ty, context)),
+            ),
+            TyKind::Optional(inner) => TyName::Optional(Box::new(Self::ty_name(inner, context))),
+            TyKind::StructDef(id, generics) => {
+                let monomorph = Monomorph::with_generics(*id, generics.clone());
+
+                TyName::Symbol(Self::of_monomorph(&monomorph, context))
+            }
+            TyKind::EnumDef(id) => {
+                let MangledName { symbol } = context
+                    .try_get_computed(*id)
+                    .cloned()
+                    .expect("internal compiler error: enum doesn't have a mangled name");
+
+                TyName::Symbol(symbol)
+            }
+            TyKind::Protocol(id) => {
+                let MangledName { symbol } = context
+                    .try_get_computed(*id)
+                    .cloned()
+                    .expect("internal compiler error: protocol doesn't have a mangled name");
+
+                TyName::Symbol(symbol)
+            }
+
+            TyKind::Integer(kind) => TyName::Builtin(kind.name().to_string()),
+            TyKind::String => TyName::Builtin("string".to_string()),
+            TyKind::Bool => TyName::Builtin("bool".to_string()),
+            TyKind::Float => TyName::Builtin("float".to_string()),
+            TyKind::Never => TyName::Builtin("never".to_string()),
+
+            TyKind::Generic(_) => {
+                panic!("internal compiler error: generic parameter wasn't substituted")
+            }
+            TyKind::Associated(..) => {
+                let normalized = ty.normalize(context);
+
+                if let TyKind::Associated(..) = normalized.kind {
+                    panic!("internal compiler error: associated type wasn't chosen")
+                }
+
+                Self::ty_name(&normalized, context)
+            }
+        }
+    }
+}
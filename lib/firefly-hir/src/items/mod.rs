@@ -1,22 +1,115 @@
 pub mod mangle;
+pub mod init_order;
+pub mod conformance;
 
-use crate::{ty::Ty, value::Value, EntityKind, Id};
+use crate::{resolve::Symbol, ty::Ty, value::Value, EntityKind, HirContext, Id, Name};
 
 #[derive(Clone, Debug)]
 pub struct StructDef {
     pub id: Id<StructDef>,
+
+    /// Whether the struct was declared with `class`, so
+    /// its instances are shared instead of copied
+    pub is_class: bool,
 }
 
 component!(base(EntityKind::StructDef) structs: StructDef);
 
+impl StructDef {
+    /// Gets the instance fields of a struct, in the order they're declared
+    pub fn fields(struct_def: Id<StructDef>, context: &HirContext) -> Vec<Id<Field>> {
+        context
+            .children(struct_def.as_base())
+            .iter()
+            .filter_map(|child| context.cast_id::<Field>(*child))
+            .collect()
+    }
+
+    /// Matches the labels of a call to the memberwise initializer
+    /// with the fields of the struct. A field without a default takes
+    /// the next unlabeled argument, and a field with a default takes
+    /// the next argument only if it's labeled with the field's name.
+    ///
+    /// Returns the index of the argument passed to each field, or
+    /// `None` for fields left to their default
+    pub fn memberwise_args(struct_def: Id<StructDef>, labels: &[Option<&str>], context: &HirContext) -> Option<Vec<Option<usize>>> {
+        let mut next = 0;
+        let mut args = Vec::new();
+
+        for field in Self::fields(struct_def, context) {
+            let label = labels.get(next).copied();
+
+            if !context.get(field).has_default {
+                let Some(None) = label else {
+                    return None;
+                };
+
+                args.push(Some(next));
+                next += 1;
+                continue;
+            }
+
+            let name = &context.try_get::<Symbol>(field)?.name.name;
+
+            match label {
+                Some(Some(label)) if label == name => {
+                    args.push(Some(next));
+                    next += 1;
+                }
+                _ => args.push(None),
+            }
+        }
+
+        if next != labels.len() {
+            return None;
+        }
+
+        Some(args)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Field {
     pub id: Id<Field>,
     pub ty: Ty,
+
+    /// The value the field starts with when
+    /// an `init` creates the struct
+    pub default: Option<Value>,
+
+    /// Whether the field was declared with a default,
+    /// which is known before the default is lowered
+    pub has_default: bool,
 }
 
 component!(base(EntityKind::Field) fields: Field);
 
+#[derive(Clone, Debug)]
+pub struct EnumDef {
+    pub id: Id<EnumDef>,
+}
+
+component!(base(EntityKind::EnumDef) enums: EnumDef);
+
+/// A protocol lists the methods a type has
+/// to implement to conform to it
+#[derive(Clone, Debug)]
+pub struct ProtocolDef {
+    pub id: Id<ProtocolDef>,
+}
+
+component!(base(EntityKind::ProtocolDef) protocols: ProtocolDef);
+
+/// A case of an enum, along with the values it carries
+#[derive(Clone, Debug)]
+pub struct EnumCase {
+    pub id: Id<EnumCase>,
+    pub index: usize,
+    pub fields: Vec<(Name, Ty)>,
+}
+
+component!(base(EntityKind::EnumCase) enum_cases: EnumCase);
+
 #[derive(Clone, Debug)]
 pub struct TypeAlias {
     pub id: Id<TypeAlias>,
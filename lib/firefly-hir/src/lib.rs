@@ -10,6 +10,7 @@ mod util;
 pub mod resolve;
 
 pub mod func;
+pub mod generics;
 pub mod items;
 pub mod path;
 pub mod ty;
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use itertools::Itertools;
 
-use crate::{ComputedComponent, Entity, HirContext, Id, ImportError};
+use crate::{ComputedComponent, Entity, HirContext, Id, ImportError, Visibility};
 
 use super::{Import, ImportRequest, Namespace, Symbol, SymbolCollection, VisibleWithin};
 
@@ -53,17 +53,35 @@ impl SymbolTable {
     /// If the symbol already exists, it will be shadowed and the old value
     /// will be restored when the current scope is popped.
     pub fn insert(&mut self, name: String, symbol: Id<Symbol>) {
-        let symbols = self.symbols.entry(name.clone()).or_default();
+        if let Some(scope) = self.scopes.last_mut() {
+            // Store the previous state before modification
+            if !scope.old_symbols.contains_key(&name) {
+                scope
+                    .old_symbols
+                    .insert(name.clone(), self.symbols.get(&name).cloned());
+            }
+        }
+
+        let symbols = self.symbols.entry(name).or_default();
         symbols.add(symbol);
+    }
 
+    /// Adds a symbol in the current scope, hiding every other
+    /// symbol with the same name until the scope is popped,
+    /// rather than overloading them
+    pub fn shadow(&mut self, name: String, symbol: Id<Symbol>) {
         if let Some(scope) = self.scopes.last_mut() {
-            // Store the previous state before modification
             if !scope.old_symbols.contains_key(&name) {
                 scope
                     .old_symbols
                     .insert(name.clone(), self.symbols.get(&name).cloned());
             }
         }
+
+        let mut symbols = SymbolCollection::default();
+        symbols.add(symbol);
+
+        self.symbols.insert(name, symbols);
     }
 
     /// Looks up a symbol by name in the current scope and all outer scopes.
@@ -110,7 +128,13 @@ impl ComputedComponent for SymbolTable {
             let symbol = context.get(symbol_id);
             let name = symbol.name.name.clone();
 
-            symbol_table.insert(name, symbol_id);
+            // Parameters hide members with the same name,
+            // rather than overloading them
+            if let Visibility::Local = symbol.visibility {
+                symbol_table.shadow(name, symbol_id);
+            } else {
+                symbol_table.insert(name, symbol_id);
+            }
         }
 
         // Go through imports and add them to the symbol table
@@ -185,7 +209,13 @@ impl SymbolTable {
             let symbol = context.get(symbol_id);
             let name = symbol.name.name.clone();
 
-            symbol_table.insert(name, symbol_id);
+            // Parameters hide members with the same name,
+            // rather than overloading them
+            if let Visibility::Local = symbol.visibility {
+                symbol_table.shadow(name, symbol_id);
+            } else {
+                symbol_table.insert(name, symbol_id);
+            }
         }
     }
 
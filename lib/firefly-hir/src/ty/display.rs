@@ -0,0 +1,89 @@
+use std::fmt::Display;
+
+use itertools::Itertools;
+
+use crate::{resolve::Symbol, HirContext};
+
+use super::{IntegerKind, Ty, TyKind};
+
+/// Displays a type the way it would be written in Firefly
+pub struct DisplayTy<'a> {
+    context: &'a HirContext,
+    ty: &'a Ty,
+}
+
+impl Ty {
+    pub fn display<'a>(&'a self, context: &'a HirContext) -> DisplayTy<'a> {
+        DisplayTy { context, ty: self }
+    }
+}
+
+impl Display for DisplayTy<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(symbol) = self.ty.alias.and_then(|alias| self.context.try_get::<Symbol>(alias)) {
+            return write!(f, "{}", symbol.name.name);
+        }
+
+        match &self.ty.kind {
+            TyKind::Unit => write!(f, "()"),
+            TyKind::Tuple(items) => write!(
+                f,
+                "({})",
+                items.iter().map(|item| item.display(self.context)).format(", ")
+            ),
+            TyKind::StructDef(id, generics) => {
+                match self.context.try_get::<Symbol>(*id) {
+                    Some(symbol) => write!(f, "{}", symbol.name.name)?,
+                    None => write!(f, "<struct>")?,
+                }
+
+                if generics.is_empty() {
+                    return Ok(());
+                }
+
+                write!(
+                    f,
+                    "[{}]",
+                    generics.iter().map(|generic| generic.display(self.context)).format(", ")
+                )
+            }
+            TyKind::EnumDef(id) => match self.context.try_get::<Symbol>(*id) {
+                Some(symbol) => write!(f, "{}", symbol.name.name),
+                None => write!(f, "<enum>"),
+            },
+            TyKind::Protocol(id) => match self.context.try_get::<Symbol>(*id) {
+                Some(symbol) => write!(f, "{}", symbol.name.name),
+                None => write!(f, "<protocol>"),
+            },
+            TyKind::Func(params, return_ty) => write!(
+                f,
+                "({}) -> {}",
+                params.iter().map(|param| param.display(self.context)).format(", "),
+                return_ty.display(self.context)
+            ),
+            TyKind::Optional(inner) => match &inner.kind {
+                // Function types are wrapped, so the `?` isn't
+                // read as part of the return type
+                TyKind::Func(..) if inner.alias.is_none() => write!(f, "({})?", inner.display(self.context)),
+                _ => write!(f, "{}?", inner.display(self.context)),
+            },
+
+            TyKind::Generic(id) => match self.context.try_get::<Symbol>(*id) {
+                Some(symbol) => write!(f, "{}", symbol.name.name),
+                None => write!(f, "<generic>"),
+            },
+            TyKind::Associated(base, id) => match self.context.try_get::<Symbol>(*id) {
+                Some(symbol) => write!(f, "{}.{}", base.display(self.context), symbol.name.name),
+                None => write!(f, "{}.<associated>", base.display(self.context)),
+            },
+
+            TyKind::Integer(IntegerKind::INT) => write!(f, "int"),
+            TyKind::Integer(kind) => write!(f, "{}", kind.name()),
+            TyKind::String => write!(f, "string"),
+            TyKind::Bool => write!(f, "bool"),
+            TyKind::Float => write!(f, "float"),
+
+            TyKind::Never => write!(f, "!"),
+        }
+    }
+}
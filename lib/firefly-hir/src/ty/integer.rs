@@ -0,0 +1,82 @@
+/// The width and signedness of an integer type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntegerKind {
+    I8,
+    I16,
+    I32,
+    I64,
+
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+impl IntegerKind {
+    pub const ALL: [IntegerKind; 8] = [
+        IntegerKind::I8,
+        IntegerKind::I16,
+        IntegerKind::I32,
+        IntegerKind::I64,
+        IntegerKind::U8,
+        IntegerKind::U16,
+        IntegerKind::U32,
+        IntegerKind::U64,
+    ];
+
+    /// The kind of `int`, which is an alias for `i64`
+    pub const INT: IntegerKind = IntegerKind::I64;
+
+    pub fn is_signed(&self) -> bool {
+        match self {
+            IntegerKind::I8 | IntegerKind::I16 | IntegerKind::I32 | IntegerKind::I64 => true,
+            IntegerKind::U8 | IntegerKind::U16 | IntegerKind::U32 | IntegerKind::U64 => false,
+        }
+    }
+
+    pub fn bits(&self) -> u32 {
+        match self {
+            IntegerKind::I8 | IntegerKind::U8 => 8,
+            IntegerKind::I16 | IntegerKind::U16 => 16,
+            IntegerKind::I32 | IntegerKind::U32 => 32,
+            IntegerKind::I64 | IntegerKind::U64 => 64,
+        }
+    }
+
+    /// The smallest value an integer of this kind can hold
+    pub fn min(&self) -> i128 {
+        if self.is_signed() {
+            -(1 << (self.bits() - 1))
+        } else {
+            0
+        }
+    }
+
+    /// The largest value an integer of this kind can hold
+    pub fn max(&self) -> i128 {
+        if self.is_signed() {
+            (1 << (self.bits() - 1)) - 1
+        } else {
+            (1 << self.bits()) - 1
+        }
+    }
+
+    /// Returns whether a value fits in an integer of this kind
+    pub fn contains(&self, value: i128) -> bool {
+        self.min() <= value && value <= self.max()
+    }
+
+    /// The name of the type in the lang module
+    pub fn name(&self) -> &'static str {
+        match self {
+            IntegerKind::I8 => "i8",
+            IntegerKind::I16 => "i16",
+            IntegerKind::I32 => "i32",
+            IntegerKind::I64 => "i64",
+            IntegerKind::U8 => "u8",
+            IntegerKind::U16 => "u16",
+            IntegerKind::U32 => "u32",
+            IntegerKind::U64 => "u64",
+        }
+    }
+}
@@ -1,10 +1,14 @@
 use firefly_span::Span;
 
-use crate::{entity::Id, items::StructDef, Entity, EntityKind};
+use crate::{entity::Id, generics::GenericParameter, items::{EnumDef, ProtocolDef, StructDef, TypeAlias}, Entity, EntityKind, HirContext};
 
+mod display;
 mod has_type;
+mod integer;
 
+pub use display::DisplayTy;
 pub use has_type::HasType;
+pub use integer::IntegerKind;
 
 /// Represents a type in the HIR.
 ///
@@ -12,14 +16,26 @@ pub use has_type::HasType;
 /// how the type is used in the source code.
 ///
 /// It does not contain any information about the actual type.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TyKind {
     Unit,
     Tuple(Vec<Ty>),
-    StructDef(Id<StructDef>),
+    StructDef(Id<StructDef>, Vec<Ty>),
+    EnumDef(Id<EnumDef>),
     Func(Vec<Ty>, Box<Ty>),
+    Generic(Id<GenericParameter>),
 
-    Integer,
+    /// An associated type of a protocol, chosen by the type
+    /// that conforms to it, like `T.Item`
+    Associated(Box<Ty>, Id<GenericParameter>),
+
+    /// A value of any type that conforms to a protocol
+    Protocol(Id<ProtocolDef>),
+
+    /// Either a value of the inner type, or `nil`
+    Optional(Box<Ty>),
+
+    Integer(IntegerKind),
     String,
     Bool,
     Float,
@@ -28,11 +44,16 @@ pub enum TyKind {
 }
 
 /// Represents a type in the HIR.
+///
+/// A type written with a type alias keeps the alias,
+/// so it can be displayed the way it was written, but
+/// is compared by its underlying kind.
 #[derive(Clone)]
 pub struct Ty {
     pub id: Id<Ty>,
     pub kind: TyKind,
     pub span: Span,
+    pub alias: Option<Id<TypeAlias>>,
 }
 
 impl Ty {
@@ -40,7 +61,8 @@ impl Ty {
         Ty {
             id: Default::default(),
             kind,
-            span
+            span,
+            alias: None,
         }
     }
 
@@ -49,20 +71,49 @@ impl Ty {
             id: Default::default(),
             kind,
             span: Default::default(),
+            alias: None,
+        }
+    }
+
+    /// Marks the type as written with a type alias
+    pub fn with_alias(self, alias: Id<TypeAlias>) -> Ty {
+        Ty {
+            alias: Some(alias),
+            ..self
         }
     }
 
     pub fn defined_by(&self) -> Option<Id<Entity>> {
         match self.kind {
-            TyKind::StructDef(id) => Some(id.as_base()),
+            TyKind::StructDef(id, _) => Some(id.as_base()),
+            TyKind::EnumDef(id) => Some(id.as_base()),
+            TyKind::Protocol(id) => Some(id.as_base()),
 
             _ => None
         }
     }
+
+    /// Whether the type is a class, whose
+    /// instances are shared by reference
+    pub fn is_class(&self, context: &HirContext) -> bool {
+        match self.kind {
+            TyKind::StructDef(id, _) => context.try_get::<StructDef>(id).is_some_and(|struct_def| struct_def.is_class),
+
+            _ => false
+        }
+    }
 }
 
 component!(base(EntityKind::Ty) types: Ty);
 
+/// Two types are equal if they are structurally the same,
+/// regardless of where they were written
+impl PartialEq for Ty {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind
+    }
+}
+
 impl std::fmt::Debug for Ty {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:?}", self.kind)
@@ -25,4 +25,18 @@ pub struct HasSelf {
     pub ty: Ty,
 }
 
-component!(has_self: HasSelf);
\ No newline at end of file
+component!(has_self: HasSelf);
+
+/// Marks a local, global, or field that can be assigned to,
+/// like one declared with `var`
+#[derive(Debug, Clone)]
+pub struct Mutable;
+
+component!(mutables: Mutable);
+
+/// Marks a local that holds a parameter of
+/// a function or closure
+#[derive(Debug, Clone)]
+pub struct Parameter;
+
+component!(parameters: Parameter);
\ No newline at end of file
@@ -1,11 +1,15 @@
 mod has_value;
+mod pattern;
+mod usefulness;
 
 use std::fmt::Debug;
 use firefly_span::Span;
 use crate::{
-    entity::Id, func::Func, items::{Field, Global, StructDef}, stmt::{CodeBlock, Local}, ty::Ty, Name
+    entity::Id, func::Func, generics::Monomorph, items::{EnumCase, Field, Global, StructDef}, stmt::{CodeBlock, Local}, ty::Ty, HirContext, Name
 };
 pub use has_value::*;
+pub use pattern::*;
+pub use usefulness::*;
 
 #[derive(Debug, Clone)]
 pub enum LiteralValue {
@@ -15,9 +19,41 @@ pub enum LiteralValue {
     Boolean(bool),
 }
 
+impl LiteralValue {
+    /// Returns the value of an integer literal, which
+    /// can be written in binary, octal, decimal, or hex
+    pub fn integer_value(&self) -> Option<i128> {
+        let LiteralValue::Integer(literal) = self else {
+            return None;
+        };
+
+        let (is_negative, literal) = match literal.strip_prefix('-') {
+            Some(literal) => (true, literal),
+            None => (false, literal.as_str()),
+        };
+
+        let (radix, digits) = match literal.get(..2) {
+            Some("0b" | "0B") => (2, &literal[2..]),
+            Some("0o" | "0O") => (8, &literal[2..]),
+            Some("0x" | "0X") => (16, &literal[2..]),
+            _ => (10, literal),
+        };
+
+        let value = i128::from_str_radix(digits, radix).ok()?;
+
+        Some(if is_negative { -value } else { value })
+    }
+}
+
+/// Runs the positive block if the condition is true
+///
+/// An `if let` has a binding instead, and runs the positive
+/// block with the inside of the optional condition bound to
+/// it, if the condition isn't `nil`
 #[derive(Debug, Clone)]
 pub struct IfValue {
     pub condition: Value,
+    pub binding: Option<Id<Local>>,
     pub positive: Id<CodeBlock>,
     pub negative: Option<ElseValue>
 }
@@ -35,6 +71,90 @@ pub struct WhileValue {
     pub body:      Id<CodeBlock>,
 }
 
+/// Runs its body until it is broken out of, and has
+/// the type of the values it is broken out with
+#[derive(Debug, Clone)]
+pub struct LoopValue {
+    pub label: Option<Name>,
+    pub body:  Id<CodeBlock>,
+}
+
+/// Runs its body, and then runs it again while the condition is true
+#[derive(Debug, Clone)]
+pub struct RepeatValue {
+    pub label:     Option<Name>,
+    pub body:      Id<CodeBlock>,
+    pub condition: Value,
+}
+
+/// Loops over each integer in a range, binding it to a local
+#[derive(Debug, Clone)]
+pub struct ForValue {
+    pub label:     Option<Name>,
+    pub binding:   Id<Local>,
+    pub start:     Value,
+    pub end:       Value,
+    pub inclusive: bool,
+    pub body:      Id<CodeBlock>,
+}
+
+/// Loops over the values an iterator gives from `next`,
+/// binding each to a local, until it gives `nil`
+#[derive(Debug, Clone)]
+pub struct ForEachValue {
+    pub label:    Option<Name>,
+    pub binding:  Id<Local>,
+    /// The local the iterator is moved into, so
+    /// `next` can change it
+    pub iterator: Id<Local>,
+    pub sequence: Value,
+    /// A call to `next` on the iterator local
+    pub next:     Value,
+    pub body:     Id<CodeBlock>,
+}
+
+/// Evaluates a value with the inside of an optional bound
+/// to a local, or is `nil` if the optional is `nil`
+#[derive(Debug, Clone)]
+pub struct OptionalChainValue {
+    pub optional:    Value,
+    pub binding:     Id<Local>,
+    pub value:       Value,
+    /// Whether the binding is stored back into the optional
+    /// afterwards, since the value can change it
+    pub writes_back: bool,
+}
+
+/// A call to a function that can throw an error
+#[derive(Debug, Clone)]
+pub struct TryValue {
+    pub call:     Value,
+    pub error_ty: Ty,
+    /// Stops the program if the function throws,
+    /// instead of passing the error on
+    pub force:    bool,
+}
+
+/// Runs its body, and runs the `catch` block with the
+/// error bound to a local if anything in the body throws
+#[derive(Debug, Clone)]
+pub struct DoValue {
+    pub body:    Id<CodeBlock>,
+    pub binding: Id<Local>,
+    pub catch:   Id<CodeBlock>,
+}
+
+/// An anonymous function
+///
+/// The values of the locals it captures are copied
+/// into the closure when it is created
+#[derive(Debug, Clone)]
+pub struct ClosureValue {
+    pub params:   Vec<Id<Local>>,
+    pub captures: Vec<Id<Local>>,
+    pub body:     Id<CodeBlock>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ValueKind {
     Unit,
@@ -43,20 +163,54 @@ pub enum ValueKind {
     TupleMember(Box<Value>, usize),
 
     FieldOf(Box<Value>, Id<Field>),
+    /// The default value of a field of a struct, passed for a
+    /// field left out of a call to the memberwise initializer
+    FieldDefault(Monomorph<StructDef>, Id<Field>),
 
     Assign(Box<Value>, Box<Value>),
 
-    StaticFunc(Id<Func>),
-    InstanceFunc(Box<Value>, Id<Func>),
-    InitFor(Id<StructDef>),
+    StaticFunc(Monomorph<Func>),
+    InstanceFunc(Box<Value>, Monomorph<Func>),
+    /// The memberwise initializer of a struct,
+    /// which only structs without inits have
+    InitFor(Monomorph<StructDef>),
+    InitCase(Id<EnumCase>),
     BuiltinFunc(&'static str),
+    Closure(Box<ClosureValue>),
+
+    /// A value used as a value of a protocol
+    /// type that its type conforms to
+    Existential(Box<Value>),
+
+    /// The absence of a value of an optional type
+    Nil,
+    /// A value used as a value of its optional type
+    Wrap(Box<Value>),
+    /// The value inside an optional, which stops
+    /// the program if the optional is `nil`
+    Unwrap(Box<Value>),
+    /// The value inside an optional, or the second
+    /// value if the optional is `nil`
+    Coalesce(Box<Value>, Box<Value>),
+    OptionalChain(Box<OptionalChainValue>),
 
     Return(Box<Value>),
-    Break(Id<CodeBlock>),
+    Break(Id<CodeBlock>, Option<Box<Value>>),
     Continue(Id<CodeBlock>),
 
+    /// Passes an error to the nearest `catch`,
+    /// or out of the current function
+    Throw(Box<Value>),
+    Try(Box<TryValue>),
+    Do(Box<DoValue>),
+
     If(Box<IfValue>),
     While(Box<WhileValue>),
+    For(Box<ForValue>),
+    ForEach(Box<ForEachValue>),
+    Loop(Box<LoopValue>),
+    Repeat(Box<RepeatValue>),
+    Match(Box<MatchValue>),
 
     Invoke(Box<Value>, Vec<Value>),
     Local(Id<Local>),
@@ -80,15 +234,32 @@ impl Value {
         }
     }
 
+    /// Creates a placeholder for a value that
+    /// couldn't be lowered
+    ///
+    /// It has the never type, so that it doesn't
+    /// cause any further type errors
+    pub fn error(span: Span) -> Value {
+        Value {
+            kind: ValueKind::Unit,
+            ty: Ty::new(crate::ty::TyKind::Never, span),
+            span
+        }
+    }
+
     /// Returns whether a value is mutable or not
     /// 
-    /// Local and global variables are mutable, as well
-    /// as fields of mutable values
-    pub fn is_mutable(&self) -> bool {
+    /// Local and global variables declared with `var` are
+    /// mutable, as well as `var` fields of mutable values.
+    /// The `var` fields of a class are always mutable, since
+    /// the instance is shared rather than part of the value
+    pub fn is_mutable(&self, context: &HirContext) -> bool {
         match &self.kind {
-            ValueKind::FieldOf(parent, _) => parent.is_mutable(),
-            ValueKind::Local(_) => true,
-            ValueKind::Global(_) => true,
+            ValueKind::FieldOf(parent, field) => {
+                context.has::<Mutable>(*field) && (parent.ty.is_class(context) || parent.is_mutable(context))
+            }
+            ValueKind::Local(local) => context.has::<Mutable>(*local),
+            ValueKind::Global(global) => context.has::<Mutable>(*global),
 
             _ => false
         }
@@ -0,0 +1,58 @@
+use firefly_span::Span;
+
+use crate::{items::EnumCase, stmt::{CodeBlock, Local}, ty::Ty, Id};
+
+use super::{LiteralValue, Value};
+
+#[derive(Debug, Clone)]
+pub struct MatchValue {
+    pub scrutinee: Value,
+    pub arms: Vec<MatchArm>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub body: Id<CodeBlock>,
+}
+
+#[derive(Debug, Clone)]
+pub enum PatternKind {
+    Wildcard,
+    Binding(Id<Local>),
+    Literal(LiteralValue),
+    Tuple(Vec<Pattern>),
+    Case(Id<EnumCase>, Vec<Pattern>),
+}
+
+/// A pattern that a value can be matched against,
+/// with the type of the values it matches
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    pub kind: PatternKind,
+    pub ty: Ty,
+    pub span: Span,
+}
+
+impl Pattern {
+    pub fn new(kind: PatternKind, ty: Ty, span: Span) -> Pattern {
+        Pattern { kind, ty, span }
+    }
+
+    /// Returns every local bound by the pattern
+    pub fn bindings(&self) -> Vec<Id<Local>> {
+        let mut bindings = Vec::new();
+        self.collect_bindings(&mut bindings);
+        bindings
+    }
+
+    fn collect_bindings(&self, bindings: &mut Vec<Id<Local>>) {
+        match &self.kind {
+            PatternKind::Binding(local) => bindings.push(*local),
+            PatternKind::Tuple(items) | PatternKind::Case(_, items) => {
+                items.iter().for_each(|item| item.collect_bindings(bindings))
+            }
+            PatternKind::Wildcard | PatternKind::Literal(_) => {}
+        }
+    }
+}
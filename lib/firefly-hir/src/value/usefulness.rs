@@ -0,0 +1,246 @@
+use itertools::Itertools;
+
+use crate::{
+    items::EnumCase,
+    resolve::Symbol,
+    ty::{Ty, TyKind},
+    HirContext, Id,
+};
+
+use super::{LiteralValue, MatchValue, Pattern, PatternKind};
+
+/// Checks the arms of a match against each other
+///
+/// An arm is useful if it matches a value that none
+/// of the arms before it match. Arms that aren't useful
+/// are unreachable, and if a wildcard after the last
+/// arm would be useful, the match isn't exhaustive
+pub struct MatchCheck {
+    /// The indices of arms that can never match
+    pub unreachable: Vec<usize>,
+
+    /// A value that none of the arms match, written as a pattern
+    pub missing: Option<String>,
+}
+
+#[derive(Clone, PartialEq)]
+enum Ctor {
+    Case(Id<EnumCase>),
+    Bool(bool),
+    Tuple,
+
+    /// Literals are compared by their value
+    Literal(String),
+}
+
+#[derive(Clone)]
+enum Pat {
+    Wildcard,
+    Ctor(Ctor, Vec<Pat>),
+}
+
+type Row = Vec<Pat>;
+
+impl MatchCheck {
+    pub fn new(match_value: &MatchValue, context: &HirContext) -> Self {
+        let checker = Checker { context };
+        let ty = &match_value.scrutinee.ty;
+
+        let mut rows: Vec<Row> = Vec::new();
+        let mut unreachable = Vec::new();
+
+        for (index, arm) in match_value.arms.iter().enumerate() {
+            let row = vec![Pat::from_pattern(&arm.pattern)];
+
+            if checker.useful(&rows, &row, std::slice::from_ref(ty)).is_none() {
+                unreachable.push(index);
+            }
+
+            rows.push(row);
+        }
+
+        let missing = checker
+            .useful(&rows, &[Pat::Wildcard], std::slice::from_ref(ty))
+            .and_then(|witness| witness.into_iter().next())
+            .map(|witness| checker.display(&witness));
+
+        MatchCheck { unreachable, missing }
+    }
+}
+
+impl Pat {
+    fn from_pattern(pattern: &Pattern) -> Pat {
+        match &pattern.kind {
+            PatternKind::Wildcard | PatternKind::Binding(_) => Pat::Wildcard,
+            PatternKind::Literal(LiteralValue::Boolean(b)) => Pat::Ctor(Ctor::Bool(*b), vec![]),
+            PatternKind::Literal(literal @ LiteralValue::Integer(_)) => {
+                let value = literal.integer_value().unwrap_or_default();
+
+                Pat::Ctor(Ctor::Literal(value.to_string()), vec![])
+            }
+            PatternKind::Literal(LiteralValue::Float(f)) => Pat::Ctor(Ctor::Literal(f.clone()), vec![]),
+            PatternKind::Literal(LiteralValue::String(s)) => Pat::Ctor(Ctor::Literal(format!("{s:?}")), vec![]),
+            PatternKind::Tuple(items) => Pat::Ctor(Ctor::Tuple, items.iter().map(Pat::from_pattern).collect()),
+            PatternKind::Case(case, items) => {
+                Pat::Ctor(Ctor::Case(*case), items.iter().map(Pat::from_pattern).collect())
+            }
+        }
+    }
+}
+
+struct Checker<'a> {
+    context: &'a HirContext,
+}
+
+impl Checker<'_> {
+    /// Finds values that `row` matches, but none of
+    /// `rows` do, returning one of them as a witness
+    fn useful(&self, rows: &[Row], row: &[Pat], tys: &[Ty]) -> Option<Row> {
+        let Some((head, rest)) = row.split_first() else {
+            return match rows.is_empty() {
+                true => Some(vec![]),
+                false => None,
+            };
+        };
+
+        let (ty, rest_tys) = (&tys[0], &tys[1..]);
+
+        if let Pat::Ctor(ctor, args) = head {
+            let specialized = self.specialize(rows, ctor, args.len());
+            let row = args.iter().chain(rest).cloned().collect_vec();
+            let tys = self.field_tys(ctor, ty).into_iter().chain(rest_tys.iter().cloned()).collect_vec();
+
+            let witness = self.useful(&specialized, &row, &tys)?;
+            return Some(Self::rebuild(ctor, witness, args.len()));
+        }
+
+        let used = rows
+            .iter()
+            .filter_map(|row| match &row[0] {
+                Pat::Ctor(ctor, _) => Some(ctor.clone()),
+                Pat::Wildcard => None,
+            })
+            .collect_vec();
+
+        let all = self.all_ctors(ty);
+
+        // If every constructor of the type appears in the first
+        // column, the wildcard is useful if any of them is
+        if let Some(all) = all.as_ref().filter(|all| all.iter().all(|ctor| used.contains(ctor))) {
+            for ctor in all {
+                let field_tys = self.field_tys(ctor, ty);
+                let arity = field_tys.len();
+
+                let specialized = self.specialize(rows, ctor, arity);
+                let row = std::iter::repeat_n(Pat::Wildcard, arity).chain(rest.iter().cloned()).collect_vec();
+                let tys = field_tys.into_iter().chain(rest_tys.iter().cloned()).collect_vec();
+
+                if let Some(witness) = self.useful(&specialized, &row, &tys) {
+                    return Some(Self::rebuild(ctor, witness, arity));
+                }
+            }
+
+            return None;
+        }
+
+        // Otherwise, only the rows starting with a wildcard
+        // can match the constructors that are missing
+        let default = rows
+            .iter()
+            .filter(|row| matches!(row[0], Pat::Wildcard))
+            .map(|row| row[1..].to_vec())
+            .collect_vec();
+
+        let witness = self.useful(&default, rest, rest_tys)?;
+
+        let missing = all
+            .and_then(|all| all.into_iter().find(|ctor| !used.contains(ctor)))
+            .filter(|_| !used.is_empty())
+            .map(|ctor| {
+                let arity = self.field_tys(&ctor, ty).len();
+
+                Pat::Ctor(ctor, vec![Pat::Wildcard; arity])
+            })
+            .unwrap_or(Pat::Wildcard);
+
+        Some(std::iter::once(missing).chain(witness).collect())
+    }
+
+    /// Keeps the rows that match a constructor, replacing
+    /// their first pattern with the patterns of its fields
+    fn specialize(&self, rows: &[Row], ctor: &Ctor, arity: usize) -> Vec<Row> {
+        rows.iter()
+            .filter_map(|row| {
+                let fields = match &row[0] {
+                    Pat::Wildcard => vec![Pat::Wildcard; arity],
+                    Pat::Ctor(row_ctor, fields) if row_ctor == ctor => fields.clone(),
+                    Pat::Ctor(..) => return None,
+                };
+
+                Some(fields.into_iter().chain(row[1..].iter().cloned()).collect())
+            })
+            .collect()
+    }
+
+    /// Puts the fields at the start of a witness back
+    /// together into the constructor they came from
+    fn rebuild(ctor: &Ctor, witness: Row, arity: usize) -> Row {
+        let mut witness = witness.into_iter();
+        let fields = witness.by_ref().take(arity).collect();
+
+        std::iter::once(Pat::Ctor(ctor.clone(), fields)).chain(witness).collect()
+    }
+
+    /// Lists every constructor of a type, or returns
+    /// `None` if there are too many to list
+    fn all_ctors(&self, ty: &Ty) -> Option<Vec<Ctor>> {
+        match &ty.kind {
+            TyKind::Bool => Some(vec![Ctor::Bool(true), Ctor::Bool(false)]),
+            TyKind::Unit | TyKind::Tuple(_) => Some(vec![Ctor::Tuple]),
+            TyKind::EnumDef(enum_def) => Some(
+                self.context
+                    .children(enum_def.as_base())
+                    .iter()
+                    .filter_map(|child| self.context.cast_id::<EnumCase>(*child))
+                    .sorted_by_key(|case| self.context.get(*case).index)
+                    .map(Ctor::Case)
+                    .collect(),
+            ),
+
+            _ => None,
+        }
+    }
+
+    fn field_tys(&self, ctor: &Ctor, ty: &Ty) -> Vec<Ty> {
+        match (ctor, &ty.kind) {
+            (Ctor::Tuple, TyKind::Tuple(items)) => items.clone(),
+            (Ctor::Case(case), _) => {
+                self.context.get(*case).fields.iter().map(|(_, ty)| ty.clone()).collect()
+            }
+
+            _ => vec![],
+        }
+    }
+
+    fn display(&self, pat: &Pat) -> String {
+        let fields = |fields: &[Pat]| fields.iter().map(|field| self.display(field)).join(", ");
+
+        match pat {
+            Pat::Wildcard => "_".to_string(),
+            Pat::Ctor(Ctor::Bool(b), _) => b.to_string(),
+            Pat::Ctor(Ctor::Literal(literal), _) => literal.clone(),
+            Pat::Ctor(Ctor::Tuple, items) => format!("({})", fields(items)),
+            Pat::Ctor(Ctor::Case(case), items) => {
+                let name = self
+                    .context
+                    .try_get::<Symbol>(*case)
+                    .map_or("<case>", |symbol| symbol.name.name.as_str());
+
+                match items.is_empty() {
+                    true => format!(".{name}"),
+                    false => format!(".{name}({})", fields(items)),
+                }
+            }
+        }
+    }
+}
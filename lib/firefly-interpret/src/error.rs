@@ -0,0 +1,52 @@
+use std::fmt::Display;
+
+use firefly_span::Span;
+
+/// An error that stops the program while it runs
+#[derive(Debug, Clone)]
+pub struct RuntimeError {
+    pub kind: RuntimeErrorKind,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone)]
+pub enum RuntimeErrorKind {
+    /// The result of an operation doesn't fit in its type.
+    /// Holds a description of the operation
+    Overflow(&'static str),
+
+    DivideByZero,
+    RemainderByZero,
+
+    UnwrapNil,
+    ForceTry,
+}
+
+impl RuntimeError {
+    pub fn new(kind: RuntimeErrorKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self.kind {
+            RuntimeErrorKind::Overflow(_) => "E0901",
+            RuntimeErrorKind::DivideByZero | RuntimeErrorKind::RemainderByZero => "E0902",
+            RuntimeErrorKind::UnwrapNil => "E0904",
+            RuntimeErrorKind::ForceTry => "E0905",
+        }
+    }
+}
+
+impl Display for RuntimeErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuntimeErrorKind::Overflow(operation) => write!(f, "attempt to {operation} with overflow"),
+            RuntimeErrorKind::DivideByZero => write!(f, "attempt to divide by zero"),
+            RuntimeErrorKind::RemainderByZero => {
+                write!(f, "attempt to calculate the remainder with a divisor of zero")
+            }
+            RuntimeErrorKind::UnwrapNil => write!(f, "attempt to unwrap a `nil` value"),
+            RuntimeErrorKind::ForceTry => write!(f, "a function called with `try!` threw an error"),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+use super::value::Value;
+
+/// Holds the values that references point to. Each value
+/// is counted by the references to it, and its slot is
+/// reused once the last of them has been dropped
+#[derive(Default)]
+pub struct Heap {
+    values: Vec<Option<Value>>,
+
+    /// Slots that can be reused
+    free: Vec<usize>,
+
+    /// Slots whose last reference has been dropped,
+    /// but whose values haven't been freed yet
+    released: Rc<RefCell<Vec<usize>>>,
+}
+
+/// A reference to a value on the heap.
+/// References compare by the slot they point to
+pub struct Handle {
+    pub index: usize,
+    released: Rc<RefCell<Vec<usize>>>,
+}
+
+impl Heap {
+    /// Moves a value onto the heap
+    pub fn alloc(&mut self, value: Value) -> Rc<Handle> {
+        self.collect();
+
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.values[index] = Some(value);
+                index
+            }
+            None => {
+                self.values.push(Some(value));
+                self.values.len() - 1
+            }
+        };
+
+        Rc::new(Handle { index, released: self.released.clone() })
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> &mut Value {
+        self.values
+            .get_mut(index)
+            .and_then(Option::as_mut)
+            .expect("internal error: reference to a freed value")
+    }
+
+    /// Counts the values that are still referenced
+    pub fn live_objects(&mut self) -> usize {
+        self.collect();
+
+        self.values.iter().filter(|value| value.is_some()).count()
+    }
+
+    /// Frees the values whose last reference was dropped
+    fn collect(&mut self) {
+        loop {
+            let Some(index) = self.released.borrow_mut().pop() else {
+                break;
+            };
+
+            // Freeing a value drops the references it holds,
+            // which can release more values
+            let value = self.values[index].take();
+            drop(value);
+
+            self.free.push(index);
+        }
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        self.released.borrow_mut().push(self.index);
+    }
+}
+
+impl PartialEq for Handle {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl PartialOrd for Handle {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.index.partial_cmp(&other.index)
+    }
+}
+
+impl Debug for Handle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ref {}", self.index)
+    }
+}
@@ -1,59 +1,102 @@
 use action::Action;
+use error::{RuntimeError, RuntimeErrorKind};
+use firefly_span::Span;
+use heap::Heap;
 use itertools::Itertools;
 use stack_frame::StackFrame;
 use value::{InnerValue, Value};
 
 use firefly_mir::{
-    code::{BasicBlockId, Function, InstructionKind, TerminatorKind},
+    code::{BasicBlockId, Function, InstructionKind, PanicKind, TerminatorKind},
+    ty::{IntegerKind, Ty, TyKind},
     value::{
         BinaryIntrinsic, BooleanBinaryOp, Comparison, ConstantValue, FloatBinaryOp, Immediate,
-        ImmediateKind, IntegerBinaryOp, Place, PlaceKind, StringBinaryOp, UnaryIntrinsic,
+        ImmediateKind, IntegerBinaryOp, Overflow, Place, PlaceKind, StringBinaryOp,
+        UnaryIntrinsic,
     },
     Id, MirContext,
 };
 
 mod action;
+pub mod error;
+mod heap;
 mod stack_frame;
 pub mod value;
 
+type Result<T> = std::result::Result<T, RuntimeError>;
+
 pub struct ExecutionEngine<'a> {
     context: &'a MirContext,
     globals: StackFrame,
+    heap: Heap,
 }
 
 impl<'a> ExecutionEngine<'a> {
     pub fn new(context: &'a MirContext) -> Self {
         let globals = StackFrame::new(context.globals().len(), Vec::new());
 
-        Self { context, globals }
+        Self { context, globals, heap: Heap::default() }
     }
 
-    pub fn execute(&mut self) {
-        if self.context.functions().is_empty() {
-            return;
+    /// Runs the program, passing each argument to a parameter of
+    /// the entry point, and returns what the entry point returns
+    pub fn execute(&mut self, args: Vec<String>) -> Result<Value> {
+        let Some(entry_point) = self.context.entry_point() else {
+            return Ok(Box::new(InnerValue::Void));
+        };
+
+        if let Some(initializer) = self.context.global_initializer() {
+            self.execute_function(initializer, Vec::new())?;
         }
-        self.execute_function(Id::new(0), Vec::new());
+
+        let args = args
+            .into_iter()
+            .map(|arg| Box::new(InnerValue::String(arg)))
+            .collect_vec();
+
+        self.execute_function(entry_point, args)
     }
 
-    fn execute_function(&mut self, id: Id<Function>, args: Vec<Value>) -> Value {
+    fn execute_function(&mut self, id: Id<Function>, args: Vec<Value>) -> Result<Value> {
         // create the stack frame
         let function = self.context.get_function(id);
+        let mut stack_frame = StackFrame::new(function.locals().len(), args);
+
+        self.run_function(id, &mut stack_frame)
+    }
 
+    /// Runs a method, giving back what it returns along
+    /// with the value it left in its receiver
+    fn execute_method(&mut self, id: Id<Function>, receiver: Value, mut args: Vec<Value>) -> Result<(Value, Value)> {
+        args.insert(0, receiver);
+
+        let function = self.context.get_function(id);
         let mut stack_frame = StackFrame::new(function.locals().len(), args);
+
+        let value = self.run_function(id, &mut stack_frame)?;
+
+        // The receiver is the first parameter
+        let receiver = std::mem::replace(stack_frame.get_value_mut(0), Value::new(InnerValue::Undefined));
+
+        Ok((value, receiver))
+    }
+
+    fn run_function(&mut self, id: Id<Function>, stack_frame: &mut StackFrame) -> Result<Value> {
+        let function = self.context.get_function(id);
         let mut current_bb = function.basic_blocks().first().cloned();
 
         while let Some(bb) = current_bb {
-            let action = self.execute_basic_block(bb, &mut stack_frame);
+            let action = self.execute_basic_block(bb, stack_frame)?;
 
             match action {
                 Action::Jump(bb) => {
                     current_bb = Some(bb);
                 }
                 Action::Return(value) => {
-                    return value;
+                    return Ok(value);
                 }
                 Action::ReturnVoid => {
-                    return Value::new(value::InnerValue::Undefined);
+                    return Ok(Value::new(value::InnerValue::Undefined));
                 }
             }
         }
@@ -61,30 +104,30 @@ impl<'a> ExecutionEngine<'a> {
         unreachable!();
     }
 
-    fn execute_basic_block(&mut self, bb: BasicBlockId, frame: &mut StackFrame) -> Action {
+    fn execute_basic_block(&mut self, bb: BasicBlockId, frame: &mut StackFrame) -> Result<Action> {
         let bb = self.context.get_basic_block(bb);
 
         for instruction in bb.instructions() {
             match &instruction.kind {
                 InstructionKind::Assign(place, imm) => {
-                    let imm = self.eval_immediate(imm, frame);
+                    let imm = self.eval_immediate(imm, frame)?;
                     let place = self.eval_place(place, frame);
 
                     *place = imm;
                 }
                 InstructionKind::Eval(imm) => {
-                    self.eval_immediate(imm, frame);
+                    self.eval_immediate(imm, frame)?;
                 }
             }
         }
 
         let Some(terminator) = bb.terminator() else {
-            return Action::ReturnVoid;
+            return Ok(Action::ReturnVoid);
         };
-        match &terminator.kind {
+        let action = match &terminator.kind {
             TerminatorKind::Branch(bb) => Action::Jump(*bb),
             TerminatorKind::BranchIf(cond, then, otherwise) => {
-                let value = self.eval_immediate(cond, frame);
+                let value = self.eval_immediate(cond, frame)?;
 
                 match value.as_ref() {
                     InnerValue::Boolean(true) => Action::Jump(*then),
@@ -92,19 +135,44 @@ impl<'a> ExecutionEngine<'a> {
                     _ => panic!(),
                 }
             }
+            TerminatorKind::Switch(value, targets, otherwise) => {
+                let value = self.eval_immediate(value, frame)?;
+
+                let target = targets
+                    .iter()
+                    .find(|(constant, _)| value.equals_constant(constant))
+                    .map_or(*otherwise, |(_, target)| *target);
+
+                Action::Jump(target)
+            }
+            TerminatorKind::Unreachable => {
+                panic!("internal compiler error: reached an unreachable block")
+            }
+            TerminatorKind::Panic(kind, span) => {
+                let kind = match kind {
+                    PanicKind::UnwrapNil => RuntimeErrorKind::UnwrapNil,
+                    PanicKind::ForceTry => RuntimeErrorKind::ForceTry,
+                };
+
+                return Err(RuntimeError::new(kind, *span));
+            }
             TerminatorKind::Return(value) => {
-                let value = self.eval_immediate(value, frame);
+                let value = self.eval_immediate(value, frame)?;
 
                 Action::Return(value)
             }
             TerminatorKind::ReturnVoid => Action::ReturnVoid,
-        }
+        };
+
+        Ok(action)
     }
 
-    fn eval_immediate(&mut self, imm: &Immediate, frame: &mut StackFrame) -> Value {
+    fn eval_immediate(&mut self, imm: &Immediate, frame: &mut StackFrame) -> Result<Value> {
         let inner = match imm.kind.as_ref() {
             ImmediateKind::Void => InnerValue::Void,
-            ImmediateKind::Constant(ConstantValue::Integer(i)) => InnerValue::Integer(*i),
+            ImmediateKind::Constant(ConstantValue::Integer(i)) => {
+                InnerValue::integer(*i, integer_kind(&imm.ty))
+            }
             ImmediateKind::Constant(ConstantValue::Bool(b)) => InnerValue::Boolean(*b),
             ImmediateKind::Constant(ConstantValue::String(s)) => InnerValue::String(s.clone()),
             ImmediateKind::Constant(ConstantValue::Float(f)) => InnerValue::Float(*f),
@@ -113,25 +181,119 @@ impl<'a> ExecutionEngine<'a> {
                 let items = items
                     .iter()
                     .map(|item| self.eval_immediate(item, frame))
-                    .collect_vec();
+                    .try_collect()?;
 
                 InnerValue::Struct(items)
             }
 
-            ImmediateKind::Move(place) => return self.eval_place(place, frame).clone(),
+            ImmediateKind::Enum(index, fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|field| self.eval_immediate(field, frame))
+                    .try_collect()?;
+
+                InnerValue::Enum(*index, fields)
+            }
+
+            ImmediateKind::Move(place) => return Ok(self.eval_place(place, frame).clone()),
 
             ImmediateKind::Call(func, args) => {
                 let args = args
                     .iter()
                     .map(|arg| self.eval_immediate(arg, frame))
-                    .collect();
+                    .try_collect()?;
                 let value = self.execute_function(*func, args);
                 return value;
             }
 
+            ImmediateKind::Closure(func, environment) => {
+                let environment = environment
+                    .as_ref()
+                    .map(|environment| self.eval_immediate(environment, frame))
+                    .transpose()?;
+
+                InnerValue::Function(*func, environment)
+            }
+
+            ImmediateKind::CallValue(func, args) => {
+                let func = self.eval_immediate(func, frame)?;
+                let InnerValue::Function(func, environment) = *func else {
+                    panic!("internal compiler error: called a value that isn't a function");
+                };
+
+                // The environment is passed before the arguments
+                let args = environment
+                    .into_iter()
+                    .map(Ok)
+                    .chain(args.iter().map(|arg| self.eval_immediate(arg, frame)))
+                    .try_collect()?;
+
+                return self.execute_function(func, args);
+            }
+
+            ImmediateKind::CallMutating(func, receiver, args) => {
+                let receiver_value = self.eval_place(receiver, frame).clone();
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval_immediate(arg, frame))
+                    .try_collect()?;
+
+                let (value, receiver_value) = self.execute_method(*func, receiver_value, args)?;
+                *self.eval_place(receiver, frame) = receiver_value;
+
+                return Ok(value);
+            }
+
+            ImmediateKind::WitnessMutating(receiver, index, args) => {
+                let existential = self.eval_place(receiver, frame).clone();
+                let InnerValue::Existential(receiver_value, witness_table) = *existential else {
+                    panic!("internal compiler error: value isn't an existential");
+                };
+                let func = self.context.get_witness_table(witness_table).functions[*index];
+
+                let args = args
+                    .iter()
+                    .map(|arg| self.eval_immediate(arg, frame))
+                    .try_collect()?;
+
+                let (value, receiver_value) = self.execute_method(func, receiver_value, args)?;
+                **self.eval_place(receiver, frame) = InnerValue::Existential(receiver_value, witness_table);
+
+                return Ok(value);
+            }
+
+            ImmediateKind::Existential(value, witness_table) => {
+                let value = self.eval_immediate(value, frame)?;
+
+                InnerValue::Existential(value, *witness_table)
+            }
+
+            ImmediateKind::Witness(existential, index) => {
+                let existential = self.eval_immediate(existential, frame)?;
+                let InnerValue::Existential(value, witness_table) = *existential else {
+                    panic!("internal compiler error: value isn't an existential");
+                };
+
+                let func = self.context.get_witness_table(witness_table).functions[*index];
+
+                InnerValue::Function(func, Some(value))
+            }
+
+            ImmediateKind::Alloc(value) => {
+                let value = self.eval_immediate(value, frame)?;
+
+                InnerValue::Reference(self.heap.alloc(value))
+            }
+
+            ImmediateKind::LiveObjects => {
+                let count = self.heap.live_objects();
+
+                InnerValue::integer(count as i128, integer_kind(&imm.ty))
+            }
+
             ImmediateKind::Binary(op, left, right) => {
-                let left = self.eval_immediate(left, frame);
-                let right = self.eval_immediate(right, frame);
+                let left = self.eval_immediate(left, frame)?;
+                let right = self.eval_immediate(right, frame)?;
 
                 match op {
                     BinaryIntrinsic::Boolean(op) => {
@@ -141,16 +303,15 @@ impl<'a> ExecutionEngine<'a> {
                             panic!();
                         };
 
-                        return self.eval_bool_op(*op, *left, *right);
+                        return Ok(self.eval_bool_op(*op, *left, *right));
                     }
-                    BinaryIntrinsic::Integer(op) => {
-                        let (InnerValue::Integer(left), InnerValue::Integer(right)) =
-                            (left.as_ref(), right.as_ref())
+                    BinaryIntrinsic::Integer(op, overflow) => {
+                        let (Some(left), Some(right)) = (left.as_integer(), right.as_integer())
                         else {
                             panic!("{op} {left:?} {right:?}");
                         };
 
-                        return self.eval_int_op(*op, *left, *right);
+                        return self.eval_int_op(*op, *overflow, left, right, &imm.ty, imm.span);
                     }
                     BinaryIntrinsic::Float(op) => {
                         let (InnerValue::Float(left), InnerValue::Float(right)) =
@@ -159,7 +320,7 @@ impl<'a> ExecutionEngine<'a> {
                             panic!("{op}");
                         };
 
-                        return self.eval_float_op(*op, *left, *right);
+                        return Ok(self.eval_float_op(*op, *left, *right));
                     }
                     BinaryIntrinsic::String(op) => {
                         let (InnerValue::String(left), InnerValue::String(right)) =
@@ -168,7 +329,7 @@ impl<'a> ExecutionEngine<'a> {
                             panic!();
                         };
 
-                        return self.eval_string_op(*op, left, right);
+                        return Ok(self.eval_string_op(*op, left, right));
                     }
                     BinaryIntrinsic::Compare(op) => {
                         let result = match op {
@@ -180,57 +341,90 @@ impl<'a> ExecutionEngine<'a> {
                             Comparison::GreaterThanOrEqual => left >= right,
                         };
 
-                        return Value::new(InnerValue::Boolean(result));
+                        return Ok(Value::new(InnerValue::Boolean(result)));
                     }
                 }
             }
 
-            ImmediateKind::Unary(op, value) => self.eval_unary(value, frame, op),
+            ImmediateKind::Unary(op, value) => self.eval_unary(value, imm, frame, op)?,
         };
 
-        Box::new(inner)
+        Ok(Box::new(inner))
     }
 
     fn eval_unary(
         &mut self,
         value: &Immediate,
+        imm: &Immediate,
         frame: &mut StackFrame,
         op: &UnaryIntrinsic,
-    ) -> InnerValue {
-        let operand = self.eval_immediate(value, frame);
+    ) -> Result<InnerValue> {
+        let operand = self.eval_immediate(value, frame)?;
+        let ty = &imm.ty;
 
-        match (operand.as_ref(), op) {
-            (InnerValue::Integer(i), UnaryIntrinsic::BitNot) => InnerValue::Integer(!i),
+        let result = match (operand.as_ref(), op) {
             (InnerValue::Boolean(b), UnaryIntrinsic::Not) => InnerValue::Boolean(!b),
-            (InnerValue::String(s), UnaryIntrinsic::Len) => InnerValue::Integer(s.len() as u64),
+            (InnerValue::Enum(index, _), UnaryIntrinsic::Discriminant) => {
+                InnerValue::integer(*index as i128, integer_kind(ty))
+            }
+            (InnerValue::String(s), UnaryIntrinsic::Len) => {
+                InnerValue::integer(s.len() as i128, integer_kind(ty))
+            }
 
-            (InnerValue::Integer(i), UnaryIntrinsic::Format) => InnerValue::String(i.to_string()),
+            (InnerValue::Int(i), UnaryIntrinsic::Format) => InnerValue::String(i.to_string()),
+            (InnerValue::UInt(u), UnaryIntrinsic::Format) => InnerValue::String(u.to_string()),
             (InnerValue::Boolean(b), UnaryIntrinsic::Format) => InnerValue::String(b.to_string()),
             (InnerValue::Float(f), UnaryIntrinsic::Format) => InnerValue::String(f.to_string()),
 
-            (InnerValue::String(s), UnaryIntrinsic::Parse) => {
-                // todo: we should parse other stuff
-                InnerValue::Integer(s.parse().unwrap())
-            }
+            (InnerValue::String(s), UnaryIntrinsic::Parse) => match ty.kind() {
+                TyKind::Integer(kind) => InnerValue::integer(s.parse().unwrap(), *kind),
+                TyKind::Float => InnerValue::Float(s.parse().unwrap()),
+                TyKind::Bool => InnerValue::Boolean(s.parse().unwrap()),
+                _ => unreachable!("{op}"),
+            },
 
             (InnerValue::String(s), UnaryIntrinsic::Print) => {
                 println!("{s}");
-                return InnerValue::Void;
+                InnerValue::Void
             }
 
-            (InnerValue::Integer(i), UnaryIntrinsic::ToFloat) => InnerValue::Float(*i as f64),
-            (InnerValue::Float(f), UnaryIntrinsic::Ceil) => InnerValue::Integer(f.ceil() as u64),
-            (InnerValue::Float(f), UnaryIntrinsic::Floor) => InnerValue::Integer(f.floor() as u64),
+            (InnerValue::Float(f), UnaryIntrinsic::Ceil) => {
+                InnerValue::integer(f.ceil() as i128, integer_kind(ty))
+            }
+            (InnerValue::Float(f), UnaryIntrinsic::Floor) => {
+                InnerValue::integer(f.floor() as i128, integer_kind(ty))
+            }
 
             (inner, UnaryIntrinsic::Identity) => inner.clone(),
 
             (InnerValue::Float(f), UnaryIntrinsic::Negate) => InnerValue::Float(-f),
-            (InnerValue::Integer(i), UnaryIntrinsic::Negate) => {
-                InnerValue::Integer(i.wrapping_neg())
+
+            (inner, op) => {
+                let Some(i) = inner.as_integer() else {
+                    unreachable!("{op}")
+                };
+
+                match op {
+                    UnaryIntrinsic::BitNot => InnerValue::integer(!i, integer_kind(ty)),
+                    UnaryIntrinsic::Negate => {
+                        let kind = integer_kind(ty);
+
+                        if !kind.contains(-i) {
+                            let kind = RuntimeErrorKind::Overflow("negate");
+                            return Err(RuntimeError::new(kind, imm.span));
+                        }
+
+                        InnerValue::integer(-i, kind)
+                    }
+                    UnaryIntrinsic::Convert => InnerValue::integer(i, integer_kind(ty)),
+                    UnaryIntrinsic::ToFloat => InnerValue::Float(i as f64),
+
+                    _ => unreachable!("{op}"),
+                }
             }
+        };
 
-            _ => unreachable!("{op}"),
-        }
+        Ok(result)
     }
 
     fn eval_place<'b>(&'b mut self, place: &Place, frame: &'b mut StackFrame) -> &'b mut Value {
@@ -244,10 +438,20 @@ impl<'a> ExecutionEngine<'a> {
 
                 match parent.as_mut() {
                     InnerValue::Struct(values) => &mut values[*index],
+                    InnerValue::Enum(_, values) => &mut values[*index],
 
                     parent => panic!("{parent:?} is not a struct"),
                 }
             }
+            PlaceKind::Deref(reference) => {
+                let index = match self.eval_place(reference, frame).as_ref() {
+                    InnerValue::Reference(handle) => handle.index,
+
+                    value => panic!("{value:?} is not a reference"),
+                };
+
+                self.heap.get_mut(index)
+            }
         }
     }
 
@@ -261,21 +465,138 @@ impl<'a> ExecutionEngine<'a> {
         Value::new(InnerValue::Boolean(result))
     }
 
-    fn eval_int_op(&mut self, int_op: IntegerBinaryOp, left: u64, right: u64) -> Value {
+    fn eval_int_op(
+        &mut self,
+        int_op: IntegerBinaryOp,
+        overflow: Overflow,
+        left: i128,
+        right: i128,
+        ty: &Ty,
+        span: Span,
+    ) -> Result<Value> {
+        if let IntegerBinaryOp::ShiftLeft | IntegerBinaryOp::ShiftRight = int_op {
+            return self.eval_shift(int_op, left, right, integer_kind(ty), span);
+        }
+
+        let kind = match (overflow, ty.kind()) {
+            (Overflow::Check, TyKind::Tuple(items)) => integer_kind(&items[0]),
+            _ => integer_kind(ty),
+        };
+
+        let is_division = matches!(int_op, IntegerBinaryOp::Div | IntegerBinaryOp::Rem);
+
+        if is_division && right == 0 {
+            if let Overflow::Check = overflow {
+                return Ok(Self::checked_result(0, kind, true));
+            }
+
+            let error = match int_op {
+                IntegerBinaryOp::Div => RuntimeErrorKind::DivideByZero,
+                _ => RuntimeErrorKind::RemainderByZero,
+            };
+
+            return Err(RuntimeError::new(error, span));
+        }
+
+        // Operands are sign extended, so the exact result
+        // can be computed in an i128, except for some products
+        let result = match int_op {
+            IntegerBinaryOp::Add => Some(left + right),
+            IntegerBinaryOp::Sub => Some(left - right),
+            IntegerBinaryOp::Mul => left.checked_mul(right),
+            IntegerBinaryOp::Div => Some(left / right),
+            IntegerBinaryOp::Rem => Some(left % right),
+            IntegerBinaryOp::BitOr => Some(left | right),
+            IntegerBinaryOp::BitAnd => Some(left & right),
+            IntegerBinaryOp::BitXor => Some(left ^ right),
+            IntegerBinaryOp::ShiftLeft | IntegerBinaryOp::ShiftRight => unreachable!(),
+        };
+
+        // The result of a remainder always fits, but
+        // it overflows if the division would have
+        let overflowed = match (int_op, result) {
+            (IntegerBinaryOp::Rem, _) => !kind.contains(left / right),
+            (_, Some(result)) => !kind.contains(result),
+            (_, None) => true,
+        };
+
+        let wrapped = result.unwrap_or_else(|| left.wrapping_mul(right));
+
+        if !overflowed {
+            return match overflow {
+                Overflow::Check => Ok(Self::checked_result(wrapped, kind, false)),
+                _ => Ok(Value::new(InnerValue::integer(wrapped, kind))),
+            };
+        }
+
+        let value = match overflow {
+            Overflow::Trap => {
+                let operation = match int_op {
+                    IntegerBinaryOp::Add => "add",
+                    IntegerBinaryOp::Sub => "subtract",
+                    IntegerBinaryOp::Mul => "multiply",
+                    IntegerBinaryOp::Div => "divide",
+                    _ => "calculate the remainder",
+                };
+
+                return Err(RuntimeError::new(RuntimeErrorKind::Overflow(operation), span));
+            }
+            Overflow::Wrap => InnerValue::integer(wrapped, kind),
+            Overflow::Saturate => {
+                // Only products can be too big for an i128,
+                // and their sign comes from the operands
+                let is_negative = match result {
+                    Some(result) => result < 0,
+                    None => (left < 0) != (right < 0),
+                };
+
+                match is_negative {
+                    true => InnerValue::integer(kind.min(), kind),
+                    false => InnerValue::integer(kind.max(), kind),
+                }
+            }
+            Overflow::Check => return Ok(Self::checked_result(wrapped, kind, true)),
+        };
+
+        Ok(Value::new(value))
+    }
+
+    /// Shifts trap if the shift amount is negative, or
+    /// isn't smaller than the number of bits in the integer
+    fn eval_shift(
+        &mut self,
+        int_op: IntegerBinaryOp,
+        left: i128,
+        right: i128,
+        kind: IntegerKind,
+        span: Span,
+    ) -> Result<Value> {
+        if right < 0 || right >= kind.bits() as i128 {
+            let operation = match int_op {
+                IntegerBinaryOp::ShiftLeft => "shift left",
+                _ => "shift right",
+            };
+
+            return Err(RuntimeError::new(RuntimeErrorKind::Overflow(operation), span));
+        }
+
+        // Right shifts of signed integers are arithmetic,
+        // since the operands are sign extended
         let result = match int_op {
-            IntegerBinaryOp::Add => left + right,
-            IntegerBinaryOp::Sub => left - right,
-            IntegerBinaryOp::Mul => left * right,
-            IntegerBinaryOp::Div => left / right,
-            IntegerBinaryOp::Rem => left % right,
             IntegerBinaryOp::ShiftLeft => left << right,
-            IntegerBinaryOp::ShiftRight => left >> right,
-            IntegerBinaryOp::BitOr => left | right,
-            IntegerBinaryOp::BitAnd => left & right,
-            IntegerBinaryOp::BitXor => left ^ right,
+            _ => left >> right,
         };
 
-        Value::new(InnerValue::Integer(result))
+        Ok(Value::new(InnerValue::integer(result, kind)))
+    }
+
+    /// The result of a checked operation is the wrapped
+    /// value, and whether the operation overflowed
+    fn checked_result(value: i128, kind: IntegerKind, overflowed: bool) -> Value {
+        let value = Value::new(InnerValue::integer(value, kind));
+        let overflowed = Value::new(InnerValue::Boolean(overflowed));
+
+        Value::new(InnerValue::Struct(vec![value, overflowed]))
     }
 
     fn eval_float_op(&mut self, float_op: FloatBinaryOp, left: f64, right: f64) -> Value {
@@ -299,3 +620,10 @@ impl<'a> ExecutionEngine<'a> {
         Value::new(InnerValue::String(result))
     }
 }
+
+fn integer_kind(ty: &Ty) -> IntegerKind {
+    match ty.kind() {
+        TyKind::Integer(kind) => *kind,
+        _ => panic!("internal compiler error: expected an integer type"),
+    }
+}
@@ -1,16 +1,73 @@
 
+use std::rc::Rc;
+
+use firefly_mir::{code::Function, ty::{witness_table::WitnessTable, IntegerKind}, value::ConstantValue, Id};
+
+use crate::heap::Handle;
+
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum InnerValue {
-    Integer(u64),
+    Int(i64),
+    UInt(u64),
     String(String),
     Boolean(bool),
     Float(f64),
 
     Struct(Vec<Value>),
 
+    /// The index of an enum case and its values
+    Enum(usize, Vec<Value>),
+
+    /// A function and the environment it's called with
+    Function(Id<Function>, Option<Value>),
+
+    /// A value and the witness table of its type
+    Existential(Value, Id<WitnessTable>),
+
+    /// A value on the heap, shared by every copy of the reference
+    Reference(Rc<Handle>),
+
     Void,
 
     Undefined,
 }
 
-pub type Value = Box<InnerValue>;
\ No newline at end of file
+pub type Value = Box<InnerValue>;
+
+impl InnerValue {
+    /// Creates an integer of the given kind, wrapping
+    /// the value around if it doesn't fit
+    pub fn integer(value: i128, kind: IntegerKind) -> InnerValue {
+        let bits = kind.bits();
+        let truncated = value & ((1 << bits) - 1);
+
+        if kind.is_signed() {
+            // Sign extend from the top bit of the integer
+            let shift = 128 - bits;
+
+            InnerValue::Int(((truncated << shift) >> shift) as i64)
+        } else {
+            InnerValue::UInt(truncated as u64)
+        }
+    }
+
+    /// Checks whether the value is equal to a constant
+    pub fn equals_constant(&self, constant: &ConstantValue) -> bool {
+        match (self, constant) {
+            (InnerValue::Boolean(b), ConstantValue::Bool(constant)) => b == constant,
+            (InnerValue::Float(f), ConstantValue::Float(constant)) => f == constant,
+            (InnerValue::String(s), ConstantValue::String(constant)) => s == constant,
+            (value, ConstantValue::Integer(constant)) => value.as_integer() == Some(*constant),
+
+            _ => false,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i128> {
+        match self {
+            InnerValue::Int(i) => Some(*i as i128),
+            InnerValue::UInt(u) => Some(*u as i128),
+            _ => None,
+        }
+    }
+}
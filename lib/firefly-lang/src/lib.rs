@@ -1,7 +1,7 @@
 use firefly_hir::{
     items::{Constant, Module, TypeAlias},
     resolve::{Import, Symbol},
-    ty::{Ty, TyKind},
+    ty::{IntegerKind, Ty, TyKind},
     value::{HasValue, LiteralValue, Value, ValueKind},
     AccessComponent, BaseComponent, Component, HirContext, Id, Name, Visibility,
 };
@@ -17,7 +17,10 @@ pub fn create_lang_module(context: &mut HirContext) {
 
     let lang_id = create("lang", Module { id: Id::default() }, root, context);
 
-    create("int", typealias(TyKind::Integer), lang_id, context);
+    create("int", typealias(TyKind::Integer(IntegerKind::INT)), lang_id, context);
+    for kind in IntegerKind::ALL {
+        create(kind.name(), typealias(TyKind::Integer(kind)), lang_id, context);
+    }
     create("string", typealias(TyKind::String), lang_id, context);
     create("bool", typealias(TyKind::Bool), lang_id, context);
     create("float", typealias(TyKind::Float), lang_id, context);
@@ -39,6 +42,42 @@ pub fn create_lang_module(context: &mut HirContext) {
         "eq_int", "neq_int", "gt_int", "geq_int", "lt_int", "leq_int",
     ];
 
+    // Arithmetic that doesn't trap on overflow
+    const WRAPPING_OPERATORS: &[&str] = &[
+        "wrapping_add",
+        "wrapping_sub",
+        "wrapping_mul",
+        "wrapping_div",
+        "wrapping_rem",
+    ];
+
+    const SATURATING_OPERATORS: &[&str] = &[
+        "saturating_add",
+        "saturating_sub",
+        "saturating_mul",
+        "saturating_div",
+    ];
+
+    const CHECKED_OPERATORS: &[&str] = &[
+        "checked_add",
+        "checked_sub",
+        "checked_mul",
+        "checked_div",
+        "checked_rem",
+    ];
+
+    const INT_CONVERSIONS: &[(&str, IntegerKind)] = &[
+        ("to_i8", IntegerKind::I8),
+        ("to_i16", IntegerKind::I16),
+        ("to_i32", IntegerKind::I32),
+        ("to_i64", IntegerKind::I64),
+        ("to_u8", IntegerKind::U8),
+        ("to_u16", IntegerKind::U16),
+        ("to_u32", IntegerKind::U32),
+        ("to_u64", IntegerKind::U64),
+        ("to_int", IntegerKind::INT),
+    ];
+
     const FLOAT_OPERATORS: &[&str] = &["fadd", "fsub", "fmul", "fdiv", "frem", "fpow"];
 
     const FLOAT_COMPARES: &[&str] = &[
@@ -52,61 +91,45 @@ pub fn create_lang_module(context: &mut HirContext) {
 
     const BOOL_OPERATORS: &[&str] = &["and", "or", "eq_bool", "neq_bool"];
 
-    for name in INT_OPERATORS {
-        create_func(
-            name,
-            &[TyKind::Integer, TyKind::Integer],
-            TyKind::Integer,
-            lang_id,
-            context,
-        )
-    }
+    // Integer builtins are overloaded for every integer type
+    for kind in IntegerKind::ALL {
+        let int = TyKind::Integer(kind);
 
-    create_func(
-        "bitnot",
-        &[TyKind::Integer],
-        TyKind::Integer,
-        lang_id,
-        context,
-    );
+        for name in INT_OPERATORS.iter().chain(WRAPPING_OPERATORS).chain(SATURATING_OPERATORS) {
+            create_func(name, &[int.clone(), int.clone()], int.clone(), lang_id, context)
+        }
 
-    create_func(
-        "identity",
-        &[TyKind::Integer],
-        TyKind::Integer,
-        lang_id,
-        context,
-    );
+        // Checked operators return whether they overflowed
+        let checked = TyKind::Tuple(vec![
+            Ty::new_unspanned(int.clone()),
+            Ty::new_unspanned(TyKind::Bool),
+        ]);
 
-    create_func(
-        "negate",
-        &[TyKind::Integer],
-        TyKind::Integer,
-        lang_id,
-        context,
-    );
+        for name in CHECKED_OPERATORS {
+            create_func(name, &[int.clone(), int.clone()], checked.clone(), lang_id, context)
+        }
 
-    for name in INT_COMPARES {
-        create_func(
-            name,
-            &[TyKind::Integer, TyKind::Integer],
-            TyKind::Bool,
-            lang_id,
-            context,
-        )
+        create_func("bitnot", &[TyKind::Integer(kind)], int.clone(), lang_id, context);
+        create_func("identity", &[TyKind::Integer(kind)], int.clone(), lang_id, context);
+        create_func("negate", &[TyKind::Integer(kind)], int.clone(), lang_id, context);
+
+        for name in INT_COMPARES {
+            create_func(name, &[int.clone(), int.clone()], TyKind::Bool, lang_id, context)
+        }
+
+        create_func("format_int", &[TyKind::Integer(kind)], TyKind::String, lang_id, context);
+        create_func("to_float", &[TyKind::Integer(kind)], TyKind::Float, lang_id, context);
+
+        // Conversions between integer types wrap around
+        for (name, target) in INT_CONVERSIONS {
+            create_func(name, &[TyKind::Integer(kind)], TyKind::Integer(*target), lang_id, context);
+        }
     }
 
     create_func(
         "parse_int",
         &[TyKind::String],
-        TyKind::Integer,
-        lang_id,
-        context,
-    );
-    create_func(
-        "format_int",
-        &[TyKind::Integer],
-        TyKind::String,
+        TyKind::Integer(IntegerKind::INT),
         lang_id,
         context,
     );
@@ -131,15 +154,10 @@ pub fn create_lang_module(context: &mut HirContext) {
         )
     }
 
-    create_func("floor", &[TyKind::Float], TyKind::Integer, lang_id, context);
-    create_func("ceil", &[TyKind::Float], TyKind::Integer, lang_id, context);
-    create_func(
-        "to_float",
-        &[TyKind::Integer],
-        TyKind::Float,
-        lang_id,
-        context,
-    );
+    let int = TyKind::Integer(IntegerKind::INT);
+
+    create_func("floor", &[TyKind::Float], int.clone(), lang_id, context);
+    create_func("ceil", &[TyKind::Float], int.clone(), lang_id, context);
 
     create_func(
         "parse_float",
@@ -205,7 +223,7 @@ pub fn create_lang_module(context: &mut HirContext) {
         lang_id,
         context,
     );
-    create_func("len", &[TyKind::String], TyKind::Integer, lang_id, context);
+    create_func("len", &[TyKind::String], int.clone(), lang_id, context);
     create_func(
         "eq_str",
         &[TyKind::String, TyKind::String],
@@ -221,6 +239,10 @@ pub fn create_lang_module(context: &mut HirContext) {
         context,
     );
 
+    // Lets programs check that the instances of
+    // classes they no longer use have been freed
+    create_func("live_objects", &[], int.clone(), lang_id, context);
+
     create_literal(
         "true",
         ValueKind::Literal(LiteralValue::Boolean(true)),
@@ -1,5 +1,7 @@
 mod path;
 mod sym;
+mod ty;
 
 pub use path::*;
-pub use sym::*;
\ No newline at end of file
+pub use sym::*;
+pub use ty::*;
\ No newline at end of file
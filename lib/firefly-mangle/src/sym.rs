@@ -1,13 +1,23 @@
 use std::fmt::Display;
 
-use crate::path::Path;
+use crate::{path::Path, ty::TyName};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum SymbolName {
     Func(Path),
     Struct(Path),
+    Enum(Path),
+    Protocol(Path),
     Var(Path),
 
+    /// An instance of a generic symbol, with
+    /// the types of its generic arguments
+    Instance(Box<SymbolName>, Vec<TyName>),
+
+    /// The witness table of a type for
+    /// a protocol it conforms to
+    Witness(Box<SymbolName>, Box<SymbolName>),
+
     Custom(String)
 }
 
@@ -16,7 +26,17 @@ impl Display for SymbolName {
         match self {
             SymbolName::Func(path) => write!(f, "_F{}", path),
             SymbolName::Struct(path) => write!(f, "_S{}", path),
+            SymbolName::Enum(path) => write!(f, "_E{}", path),
+            SymbolName::Protocol(path) => write!(f, "_P{}", path),
             SymbolName::Var(path) => write!(f, "_V{}", path),
+            SymbolName::Instance(symbol, generics) => {
+                write!(f, "{}I", symbol)?;
+                for generic in generics {
+                    write!(f, "{}", generic)?;
+                }
+                write!(f, "E")
+            }
+            SymbolName::Witness(ty, protocol) => write!(f, "_W{}{}", ty, protocol),
             SymbolName::Custom(name) => write!(f, "{}", name),
         }
     }
@@ -0,0 +1,38 @@
+use crate::sym::SymbolName;
+
+/// The name of a type, used to tell apart the
+/// instances of a generic symbol
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TyName {
+    Unit,
+    Tuple(Vec<TyName>),
+    Func(Vec<TyName>, Box<TyName>),
+    Optional(Box<TyName>),
+    Builtin(String),
+    Symbol(SymbolName),
+}
+
+impl std::fmt::Display for TyName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TyName::Unit => write!(f, "u"),
+            TyName::Tuple(items) => {
+                write!(f, "T")?;
+                for item in items {
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "E")
+            }
+            TyName::Func(params, return_ty) => {
+                write!(f, "F")?;
+                for param in params {
+                    write!(f, "{}", param)?;
+                }
+                write!(f, "R{}E", return_ty)
+            }
+            TyName::Optional(inner) => write!(f, "O{}", inner),
+            TyName::Builtin(name) => write!(f, "B{}{}", name.len(), name),
+            TyName::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
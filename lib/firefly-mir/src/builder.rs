@@ -88,7 +88,8 @@ impl<'a> Builder<'a> {
         return basic_block.terminator.is_some()
     }
 
-    fn current_func_id(&self) -> Id<Function> {
+    /// The function the builder is positioned on
+    pub fn current_func_id(&self) -> Id<Function> {
         self.current_func.expect("internal compiler error: no mir id selected")
     }
 
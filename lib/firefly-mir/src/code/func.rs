@@ -22,6 +22,10 @@ pub struct FunctionSignature {
 }
 
 impl Function {
+    pub fn name(&self) -> &SymbolName {
+        &self.name
+    }
+
     pub fn locals(&self) -> &Vec<Local> {
         &self.locals
     }
@@ -29,6 +33,20 @@ impl Function {
     pub fn basic_blocks(&self) -> &Vec<BasicBlockId> {
         &self.basic_blocks
     }
+
+    pub fn signature(&self) -> &FunctionSignature {
+        &self.signature
+    }
+}
+
+impl FunctionSignature {
+    pub fn parameters(&self) -> &Vec<Ty> {
+        &self.parameters
+    }
+
+    pub fn return_ty(&self) -> &Ty {
+        &self.return_ty
+    }
 }
 
 impl DisplayInContext for Function {
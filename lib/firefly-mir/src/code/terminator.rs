@@ -1,14 +1,37 @@
-use crate::{value::Immediate, MirContext, util::DisplayInContext};
+use firefly_span::Span;
+use itertools::Itertools;
+
+use crate::{value::{ConstantValue, Immediate}, MirContext, util::DisplayInContext};
 
 use super::BasicBlockId;
 
 pub enum TerminatorKind {
     Branch(BasicBlockId),
     BranchIf(Immediate, BasicBlockId, BasicBlockId),
+
+    /// Branches to the block of the first constant equal
+    /// to the value, or to the last block if none are
+    Switch(Immediate, Vec<(ConstantValue, BasicBlockId)>, BasicBlockId),
+
+    /// Marks a block that can never be reached
+    Unreachable,
+
+    /// Stops the program with a runtime error
+    Panic(PanicKind, Span),
     ReturnVoid,
     Return(Immediate)
 }
 
+/// The reason a program stops with a runtime error
+#[derive(Clone, Copy, Debug)]
+pub enum PanicKind {
+    /// A `nil` optional was unwrapped
+    UnwrapNil,
+
+    /// A function called with `try!` threw an error
+    ForceTry,
+}
+
 /// A Terminator is a special instruction that ends a basic block.
 /// Each basic block has exactly one terminator, which either moves
 /// execution to another basic block, or exits the program.
@@ -29,6 +52,24 @@ impl Terminator {
         }
     }
 
+    pub fn switch(value: Immediate, targets: Vec<(ConstantValue, BasicBlockId)>, otherwise: BasicBlockId) -> Self {
+        Self {
+            kind: TerminatorKind::Switch(value, targets, otherwise)
+        }
+    }
+
+    pub fn unreachable() -> Self {
+        Self {
+            kind: TerminatorKind::Unreachable
+        }
+    }
+
+    pub fn panic(kind: PanicKind, span: Span) -> Self {
+        Self {
+            kind: TerminatorKind::Panic(kind, span)
+        }
+    }
+
     pub fn returns(value: Immediate) -> Self {
         Self {
             kind: TerminatorKind::Return(value)
@@ -42,11 +83,29 @@ impl Terminator {
     }
 }
 
+impl std::fmt::Display for PanicKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PanicKind::UnwrapNil => write!(f, "unwrap_nil"),
+            PanicKind::ForceTry => write!(f, "force_try"),
+        }
+    }
+}
+
 impl DisplayInContext for TerminatorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>, context: &MirContext) -> std::fmt::Result {
         match self {
             TerminatorKind::Branch(dest) => write!(f, "branch {}", dest.local_id),
             TerminatorKind::BranchIf(condition, positive, negative) => write!(f, "branch if {} ({} else {})", context.display(condition), positive.local_id, negative.local_id),
+            TerminatorKind::Switch(value, targets, otherwise) => write!(
+                f,
+                "switch {} ({}, else {})",
+                context.display(value),
+                targets.iter().map(|(constant, target)| format!("{constant} => {}", target.local_id)).format(", "),
+                otherwise.local_id
+            ),
+            TerminatorKind::Unreachable => write!(f, "unreachable"),
+            TerminatorKind::Panic(kind, _) => write!(f, "panic {kind}"),
             TerminatorKind::ReturnVoid => write!(f, "return"),
             TerminatorKind::Return(value) => write!(f, "return {}", context.display(value))
         }
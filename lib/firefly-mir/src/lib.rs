@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use code::{BasicBlock, BasicBlockId, Function, FunctionSignature, Global, Local};
 use firefly_mangle::SymbolName;
-use ty::{struct_def::StructDef, Ty};
+use ty::{enum_def::EnumDef, struct_def::StructDef, witness_table::WitnessTable, Ty};
 
 pub mod ty;
 pub mod value;
@@ -16,7 +16,15 @@ pub struct MirContext {
     pub(crate) basic_blocks: UniqueContainer<BasicBlock>,
     pub(crate) functions:    UniqueContainer<Function>,
     pub(crate) structs:      UniqueContainer<StructDef>,
+    pub(crate) enums:        UniqueContainer<EnumDef>,
     pub(crate) globals:      UniqueContainer<Global>,
+    pub(crate) witnesses:    UniqueContainer<WitnessTable>,
+
+    /// The function that runs when the program starts
+    pub(crate) entry_point: Option<UniqueId<Function>>,
+
+    /// Assigns every global its default value before main runs
+    pub(crate) global_initializer: Option<UniqueId<Function>>,
 }
 
 impl MirContext {
@@ -25,7 +33,12 @@ impl MirContext {
             basic_blocks: UniqueContainer::new(),
             functions:    UniqueContainer::new(),
             structs:      UniqueContainer::new(),
+            enums:        UniqueContainer::new(),
             globals:      UniqueContainer::new(),
+            witnesses:    UniqueContainer::new(),
+
+            entry_point: None,
+            global_initializer: None,
         }
     }
 
@@ -61,6 +74,58 @@ impl MirContext {
         return struct_def.fields.len() - 1
     }
 
+    /// Create an enum in the MirContext
+    pub fn create_enum(
+        &mut self,
+        name: &SymbolName,
+    ) -> UniqueId<EnumDef>
+    {
+        let id = self.enums.next();
+
+        let enum_def = EnumDef {
+            id,
+            name: name.clone(),
+            cases: Vec::new(),
+        };
+
+        self.enums.push(enum_def);
+
+        id
+    }
+
+    /// Create a case in an enum, carrying values of the given types
+    pub fn create_case(
+        &mut self,
+        enum_def: Id<EnumDef>,
+        fields: Vec<Ty>) -> usize
+    {
+        let enum_def = self.get_enum_mut(enum_def);
+
+        enum_def.cases.push(fields);
+
+        enum_def.cases.len() - 1
+    }
+
+    /// Create a witness table, with a function
+    /// for each requirement of a protocol
+    pub fn create_witness_table(
+        &mut self,
+        name: &SymbolName,
+        functions: Vec<UniqueId<Function>>) -> UniqueId<WitnessTable>
+    {
+        let id = self.witnesses.next();
+
+        let witness_table = WitnessTable {
+            id,
+            name: name.clone(),
+            functions,
+        };
+
+        self.witnesses.push(witness_table);
+
+        id
+    }
+
     /// Create a function in the MirContext
     pub fn create_function(
         &mut self,
@@ -185,6 +250,27 @@ impl MirContext {
             .expect("internal compiler error: struct not found")
     }
 
+    /// Gets a reference to an enum by id
+    pub fn get_enum(&self, id: UniqueId<EnumDef>) -> &EnumDef {
+        self.enums
+            .get_by_id(id)
+            .expect("internal compiler error: enum not found")
+    }
+
+    /// Gets a mutable reference to an enum by id
+    pub fn get_enum_mut(&mut self, id: UniqueId<EnumDef>) -> &mut EnumDef {
+        self.enums
+            .get_mut_by_id(id)
+            .expect("internal compiler error: enum not found")
+    }
+
+    /// Gets a reference to a witness table by id
+    pub fn get_witness_table(&self, id: UniqueId<WitnessTable>) -> &WitnessTable {
+        self.witnesses
+            .get_by_id(id)
+            .expect("internal compiler error: witness table not found")
+    }
+
     /// Gets a reference to a global by id
     pub fn get_global(&self, id: UniqueId<Global>) -> &Global {
         self.globals
@@ -203,6 +289,26 @@ impl MirContext {
     pub fn globals(&self) -> &Vec<Global> {
         &self.globals
     }
+
+    /// Sets the function that runs when the program starts
+    pub fn set_entry_point(&mut self, func: UniqueId<Function>) {
+        self.entry_point = Some(func);
+    }
+
+    /// Gets the function that runs when the program starts
+    pub fn entry_point(&self) -> Option<UniqueId<Function>> {
+        self.entry_point
+    }
+
+    /// Sets the function that initializes the globals
+    pub fn set_global_initializer(&mut self, func: UniqueId<Function>) {
+        self.global_initializer = Some(func);
+    }
+
+    /// Gets the function that initializes the globals
+    pub fn global_initializer(&self) -> Option<UniqueId<Function>> {
+        self.global_initializer
+    }
 }
 
 impl Display for MirContext {
@@ -215,6 +321,14 @@ impl Display for MirContext {
             writeln!(f, "{}", self.display(struct_def))?;
         }
 
+        for enum_def in self.enums.iter() {
+            writeln!(f, "{}", self.display(enum_def))?;
+        }
+
+        for witness_table in self.witnesses.iter() {
+            writeln!(f, "{}", self.display(witness_table))?;
+        }
+
         for func in self.functions.iter() {
             writeln!(f, "{}", self.display(func))?;
         }
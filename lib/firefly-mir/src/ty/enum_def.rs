@@ -0,0 +1,25 @@
+use firefly_mangle::SymbolName;
+use itertools::Itertools;
+
+use crate::{MirContext, util::{DisplayInContext, UniqueId}};
+
+use super::Ty;
+
+/// A tagged union. A value of an enum is one of its
+/// cases, identified by the case's index, along
+/// with the values that case carries
+pub struct EnumDef {
+    pub id:    UniqueId<EnumDef>,
+    pub name:  SymbolName,
+    pub cases: Vec<Vec<Ty>>,
+}
+
+impl DisplayInContext for EnumDef {
+    fn fmt(&self, f: &mut std::fmt::Formatter, context: &MirContext) -> std::fmt::Result {
+        writeln!(f, "enum {} {{", self.name)?;
+        for (index, case) in self.cases.iter().enumerate() {
+            writeln!(f, "  case {index}({}),", case.iter().map(|ty| context.display(ty)).format(", "))?;
+        }
+        write!(f, "}}")
+    }
+}
@@ -1,17 +1,24 @@
 pub mod struct_def;
+pub mod enum_def;
+pub mod witness_table;
+mod integer;
 
 use std::fmt::Formatter;
 
+use firefly_mangle::SymbolName;
 use itertools::Itertools;
 use struct_def::StructDef;
+use enum_def::EnumDef;
 
 use crate::util::{DisplayInContext, UniqueId};
 
 use super::MirContext;
 
+pub use integer::IntegerKind;
+
 #[derive(Clone)]
 pub enum TyKind {
-    Integer,
+    Integer(IntegerKind),
     String,
     Bool,
     Float,
@@ -19,9 +26,30 @@ pub enum TyKind {
     Void,
 
     Struct(UniqueId<StructDef>),
+    Enum(UniqueId<EnumDef>),
 
     Tuple(Vec<Ty>),
     Func(Vec<Ty>, Ty),
+
+    /// A tag, which is 0 for `nil` and 1 for a value,
+    /// followed by the value if there is one. Its
+    /// values are constructed like the cases of an enum
+    Optional(Ty),
+
+    /// What a function that can throw returns: a tag, which is
+    /// 0 for a returned value and 1 for a thrown error, followed
+    /// by the value or the error. Its values are constructed
+    /// like the cases of an enum
+    Result(Ty, Ty),
+
+    /// A value of any type that conforms to a protocol,
+    /// along with the type's witness table
+    Existential(SymbolName),
+
+    /// A reference to a value on the heap. Copies of the
+    /// reference share the value, which is freed when the
+    /// last reference to it goes away
+    Reference(Ty),
 }
 
 #[derive(Clone)]
@@ -35,12 +63,16 @@ impl Ty {
             kind: Box::new(kind)
         }
     }
+
+    pub fn kind(&self) -> &TyKind {
+        &self.kind
+    }
 }
 
 impl DisplayInContext for TyKind {
     fn fmt(&self, f: &mut Formatter<'_>, context: &MirContext) -> std::fmt::Result {
         match self {
-            TyKind::Integer => write!(f, "int"),
+            TyKind::Integer(kind) => write!(f, "{}", kind.name()),
             TyKind::String => write!(f, "string"),
             TyKind::Float => write!(f, "float"),
             TyKind::Bool => write!(f, "bool"),
@@ -52,9 +84,18 @@ impl DisplayInContext for TyKind {
 
                 write!(f, "struct {}", struct_def.name)
             }
+            TyKind::Enum(id) => {
+                let enum_def = context.get_enum(*id);
+
+                write!(f, "enum {}", enum_def.name)
+            }
 
             TyKind::Tuple(items) => write!(f, "({})", items.iter().map(|item| context.display(item)).format(", ")),
-            TyKind::Func(params, return_ty) => write!(f, "func ({}) -> {}", params.iter().map(|item| context.display(item)).format(", "), context.display(return_ty))
+            TyKind::Func(params, return_ty) => write!(f, "func ({}) -> {}", params.iter().map(|item| context.display(item)).format(", "), context.display(return_ty)),
+            TyKind::Optional(inner) => write!(f, "optional {}", context.display(inner)),
+            TyKind::Result(value, error) => write!(f, "result {} throws {}", context.display(value), context.display(error)),
+            TyKind::Existential(protocol) => write!(f, "existential {protocol}"),
+            TyKind::Reference(pointee) => write!(f, "ref {}", context.display(pointee)),
         }
     }
 }
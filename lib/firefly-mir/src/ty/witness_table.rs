@@ -0,0 +1,21 @@
+use firefly_mangle::SymbolName;
+use itertools::Itertools;
+
+use crate::{code::Function, MirContext, util::{DisplayInContext, UniqueId}};
+
+/// The functions a type implements the requirements
+/// of a protocol with, in the order the protocol
+/// declares them
+pub struct WitnessTable {
+    pub id:        UniqueId<WitnessTable>,
+    pub name:      SymbolName,
+    pub functions: Vec<UniqueId<Function>>,
+}
+
+impl DisplayInContext for WitnessTable {
+    fn fmt(&self, f: &mut std::fmt::Formatter, context: &MirContext) -> std::fmt::Result {
+        let functions = self.functions.iter().map(|function| &context.get_function(*function).name);
+
+        write!(f, "witness {} [{}]", self.name, functions.format(", "))
+    }
+}
@@ -3,13 +3,13 @@ use std::fmt::{Display, Formatter};
 use firefly_span::Span;
 use itertools::Itertools;
 
-use crate::{code::Function, ty::{Ty, TyKind}, util::Id, DisplayInContext, MirContext};
+use crate::{code::Function, ty::{witness_table::WitnessTable, Ty, TyKind}, util::Id, DisplayInContext, MirContext};
 
 use super::{intrinsics::BinaryIntrinsic, Place, UnaryIntrinsic};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ConstantValue {
-    Integer(u64),
+    Integer(i128),
     Bool(bool),
     Float(f64),
     String(String),
@@ -26,12 +26,47 @@ pub enum ImmediateKind {
     /// Constructs a struct
     Struct(Vec<Immediate>),
 
+    /// Constructs the case of an enum with the given index,
+    /// or an optional, which is case 0 if it is `nil`
+    Enum(usize, Vec<Immediate>),
+
     /// Takes the value currently in a place
     Move(Place),
 
     /// Calls a function
     Call(Id<Function>, Vec<Immediate>),
 
+    /// Creates a function value. If it has an environment,
+    /// it is passed to the function before the arguments
+    Closure(Id<Function>, Option<Immediate>),
+
+    /// Calls a function value
+    CallValue(Immediate, Vec<Immediate>),
+
+    /// Calls a method that can change its receiver. The value in the
+    /// place is passed before the arguments, and the method's `self`
+    /// is stored back into the place when it returns
+    CallMutating(Id<Function>, Place, Vec<Immediate>),
+
+    /// Calls the function at an index in the witness table of the
+    /// existential in a place, storing the value the function
+    /// leaves in `self` back into the existential
+    WitnessMutating(Place, usize, Vec<Immediate>),
+
+    /// Wraps a value in an existential, along with
+    /// the witness table of the value's type
+    Existential(Immediate, Id<WitnessTable>),
+
+    /// Gets the function at an index in the witness table
+    /// of an existential, bound to the value inside it
+    Witness(Immediate, usize),
+
+    /// Moves a value onto the heap, giving a reference to it
+    Alloc(Immediate),
+
+    /// Counts the values on the heap that haven't been freed
+    LiveObjects,
+
     /// Performs an intrinsic operation on two immediates 
     Binary(BinaryIntrinsic, Immediate, Immediate),
 
@@ -71,6 +106,7 @@ impl DisplayInContext for ImmediateKind {
             ImmediateKind::Constant(constant) => write!(f, "const {constant}"),
             ImmediateKind::Tuple(items) => write!(f, "tuple ({})", items.iter().map(|item| context.display(item)).format(", ")),
             ImmediateKind::Struct(fields) => write!(f, "struct ({})", fields.iter().map(|field| context.display(field)).format(", ")),
+            ImmediateKind::Enum(index, fields) => write!(f, "case {index} ({})", fields.iter().map(|field| context.display(field)).format(", ")),
             ImmediateKind::Move(place) => write!(f, "move {place}"),
             ImmediateKind::Call(function, args) => {
                 let func = context.get_function(*function);
@@ -79,6 +115,33 @@ impl DisplayInContext for ImmediateKind {
 
                 write!(f, "invoke {func_name} ({})", args.iter().map(|arg| context.display(arg)).format(", "))
             }
+            ImmediateKind::Closure(function, environment) => {
+                let func_name = &context.get_function(*function).name;
+
+                match environment {
+                    Some(environment) => write!(f, "closure {func_name} ({})", context.display(environment)),
+                    None => write!(f, "closure {func_name}"),
+                }
+            }
+            ImmediateKind::CallValue(function, args) => {
+                write!(f, "invoke {} ({})", context.display(function), args.iter().map(|arg| context.display(arg)).format(", "))
+            }
+            ImmediateKind::CallMutating(function, receiver, args) => {
+                let func_name = &context.get_function(*function).name;
+
+                write!(f, "invoke_mutating {func_name} ({receiver}; {})", args.iter().map(|arg| context.display(arg)).format(", "))
+            }
+            ImmediateKind::WitnessMutating(receiver, index, args) => {
+                write!(f, "invoke_mutating witness {index} ({receiver}; {})", args.iter().map(|arg| context.display(arg)).format(", "))
+            }
+            ImmediateKind::Existential(value, witness_table) => {
+                let witness_name = &context.get_witness_table(*witness_table).name;
+
+                write!(f, "existential {witness_name} ({})", context.display(value))
+            }
+            ImmediateKind::Witness(existential, index) => write!(f, "witness {index} ({})", context.display(existential)),
+            ImmediateKind::Alloc(value) => write!(f, "alloc ({})", context.display(value)),
+            ImmediateKind::LiveObjects => write!(f, "live_objects"),
             ImmediateKind::Binary(func, left, right) => write!(f, "{func} ({}, {})", context.display(left), context.display(right)),
             ImmediateKind::Unary(func, operand) => write!(f, "{func} ({})", context.display(operand)),
             ImmediateKind::Void => write!(f, "void")
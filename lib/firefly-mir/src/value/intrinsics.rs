@@ -24,6 +24,23 @@ pub enum IntegerBinaryOp {
     BitXor,
 }
 
+/// What an integer operation does when
+/// its result doesn't fit in its type
+#[derive(Copy, Clone)]
+pub enum Overflow {
+    /// Stops the program with a runtime error
+    Trap,
+
+    /// Wraps around to the other end of the range
+    Wrap,
+
+    /// Clamps to the smallest or largest value
+    Saturate,
+
+    /// Gives the wrapped result and whether it overflowed
+    Check,
+}
+
 #[derive(Copy, Clone)]
 pub enum FloatBinaryOp {
     Add,
@@ -50,7 +67,7 @@ pub enum StringBinaryOp {
 pub enum BinaryIntrinsic {
     Compare(Comparison),
 
-    Integer(IntegerBinaryOp),
+    Integer(IntegerBinaryOp, Overflow),
     Float(FloatBinaryOp),
     Boolean(BooleanBinaryOp),
     String(StringBinaryOp),
@@ -72,8 +89,15 @@ pub enum UnaryIntrinsic {
     Ceil,
     ToFloat,
 
+    /// Converts between integer types,
+    /// wrapping values that don't fit
+    Convert,
+
     Identity,
     Negate,
+
+    /// Gets the index of the case an enum holds
+    Discriminant,
 }
 
 impl Display for Comparison {
@@ -106,6 +130,17 @@ impl Display for IntegerBinaryOp {
     }
 }
 
+impl Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Overflow::Trap => Ok(()),
+            Overflow::Wrap => write!(f, "wrapping_"),
+            Overflow::Saturate => write!(f, "saturating_"),
+            Overflow::Check => write!(f, "checked_"),
+        }
+    }
+}
+
 impl Display for FloatBinaryOp {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -142,7 +177,7 @@ impl Display for BinaryIntrinsic {
         match self {
             BinaryIntrinsic::Compare(comparison) => write!(f, "compare[{comparison}]"),
             BinaryIntrinsic::Float(op) => write!(f, "{op}"),
-            BinaryIntrinsic::Integer(op) => write!(f, "{op}"),
+            BinaryIntrinsic::Integer(op, overflow) => write!(f, "{overflow}{op}"),
             BinaryIntrinsic::Boolean(op) => write!(f, "{op}"),
             BinaryIntrinsic::String(op) => write!(f, "{op}"),
         }
@@ -161,8 +196,10 @@ impl Display for UnaryIntrinsic {
             UnaryIntrinsic::Floor => write!(f, "floor"),
             UnaryIntrinsic::Ceil => write!(f, "ceil"),
             UnaryIntrinsic::ToFloat => write!(f, "to_float"),
+            UnaryIntrinsic::Convert => write!(f, "convert"),
             UnaryIntrinsic::Identity => write!(f, "identity"),
             UnaryIntrinsic::Negate => write!(f, "negate"),
+            UnaryIntrinsic::Discriminant => write!(f, "discriminant"),
         }
     }
 }
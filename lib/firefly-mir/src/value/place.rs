@@ -16,6 +16,9 @@ pub enum PlaceKind {
 
     /// A field of a struct or a tuple
     Field(Place, usize),
+
+    /// The value on the heap a reference points to
+    Deref(Place),
 }
 
 #[derive(Clone)]
@@ -41,6 +44,7 @@ impl Display for PlaceKind {
             PlaceKind::Local(local_id) => write!(f, "{local_id}"),
             PlaceKind::Global(global_id) => write!(f, "{global_id}"),
             PlaceKind::Field(place, index) => write!(f, "{place}.{index}"),
+            PlaceKind::Deref(place) => write!(f, "(*{place})"),
         }
     }
 }
@@ -55,6 +59,7 @@ impl DisplayInContext for PlaceKind {
                 write!(f, "@{}", global.name)
             }
             PlaceKind::Field(place, index) => write!(f, "{place}.{index}"),
+            PlaceKind::Deref(place) => write!(f, "(*{place})"),
         }
     }
 }
@@ -133,6 +133,7 @@ impl ParserErrorEnv<'_> {
 
       Token::IntegerLiteral(int) => format!("integer literal `{}`", int),
 			Token::FloatLiteral(float) => format!("float literal `{}`", float),
+			Token::Decimal => "decimal literal".to_string(),
 			Token::StringLiteral(string) => format!("string literal `{}`", string),
 			Token::LongStringLiteral(string) => format!("string literal `{}`", string),
 
@@ -150,8 +151,17 @@ impl ParserErrorEnv<'_> {
 			Token::AsKw => "keyword `as`".to_string(),
 
 			Token::VarKw => "keyword `var`".to_string(),
+			Token::LetKw => "keyword `let`".to_string(),
 			Token::FuncKw => "keyword `func`".to_string(),
+			Token::InitKw => "keyword `init`".to_string(),
+			Token::MutatingKw => "keyword `mutating`".to_string(),
 			Token::StructKw => "keyword `struct`".to_string(),
+			Token::ClassKw => "keyword `class`".to_string(),
+			Token::EnumKw => "keyword `enum`".to_string(),
+			Token::ProtocolKw => "keyword `protocol`".to_string(),
+			Token::TypeAliasKw => "keyword `typealias`".to_string(),
+			Token::AssociatedTypeKw => "keyword `associatedtype`".to_string(),
+			Token::CaseKw => "keyword `case`".to_string(),
 
 			Token::ReturnKw => "keyword `return`".to_string(),
 			Token::BreakKw => "keyword `break`".to_string(),
@@ -159,19 +169,40 @@ impl ParserErrorEnv<'_> {
 
 			Token::IfKw => "keyword `if`".to_string(),
 			Token::ElseKw => "keyword `else`".to_string(),
+			Token::ForKw => "keyword `for`".to_string(),
+			Token::InKw => "keyword `in`".to_string(),
+			Token::LoopKw => "keyword `loop`".to_string(),
+			Token::RepeatKw => "keyword `repeat`".to_string(),
 			Token::WhileKw => "keyword `while`".to_string(),
+			Token::MatchKw => "keyword `match`".to_string(),
+
+			Token::NilKw => "keyword `nil`".to_string(),
+
+			Token::ThrowsKw => "keyword `throws`".to_string(),
+			Token::ThrowKw => "keyword `throw`".to_string(),
+			Token::TryKw => "keyword `try`".to_string(),
+			Token::ForceTryKw => "keyword `try!`".to_string(),
+			Token::DoKw => "keyword `do`".to_string(),
+			Token::CatchKw => "keyword `catch`".to_string(),
 
 			// Symbols
 			Token::OpenParen => "symbol `(`".to_string(),
 			Token::CloseParen => "symbol `)`".to_string(),
 			Token::OpenBrace => "symbol `{`".to_string(),
 			Token::CloseBrace => "symbol `}`".to_string(),
+			Token::OpenBracket => "symbol `[`".to_string(),
+			Token::CloseBracket => "symbol `]`".to_string(),
 
 			Token::Period => "symbol `.`".to_string(),
+			Token::HalfOpenRange => "operator `..<`".to_string(),
+			Token::ClosedRange => "operator `...`".to_string(),
 			Token::Comma => "symbol `,`".to_string(),
 			Token::Semicolon => "symbol `;`".to_string(),
 			Token::Colon => "symbol `:`".to_string(),
 			Token::Arrow => "symbol `->`".to_string(),
+			Token::FatArrow => "symbol `=>`".to_string(),
+			Token::Question => "symbol `?`".to_string(),
+			Token::OptionalChain => "symbol `?.`".to_string(),
 
 			Token::Equals => "symbol `=`".to_string(),
 
@@ -196,9 +227,12 @@ impl ParserErrorEnv<'_> {
 			Token::GreaterThanOrEqual => "operator `>=`".to_string(),
 			Token::EqualEqual => "operator `==`".to_string(),
 			Token::NotEquals => "operator `!=`".to_string(),
+			Token::Identical => "operator `===`".to_string(),
+			Token::NotIdentical => "operator `!==`".to_string(),
 
 			Token::LogicalAnd => "operator `&&`".to_string(),
 			Token::LogicalOr => "operator `||`".to_string(),
+			Token::Coalesce => "operator `??`".to_string(),
 
 			Token::PlusEquals => "operator `+=`".to_string(),
 			Token::MinusEquals => "operator `-=`".to_string(),
@@ -12,16 +12,20 @@ pub enum Token<'a> {
     Ident(&'a str),
 
     // Literals
-    #[regex("([0-9][0-9_]*)")]
     #[regex("(0[xX][0-9a-fA-F_]+)")]
     #[regex("(0[oO][0-7_]+)")]
     #[regex("(0[bB][01_]+)")]
     IntegerLiteral(&'a str),
 
-    #[regex("[0-9][_0-9]*[eE][+-]?[0-9][_0-9]*")]
-    #[regex("[0-9][_0-9]*[.]([0-9][_0-9]*)?([eE][+-]?[0-9][_0-9]*)?")]
+    #[regex("[0-9][_0-9]*[.]?[eE][+-]?[0-9][_0-9]*")]
+    #[regex("[0-9][_0-9]*[.][0-9][_0-9]*([eE][+-]?[0-9][_0-9]*)?")]
     FloatLiteral(&'a str),
 
+    /// Decimal integers, and floats ending in a period,
+    /// which are told apart in `lex_decimal`
+    #[regex("[0-9][0-9_]*", lex_decimal)]
+    Decimal,
+
     #[regex(r#"raw""#, |lex| lex_string(lex, true))]
     #[regex(r#"""#, |lex| lex_string(lex, false))]
     StringLiteral(&'a str),
@@ -50,13 +54,31 @@ pub enum Token<'a> {
 
     #[token("var")]
     VarKw,
+    #[token("let")]
+    LetKw,
     #[token("func")]
     FuncKw,
     #[token("struct")]
     StructKw,
+    #[token("class")]
+    ClassKw,
+    #[token("enum")]
+    EnumKw,
+    #[token("protocol")]
+    ProtocolKw,
+    #[token("typealias")]
+    TypeAliasKw,
+    #[token("associatedtype")]
+    AssociatedTypeKw,
+    #[token("case")]
+    CaseKw,
+    #[token("init")]
+    InitKw,
 
     #[token("static")]
     StaticKw,
+    #[token("mutating")]
+    MutatingKw,
 
     #[token("return")]
     ReturnKw,
@@ -69,8 +91,34 @@ pub enum Token<'a> {
     IfKw,
     #[token("else")]
     ElseKw,
+    #[token("for")]
+    ForKw,
+    #[token("in")]
+    InKw,
+    #[token("loop")]
+    LoopKw,
+    #[token("repeat")]
+    RepeatKw,
     #[token("while")]
     WhileKw,
+    #[token("match")]
+    MatchKw,
+
+    #[token("nil")]
+    NilKw,
+
+    #[token("throws")]
+    ThrowsKw,
+    #[token("throw")]
+    ThrowKw,
+    #[token("try")]
+    TryKw,
+    #[token("try!")]
+    ForceTryKw,
+    #[token("do")]
+    DoKw,
+    #[token("catch")]
+    CatchKw,
 
     // Symbols
     #[token("(")]
@@ -81,9 +129,17 @@ pub enum Token<'a> {
     OpenBrace,
     #[token("}")]
     CloseBrace,
+    #[token("[")]
+    OpenBracket,
+    #[token("]")]
+    CloseBracket,
 
     #[token(".")]
     Period,
+    #[token("..<")]
+    HalfOpenRange,
+    #[token("...")]
+    ClosedRange,
     #[token(",")]
     Comma,
     #[token(";")]
@@ -92,6 +148,12 @@ pub enum Token<'a> {
     Colon,
     #[token("->")]
     Arrow,
+    #[token("=>")]
+    FatArrow,
+    #[token("?")]
+    Question,
+    #[token("?.")]
+    OptionalChain,
 
     #[token("=")]
     Equals,
@@ -132,11 +194,17 @@ pub enum Token<'a> {
     EqualEqual,
     #[token("!=")]
     NotEquals,
+    #[token("===")]
+    Identical,
+    #[token("!==")]
+    NotIdentical,
 
     #[token("&&")]
     LogicalAnd,
     #[token("||")]
     LogicalOr,
+    #[token("??")]
+    Coalesce,
 
     #[token("+=")]
     PlusEquals,
@@ -167,6 +235,20 @@ pub enum Token<'a> {
     Comment,
 }
 
+/// A decimal followed by a single period is a float, like `3.`,
+/// but one followed by a range operator, like `0..<n`, is an integer
+fn lex_decimal<'a>(lexer: &mut logos::Lexer<'a, Token<'a>>) -> Token<'a> {
+    let remainder = lexer.remainder();
+
+    if remainder.starts_with('.') && !remainder.starts_with("..") {
+        lexer.bump('.'.len_utf8());
+
+        return Token::FloatLiteral(lexer.slice());
+    }
+
+    Token::IntegerLiteral(lexer.slice())
+}
+
 fn lex_string<'a>(
     lexer: &mut logos::Lexer<'a, Token<'a>>,
     raw: bool,
@@ -90,7 +90,9 @@ impl SourceFile {
 			.0
 			.iter()
 			.find(|(_, l)| **l == line + 1)
-			.map(|(pos, _)| pos.0)?
+			.map(|(pos, _)| pos.0)
+			// the last line might not end in a newline
+			.unwrap_or(self.end_pos.0)
 			- self.start_pos.0;
 
 		Some(&self.src.as_ref()?[start..end])
@@ -189,7 +191,12 @@ impl SourceMap {
 			// Get the beginning of the next line and the end of this one
 			let line_beginning = file.lines.start_of_last_line(base);
 			let next_beginning = file.lines.start_of_next_line(base);
-			let end = next_beginning - 1;
+			let end = if next_beginning == file.end_pos {
+				// the last line might not end in a newline
+				next_beginning
+			} else {
+				next_beginning - 1
+			};
 
 			// Get the length of the line
 			let end_of_line = CharPos(end.0.min(span.hi.0) - line_beginning.0);
@@ -254,7 +261,7 @@ impl FileLines {
 	pub fn get_column(&self, pos: BytePos) -> CharPos {
 		let line_start = self
 			.0
-			.range(..pos)
+			.range(..=pos)
 			.next_back()
 			.map(|(k, _)| k.0)
 			.unwrap_or(usize::MAX);
@@ -267,7 +274,7 @@ impl FileLines {
 	///
 	pub fn start_of_last_line(&self, pos: BytePos) -> BytePos {
 		self.0
-			.range(..pos)
+			.range(..=pos)
 			.next_back()
 			.map(|(k, _)| *k)
 			.unwrap_or(self.1)
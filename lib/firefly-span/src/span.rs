@@ -66,6 +66,16 @@ impl Span {
 	pub fn between(self, end: Span) -> Span {
 		Span::new(self.hi, end.lo)
 	}
+
+	/// Returns a span covering the last byte of `self`
+	///
+	/// ```text
+	///     { lorem ipsum }
+	///                   ^
+	/// ```
+	pub fn last(self) -> Span {
+		Span::new(BytePos(self.hi.0.saturating_sub(1).max(self.lo.0)), self.hi)
+	}
 }
 
 impl<T> Spanned<T> {
@@ -1,4 +1,4 @@
-use firefly_driver::{pass::{lower::{LinkPass, LowerCodePass, LowerDefsPass}, parse::ParsePass, hir_lower::LowerHirPass, IgnorePass}, Driver};
+use firefly_driver::{pass::{lower::{CheckDeclarationsPass, LinkPass, LowerCodePass, LowerDefsPass, LowerImportsPass}, parse::ParsePass, hir_lower::LowerHirPass}, Driver};
 
 fn main() {
     let mut driver = Driver::new();
@@ -7,10 +7,11 @@ fn main() {
     driver.run_pipeline((
         ParsePass,
         LinkPass,
+        LowerImportsPass,
         LowerDefsPass,
         LowerCodePass,
-        IgnorePass::new(),
+        CheckDeclarationsPass,
         LowerHirPass,
     ));
-    driver.output();
+    std::process::exit(driver.output());
 }
\ No newline at end of file